@@ -93,6 +93,84 @@ impl Bbox {
     }
 }
 
+/// An incremental accumulator of bounding boxes.
+///
+/// Starts out empty; the built [`Bbox`] is the union of the bounding boxes
+/// of everything added to the builder.
+///
+/// # Example
+///
+/// ```
+/// # use subgeom::*;
+/// # use subgeom::bbox::{Bbox, BboxBuilder};
+/// let bbox = BboxBuilder::new()
+///     .add_point(Point::new(-10, 5))
+///     .add_rect(Rect::new(Point::new(0, 0), Point::new(20, 10)))
+///     .build();
+/// assert_eq!(bbox, Bbox::new(Point::new(-10, 0), Point::new(20, 10)));
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct BboxBuilder {
+    bbox: Bbox,
+}
+
+impl Default for BboxBuilder {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BboxBuilder {
+    /// Creates a new, empty [`BboxBuilder`].
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            bbox: Bbox::empty(),
+        }
+    }
+
+    /// Adds the bounding box of `item` to the accumulated bounding box.
+    pub fn add(&mut self, item: impl BoundBox) -> &mut Self {
+        self.bbox = item.union(self.bbox);
+        self
+    }
+
+    /// Adds a [`Point`] to the accumulated bounding box.
+    #[inline]
+    pub fn add_point(&mut self, p: Point) -> &mut Self {
+        self.add(p)
+    }
+
+    /// Adds a [`Rect`] to the accumulated bounding box.
+    #[inline]
+    pub fn add_rect(&mut self, r: Rect) -> &mut Self {
+        self.add(r)
+    }
+
+    /// Adds the bounding boxes of all items in `iter` to the accumulated bounding box.
+    pub fn add_all<T: BoundBox>(&mut self, iter: impl IntoIterator<Item = T>) -> &mut Self {
+        for item in iter {
+            self.add(item);
+        }
+        self
+    }
+
+    /// Returns `true` if nothing with a non-empty bounding box has been added.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.bbox.is_empty()
+    }
+
+    /// Returns the accumulated bounding box.
+    ///
+    /// The result is empty if nothing with a non-empty bounding box has been added.
+    #[inline]
+    pub fn build(&self) -> Bbox {
+        self.bbox
+    }
+}
+
 impl From<Rect> for Bbox {
     fn from(r: Rect) -> Self {
         debug_assert!(r.p0.x <= r.p1.x);
@@ -207,10 +285,47 @@ impl BoundBox for Rect {
 impl BoundBox for Vec<Point> {
     fn bbox(&self) -> Bbox {
         // Take the union of all points in the vector
-        let mut bbox = Bbox::empty();
-        for pt in self {
-            bbox = bbox.union(pt.bbox());
+        BboxBuilder::new().add_all(self).build()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Path, Polygon};
+
+    #[test]
+    fn bbox_builder_matches_union() {
+        let shapes = vec![
+            Shape::Rect(Rect::new(Point::new(0, 0), Point::new(10, 10))),
+            Shape::Point(Point::new(-5, 3)),
+            Shape::Polygon(Polygon {
+                points: vec![Point::new(2, 2), Point::new(8, 20), Point::new(4, 25)],
+            }),
+            Shape::Path(Path {
+                points: vec![Point::new(0, -7), Point::new(15, -7)],
+                width: 2,
+            }),
+        ];
+
+        let mut manual = Bbox::empty();
+        for shape in shapes.iter() {
+            manual = shape.union(manual);
         }
-        bbox
+
+        let mut builder = BboxBuilder::new();
+        assert!(builder.is_empty());
+        for shape in shapes.iter() {
+            builder.add(shape);
+        }
+        assert!(!builder.is_empty());
+        assert_eq!(builder.build(), manual);
+        assert_eq!(
+            builder.build(),
+            Bbox::new(Point::new(-5, -7), Point::new(15, 25))
+        );
+
+        assert_eq!(BboxBuilder::new().add_all(&shapes).build(), manual);
+        assert!(BboxBuilder::new().build().is_empty());
     }
 }
@@ -16,21 +16,48 @@ use self::trim::Trim;
 
 pub mod bbox;
 pub mod orientation;
+pub mod polygon_set;
 pub mod ring;
 pub mod transform;
 pub mod trim;
 
+/// Specifies how [`snap_to_grid_with`] rounds positions that are not on the grid.
+#[derive(Copy, Clone, Eq, PartialEq, Default, Debug, Hash, Serialize, Deserialize)]
+pub enum SnapMode {
+    /// Round to the nearest multiple of the grid, with ties rounding up.
+    ///
+    /// Ties are broken towards positive infinity regardless of the sign of the position,
+    /// so snapping commutes with translation by a multiple of the grid.
+    #[default]
+    Nearest,
+    /// Round to the greatest multiple of the grid that is less than or equal to the position.
+    Down,
+    /// Round to the smallest multiple of the grid that is greater than or equal to the position.
+    Up,
+}
+
 /// Snaps `pos` to the nearest multiple of `grid`.
+///
+/// Equivalent to [`snap_to_grid_with`] with [`SnapMode::Nearest`].
 pub fn snap_to_grid(pos: i64, grid: i64) -> i64 {
+    snap_to_grid_with(pos, grid, SnapMode::Nearest)
+}
+
+/// Snaps `pos` to a multiple of `grid`, rounding according to `mode`.
+pub fn snap_to_grid_with(pos: i64, grid: i64, mode: SnapMode) -> i64 {
     assert!(grid > 0);
 
     let rem = pos.rem_euclid(grid);
     assert!(rem >= 0);
     assert!(rem < grid);
-    if rem <= grid / 2 {
-        pos - rem
-    } else {
-        pos + grid - rem
+    if rem == 0 {
+        return pos;
+    }
+    match mode {
+        SnapMode::Nearest if rem < grid - rem => pos - rem,
+        SnapMode::Nearest => pos + grid - rem,
+        SnapMode::Down => pos - rem,
+        SnapMode::Up => pos + grid - rem,
     }
 }
 
@@ -79,13 +106,24 @@ impl Point {
     }
     /// Creates a new [`Point`] shifted by `x` in the x-dimension and by `y` in the y-dimension.
     #[inline]
+    #[must_use]
     pub fn translated(&self, p: Point) -> Self {
         let mut pt = *self;
         pt.translate(p);
         pt
     }
+    /// Creates a new [`Point`] shifted by `p`, or [`None`] if either coordinate overflows.
+    #[inline]
+    #[must_use]
+    pub fn checked_translated(&self, p: Point) -> Option<Self> {
+        Some(Self::new(
+            self.x.checked_add(p.x)?,
+            self.y.checked_add(p.y)?,
+        ))
+    }
     /// Creates a new point scaled by `p.x` in the x-dimension and by `p.y` in the y-dimension.
     #[inline]
+    #[must_use]
     pub fn scaled(&self, p: Point) -> Self {
         let mut pt = *self;
         pt.scale(p);
@@ -108,6 +146,38 @@ impl Point {
         let y = snap_to_grid(self.y, grid);
         Self { x: self.x, y }
     }
+
+    /// Snaps the x-coordinate to a grid of `gx` and the y-coordinate to a grid of `gy`.
+    #[inline]
+    pub fn snap_to_grid_xy(&self, gx: i64, gy: i64) -> Self {
+        self.snap_x_to_grid(gx).snap_y_to_grid(gy)
+    }
+
+    /// Snaps both coordinates to the given grid, rounding according to `mode`.
+    #[inline]
+    pub fn snap_to_grid_with(&self, grid: i64, mode: SnapMode) -> Self {
+        Self::new(
+            snap_to_grid_with(self.x, grid, mode),
+            snap_to_grid_with(self.y, grid, mode),
+        )
+    }
+
+    /// Rotates this point counterclockwise about `center` by `quarter_turns` multiples of 90 degrees.
+    ///
+    /// Negative values of `quarter_turns` rotate clockwise.
+    /// Unlike [`Transformation::rotate`], the rotation is performed with exact integer arithmetic.
+    pub fn rotate_about(&self, center: Point, quarter_turns: i32) -> Point {
+        let dx = self.x - center.x;
+        let dy = self.y - center.y;
+        let (dx, dy) = match quarter_turns.rem_euclid(4) {
+            0 => (dx, dy),
+            1 => (-dy, dx),
+            2 => (-dx, -dy),
+            3 => (dy, -dx),
+            _ => unreachable!(),
+        };
+        Point::new(center.x + dx, center.y + dy)
+    }
 }
 
 impl Trim<Rect> for Point {
@@ -242,6 +312,10 @@ impl Span {
     }
 
     /// Creates a new [`Span`] expanded by `amount` in the direction indicated by `pos`.
+    ///
+    /// Like other integer arithmetic, this panics on overflow in debug builds
+    /// and wraps in release builds. See [`Span::checked_expand`] for a checked variant.
+    #[must_use]
     pub fn expand(mut self, pos: bool, amount: i64) -> Self {
         if pos {
             self.stop += amount;
@@ -251,13 +325,39 @@ impl Span {
         self
     }
 
+    /// Creates a new [`Span`] expanded by `amount` in the direction indicated by `pos`,
+    /// or [`None`] if the expanded endpoint overflows.
+    #[must_use]
+    pub fn checked_expand(mut self, pos: bool, amount: i64) -> Option<Self> {
+        if pos {
+            self.stop = self.stop.checked_add(amount)?;
+        } else {
+            self.start = self.start.checked_sub(amount)?;
+        }
+        Some(self)
+    }
+
     /// Creates a new [`Span`] expanded by `amount` in both directions.
+    ///
+    /// Like other integer arithmetic, this panics on overflow in debug builds
+    /// and wraps in release builds. See [`Span::checked_expand_all`] for a checked variant.
+    #[must_use]
     pub fn expand_all(mut self, amount: i64) -> Self {
         self.stop += amount;
         self.start -= amount;
         self
     }
 
+    /// Creates a new [`Span`] expanded by `amount` in both directions,
+    /// or [`None`] if either endpoint overflows.
+    #[must_use]
+    pub fn checked_expand_all(self, amount: i64) -> Option<Self> {
+        Some(Self {
+            start: self.start.checked_sub(amount)?,
+            stop: self.stop.checked_add(amount)?,
+        })
+    }
+
     /// Gets the starting ([`Sign::Neg`]) or stopping ([`Sign::Pos`]) point of a span.
     #[inline]
     pub fn point(&self, sign: Sign) -> i64 {
@@ -365,6 +465,7 @@ impl Span {
         merged_spans.into_iter()
     }
 
+    #[must_use]
     pub fn union(self, other: Self) -> Self {
         use std::cmp::{max, min};
         Self {
@@ -377,7 +478,23 @@ impl Span {
         self.union(other) == self
     }
 
+    /// Checks if the span contains the coordinate `x`.
+    ///
+    /// Containment is inclusive of the span's endpoints,
+    /// consistent with [`ShapeTrait::contains`] for [`Rect`]s.
+    #[inline]
+    pub fn contains_point(&self, x: i64) -> bool {
+        self.start <= x && x <= self.stop
+    }
+
+    /// Checks if the coordinate `x` lies strictly between the span's endpoints.
+    #[inline]
+    pub fn contains_point_exclusive(&self, x: i64) -> bool {
+        self.start < x && x < self.stop
+    }
+
     /// Returns a new [`Span`] representing the union of the current span with the given point.
+    #[must_use]
     pub fn add_point(self, pos: i64) -> Self {
         use std::cmp::{max, min};
         Self {
@@ -391,6 +508,7 @@ impl Span {
     /// Behavior is controlled by the given [`Sign`]:
     /// * If `side` is [`Sign::Pos`], shrinks from the positive end (ie. decreases the `stop`).
     /// * If `side` is [`Sign::Neg`], shrinks from the negative end (ie. increases the `start`).
+    #[must_use]
     pub fn shrink(self, side: Sign, amount: i64) -> Self {
         assert!(self.length() >= amount);
         match side {
@@ -399,6 +517,7 @@ impl Span {
         }
     }
 
+    #[must_use]
     pub fn shrink_all(self, amount: i64) -> Self {
         assert!(self.length() >= 2 * amount);
         Self {
@@ -407,6 +526,11 @@ impl Span {
         }
     }
 
+    /// Creates a new [`Span`] shifted by `amount`.
+    ///
+    /// Like other integer arithmetic, this panics on overflow in debug builds
+    /// and wraps in release builds. See [`Span::checked_translate`] for a checked variant.
+    #[must_use]
     pub fn translate(self, amount: i64) -> Self {
         Self {
             start: self.start + amount,
@@ -414,6 +538,15 @@ impl Span {
         }
     }
 
+    /// Creates a new [`Span`] shifted by `amount`, or [`None`] if either endpoint overflows.
+    #[must_use]
+    pub fn checked_translate(self, amount: i64) -> Option<Self> {
+        Some(Self {
+            start: self.start.checked_add(amount)?,
+            stop: self.stop.checked_add(amount)?,
+        })
+    }
+
     pub fn min_distance(self, other: Span) -> i64 {
         std::cmp::max(
             0,
@@ -512,6 +645,20 @@ impl Dir {
             Self::Vert => "v",
         }
     }
+
+    /// Returns an iterator over all directions, starting with [`Dir::Horiz`].
+    #[inline]
+    pub fn all() -> impl Iterator<Item = Dir> {
+        [Self::Horiz, Self::Vert].into_iter()
+    }
+
+    /// Evaluates `f` in each direction.
+    ///
+    /// Returns the results for [`Dir::Horiz`] and [`Dir::Vert`], in that order.
+    #[inline]
+    pub fn both<T>(f: impl Fn(Dir) -> T) -> (T, T) {
+        (f(Self::Horiz), f(Self::Vert))
+    }
 }
 
 impl Default for Dir {
@@ -621,6 +768,13 @@ impl Side {
             Dir::Vert => [Side::Bot, Side::Top].into_iter(),
         }
     }
+
+    /// Returns an iterator over all sides, in the order in which
+    /// the variants of [`Side`] are declared.
+    #[inline]
+    pub fn all() -> impl Iterator<Item = Side> {
+        [Side::Top, Side::Right, Side::Bot, Side::Left].into_iter()
+    }
 }
 
 impl std::ops::Not for Side {
@@ -681,6 +835,13 @@ impl<T> Sides<T> {
             inner: self.inner.map(f),
         }
     }
+
+    /// Returns an iterator over each side and its associated value.
+    ///
+    /// Sides are visited in the order given by [`Side::all`].
+    pub fn iter(&self) -> impl Iterator<Item = (Side, &T)> {
+        Side::all().map(move |side| (side, &self[side]))
+    }
 }
 
 impl<T> std::ops::Index<Side> for Sides<T> {
@@ -863,6 +1024,19 @@ pub enum Corner {
 }
 
 impl Corner {
+    /// Returns an iterator over all corners, in the order in which
+    /// the variants of [`Corner`] are declared.
+    #[inline]
+    pub fn all() -> impl Iterator<Item = Corner> {
+        [
+            Corner::LowerLeft,
+            Corner::LowerRight,
+            Corner::UpperLeft,
+            Corner::UpperRight,
+        ]
+        .into_iter()
+    }
+
     pub fn side(&self, dir: Dir) -> Side {
         use Corner::*;
         use Dir::*;
@@ -888,6 +1062,32 @@ pub struct Path {
     pub points: Vec<Point>,
     pub width: usize,
 }
+impl Path {
+    /// Returns the total length of the path's segments.
+    ///
+    /// The length of each non-Manhattan segment is rounded to the nearest integer.
+    pub fn length(&self) -> i64 {
+        self.points
+            .windows(2)
+            .map(|seg| {
+                let dx = seg[1].x - seg[0].x;
+                let dy = seg[1].y - seg[0].y;
+                if dx == 0 || dy == 0 {
+                    dx.abs() + dy.abs()
+                } else {
+                    ((dx as f64).hypot(dy as f64)).round() as i64
+                }
+            })
+            .sum()
+    }
+
+    /// Returns the area covered by the path, computed as its [length](Path::length) times its width.
+    ///
+    /// Paths are assumed to have flush ends, so the area does not include any end extensions.
+    pub fn area(&self) -> i64 {
+        self.length() * self.width as i64
+    }
+}
 impl Translate for Path {
     fn translate(&mut self, p: Point) {
         for pt in self.points.iter_mut() {
@@ -895,6 +1095,94 @@ impl Translate for Path {
         }
     }
 }
+
+impl Trim<Rect> for Path {
+    type Output = Vec<Path>;
+
+    /// Clips the path's centerline to `bounds`.
+    ///
+    /// Segments are split where they cross the boundary of `bounds`, and portions lying
+    /// outside `bounds` are dropped. Since a path may leave and re-enter `bounds`, the
+    /// result may consist of several disjoint paths, each with the original width.
+    /// The width itself is not clipped, so the clipped paths may extend up to half their
+    /// width beyond `bounds` on either side of their centerlines.
+    ///
+    /// Returns [`None`] if no segment of the path has a portion of non-zero length
+    /// within `bounds`.
+    fn trim(&self, bounds: &Rect) -> Option<Self::Output> {
+        let mut paths: Vec<Path> = Vec::new();
+        let mut continues = false;
+
+        for seg in self.points.windows(2) {
+            let (p, q) = (seg[0], seg[1]);
+            let clipped = clip_segment(p, q, bounds).filter(|(a, b)| a != b);
+            match clipped {
+                Some((a, b)) => {
+                    if continues && a == p {
+                        paths.last_mut().unwrap().points.push(b);
+                    } else {
+                        paths.push(Path {
+                            points: vec![a, b],
+                            width: self.width,
+                        });
+                    }
+                    continues = b == q;
+                }
+                None => continues = false,
+            }
+        }
+
+        if paths.is_empty() {
+            None
+        } else {
+            Some(paths)
+        }
+    }
+}
+
+/// Clips the segment from `p` to `q` to `bounds` using the Liang-Barsky algorithm.
+///
+/// Returns the endpoints of the clipped segment, in the same direction as the original,
+/// or [`None`] if the segment lies entirely outside `bounds`.
+fn clip_segment(p: Point, q: Point, bounds: &Rect) -> Option<(Point, Point)> {
+    let (dx, dy) = ((q.x - p.x) as f64, (q.y - p.y) as f64);
+    let (mut t0, mut t1) = (0f64, 1f64);
+
+    for (dp, dist) in [
+        (-dx, p.x - bounds.left()),
+        (dx, bounds.right() - p.x),
+        (-dy, p.y - bounds.bottom()),
+        (dy, bounds.top() - p.y),
+    ] {
+        let dist = dist as f64;
+        if dp == 0. {
+            if dist < 0. {
+                return None;
+            }
+        } else {
+            let t = dist / dp;
+            if dp < 0. {
+                t0 = t0.max(t);
+            } else {
+                t1 = t1.min(t);
+            }
+        }
+    }
+    if t0 > t1 {
+        return None;
+    }
+
+    let at = |t: f64| {
+        if t == 0. {
+            p
+        } else if t == 1. {
+            q
+        } else {
+            Point::new(p.x + (dx * t).round() as i64, p.y + (dy * t).round() as i64)
+        }
+    };
+    Some((at(t0), at(t1)))
+}
 /// A closed n-sided polygon with arbitrary number of vertices.
 ///
 /// Closure from the last point back to the first is implied;
@@ -903,6 +1191,93 @@ impl Translate for Path {
 pub struct Polygon {
     pub points: Vec<Point>,
 }
+impl Polygon {
+    /// Returns the area enclosed by the polygon, computed using the shoelace formula.
+    ///
+    /// The area is non-negative regardless of the orientation of the vertices.
+    /// Polygons whose area is not an integer (which cannot occur for Manhattan polygons)
+    /// have their area rounded down.
+    pub fn area(&self) -> i64 {
+        self.twice_signed_area().abs() / 2
+    }
+
+    /// Returns twice the signed area of the polygon.
+    ///
+    /// The result is positive if the vertices are in counterclockwise order,
+    /// and negative if they are in clockwise order.
+    fn twice_signed_area(&self) -> i64 {
+        let n = self.points.len();
+        (0..n)
+            .map(|i| {
+                let p0 = self.points[i];
+                let p1 = self.points[(i + 1) % n];
+                p0.x * p1.y - p1.x * p0.y
+            })
+            .sum()
+    }
+
+    /// Returns the centroid (center of mass) of the area enclosed by the polygon.
+    ///
+    /// The centroid is computed from the signed area, so it does not depend on the winding
+    /// direction of the vertices. If the polygon has zero area, the average of its vertices
+    /// is returned instead. Coordinates are rounded to the nearest integer, with ties rounding
+    /// away from zero.
+    ///
+    /// The centroid of a convex polygon always lies inside it, but the centroid of a
+    /// non-convex polygon may not. For example, the centroid of a U-shaped polygon with thin
+    /// arms and a thin base lies in the notch between its arms. Callers placing labels or
+    /// markers on non-convex polygons should check the result with [`ShapeTrait::contains`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the polygon has no vertices.
+    pub fn centroid(&self) -> Point {
+        assert!(
+            !self.points.is_empty(),
+            "cannot compute the centroid of a polygon with no vertices"
+        );
+        let n = self.points.len();
+        let twice_area = self.twice_signed_area();
+        if twice_area == 0 {
+            let (x, y) = self
+                .points
+                .iter()
+                .fold((0, 0), |(x, y), p| (x + p.x, y + p.y));
+            return Point::new(
+                (x as f64 / n as f64).round() as i64,
+                (y as f64 / n as f64).round() as i64,
+            );
+        }
+
+        let (cx, cy) = (0..n).fold((0, 0), |(cx, cy), i| {
+            let p0 = self.points[i];
+            let p1 = self.points[(i + 1) % n];
+            let cross = p0.x * p1.y - p1.x * p0.y;
+            (cx + (p0.x + p1.x) * cross, cy + (p0.y + p1.y) * cross)
+        });
+        let denom = 3. * twice_area as f64;
+        Point::new(
+            (cx as f64 / denom).round() as i64,
+            (cy as f64 / denom).round() as i64,
+        )
+    }
+
+    /// Returns `true` if the vertices of the polygon are in clockwise order.
+    ///
+    /// The winding direction is determined by the sign of the polygon's signed area.
+    /// Degenerate polygons with zero area are not considered clockwise.
+    pub fn is_clockwise(&self) -> bool {
+        self.twice_signed_area() < 0
+    }
+
+    /// Reverses the order of the polygon's vertices if they are in clockwise order,
+    /// so that they are in counterclockwise order.
+    pub fn ensure_ccw(&mut self) {
+        if self.is_clockwise() {
+            self.points.reverse();
+        }
+    }
+}
 impl Translate for Polygon {
     fn translate(&mut self, p: Point) {
         for pt in self.points.iter_mut() {
@@ -911,6 +1286,218 @@ impl Translate for Polygon {
     }
 }
 
+/// Returns the point at which the segment from `p` to `q` crosses the line
+/// `x = c` (if `dir` is [`Dir::Horiz`]) or `y = c` (if `dir` is [`Dir::Vert`]).
+///
+/// The segment must not be parallel to the line.
+/// Non-integral intersections are rounded to the nearest point.
+fn segment_crossing(p: Point, q: Point, dir: Dir, c: i64) -> Point {
+    let (pc, qc, po, qo) = match dir {
+        Dir::Horiz => (p.x, q.x, p.y, q.y),
+        Dir::Vert => (p.y, q.y, p.x, q.x),
+    };
+    debug_assert_ne!(pc, qc);
+    let t = (c - pc) as f64 / (qc - pc) as f64;
+    let other = po + ((qo - po) as f64 * t).round() as i64;
+    match dir {
+        Dir::Horiz => Point::new(c, other),
+        Dir::Vert => Point::new(other, c),
+    }
+}
+
+impl Polygon {
+    /// Clips the polygon to `bounds` using the Sutherland-Hodgman algorithm,
+    /// returning the vertices of the clipped polygon.
+    ///
+    /// Disjoint pieces of a clipped concave polygon are joined by zero-width edges
+    /// along the boundary of `bounds`.
+    ///
+    /// Returns [`None`] if the clipped polygon has fewer than three distinct vertices.
+    fn clip_points(&self, bounds: &Rect) -> Option<Vec<Point>> {
+        let mut points = self.points.clone();
+
+        // Clip against each edge of the bounds in turn. Each edge is given by the
+        // direction of the coordinate it constrains, its position, and whether
+        // points inside the bounds lie above (true) or below (false) it.
+        for (dir, c, above) in [
+            (Dir::Horiz, bounds.left(), true),
+            (Dir::Horiz, bounds.right(), false),
+            (Dir::Vert, bounds.bottom(), true),
+            (Dir::Vert, bounds.top(), false),
+        ] {
+            let inside = |p: &Point| {
+                let coord = p.coord(dir);
+                if above {
+                    coord >= c
+                } else {
+                    coord <= c
+                }
+            };
+
+            let input = std::mem::take(&mut points);
+            for (i, &cur) in input.iter().enumerate() {
+                let prev = input[(i + input.len() - 1) % input.len()];
+                match (inside(&prev), inside(&cur)) {
+                    (true, true) => points.push(cur),
+                    (false, true) => {
+                        points.push(segment_crossing(prev, cur, dir, c));
+                        points.push(cur);
+                    }
+                    (true, false) => points.push(segment_crossing(prev, cur, dir, c)),
+                    (false, false) => {}
+                }
+            }
+        }
+
+        points.dedup();
+        while points.len() > 1 && points.first() == points.last() {
+            points.pop();
+        }
+        (points.len() >= 3).then_some(points)
+    }
+}
+
+impl Trim<Rect> for Polygon {
+    type Output = Vec<Self>;
+
+    /// Clips the polygon to `bounds` using the Sutherland-Hodgman algorithm.
+    ///
+    /// Vertices are added where the polygon crosses the boundary of `bounds`.
+    /// A polygon lying entirely within `bounds` is returned unchanged.
+    ///
+    /// Clipping a concave polygon may split it into several disjoint pieces, which
+    /// Sutherland-Hodgman joins with zero-width edges running along the boundary of
+    /// `bounds`. These edges are removed, and each piece is returned as its own polygon.
+    ///
+    /// Returns [`None`] if no piece has at least three distinct vertices.
+    fn trim(&self, bounds: &Rect) -> Option<Self::Output> {
+        let points = self.clip_points(bounds)?;
+        let pieces = split_clipped_polygon(points, bounds, &self.points);
+        (!pieces.is_empty()).then_some(pieces)
+    }
+}
+
+/// Splits the output of Sutherland-Hodgman clipping into its disjoint pieces.
+///
+/// Edges lying on the boundary of `bounds` are split at every clipped vertex on the
+/// boundary, and pairs of opposing edges (the zero-width bridges between pieces) are
+/// removed. The remaining edges are then traced into closed loops. Vertices that became
+/// redundant, other than those of the `original` polygon, are removed.
+fn split_clipped_polygon(points: Vec<Point>, bounds: &Rect, original: &[Point]) -> Vec<Polygon> {
+    let sides = [
+        (Dir::Horiz, bounds.left()),
+        (Dir::Horiz, bounds.right()),
+        (Dir::Vert, bounds.bottom()),
+        (Dir::Vert, bounds.top()),
+    ];
+    let on_side = |p: Point, q: Point| {
+        sides
+            .iter()
+            .find(|&&(dir, c)| p.coord(dir) == c && q.coord(dir) == c)
+            .copied()
+    };
+
+    let n = points.len();
+    let mut edges = Vec::with_capacity(n);
+    for (i, &p) in points.iter().enumerate() {
+        let q = points[(i + 1) % n];
+        match on_side(p, q) {
+            Some((dir, _)) => {
+                let along = |v: &Point| v.coord(!dir);
+                let (lo, hi) = (along(&p).min(along(&q)), along(&p).max(along(&q)));
+                let mut splits = points
+                    .iter()
+                    .filter(|v| v.coord(dir) == p.coord(dir) && lo < along(v) && along(v) < hi)
+                    .copied()
+                    .collect::<Vec<_>>();
+                splits.sort_by_key(|v| (along(v) - along(&p)).abs());
+                splits.dedup();
+                let mut prev = p;
+                for v in splits {
+                    edges.push((prev, v));
+                    prev = v;
+                }
+                edges.push((prev, q));
+            }
+            None => edges.push((p, q)),
+        }
+    }
+
+    let mut alive = vec![true; edges.len()];
+    let mut bridged = false;
+    for (i, &(p, q)) in edges.iter().enumerate() {
+        if !alive[i] {
+            continue;
+        }
+        if let Some(j) = (0..edges.len()).find(|&j| alive[j] && edges[j] == (q, p)) {
+            alive[i] = false;
+            alive[j] = false;
+            bridged = true;
+        }
+    }
+    if !bridged {
+        return vec![Polygon { points }];
+    }
+
+    // Trace the remaining edges into loops, preferring to continue in the original
+    // vertex order. Whenever a vertex is revisited, the loop back to it is split off.
+    let mut pieces = Vec::new();
+    let mut used = alive.iter().map(|alive| !alive).collect::<Vec<_>>();
+    for start in 0..edges.len() {
+        if used[start] {
+            continue;
+        }
+        let mut path = vec![edges[start].0];
+        let mut cur = start;
+        loop {
+            used[cur] = true;
+            let q = edges[cur].1;
+            match path.iter().position(|&v| v == q) {
+                Some(pos) => {
+                    pieces.push(path.split_off(pos));
+                    path.push(q);
+                }
+                None => path.push(q),
+            }
+            let next = (1..=edges.len())
+                .map(|k| (cur + k) % edges.len())
+                .find(|&j| !used[j] && edges[j].0 == q);
+            match next {
+                Some(next) => cur = next,
+                None => break,
+            }
+        }
+    }
+
+    pieces
+        .into_iter()
+        .filter_map(|mut points| {
+            // Remove vertices introduced by clipping that lie in the middle of a straight edge.
+            let redundant = |points: &[Point], i: usize| {
+                let len = points.len();
+                let (prev, v, next) = (
+                    points[(i + len - 1) % len],
+                    points[i],
+                    points[(i + 1) % len],
+                );
+                let (d0, d1) = (v - prev, next - v);
+                d0.x * d1.y == d0.y * d1.x
+                    && d0.x * d1.x + d0.y * d1.y > 0
+                    && !original.contains(&v)
+            };
+            while points.len() >= 3 {
+                match (0..points.len()).find(|&i| redundant(&points, i)) {
+                    Some(i) => {
+                        points.remove(i);
+                    }
+                    None => break,
+                }
+            }
+            (points.len() >= 3).then_some(Polygon { points })
+        })
+        .collect()
+}
+
 /// An axis-aligned rectangle, specified by lower-left and upper-right corners.
 #[derive(Debug, Default, Copy, Clone, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
 pub struct Rect {
@@ -928,6 +1515,27 @@ impl Rect {
         Self::new(Point::zero(), Point::new(dims.w(), dims.h()))
     }
 
+    /// Creates a rectangle with the given dimensions, positioned so that `anchor` lies at `at`.
+    ///
+    /// * [`Corner::LowerLeft`] places the lower-left corner at `at`, so the rectangle extends
+    /// up and to the right. The other corners similarly place the named corner at `at`.
+    /// * [`Anchor::Center`] centers the rectangle on `at`. If a dimension is odd,
+    /// the extra unit lies on the upper or right side.
+    ///
+    /// The caller should ensure that `dims.w()` and `dims.h()` are non-negative.
+    /// See [`Dims`] for more information.
+    pub fn from_dims_anchored(dims: Dims, anchor: impl Into<Anchor>, at: Point) -> Self {
+        let (w, h) = (dims.w(), dims.h());
+        let p0 = match anchor.into() {
+            Anchor::Corner(Corner::LowerLeft) => at,
+            Anchor::Corner(Corner::LowerRight) => Point::new(at.x - w, at.y),
+            Anchor::Corner(Corner::UpperLeft) => Point::new(at.x, at.y - h),
+            Anchor::Corner(Corner::UpperRight) => Point::new(at.x - w, at.y - h),
+            Anchor::Center => Point::new(at.x - w / 2, at.y - h / 2),
+        };
+        Self::new(p0, Point::new(p0.x + w, p0.y + h))
+    }
+
     /// Returns the center point of the rectangle.
     pub fn center(&self) -> Point {
         Point::new((self.p0.x + self.p1.x) / 2, (self.p0.y + self.p1.y) / 2)
@@ -990,6 +1598,7 @@ impl Rect {
     }
 
     /// Returns a [`Rect`] with the given `hspan` and the same vertical span.
+    #[must_use]
     pub fn with_hspan(self, hspan: Span) -> Self {
         Rect::new(
             Point::new(hspan.start(), self.p0.y),
@@ -998,6 +1607,7 @@ impl Rect {
     }
 
     /// Returns a [`Rect`] with the given `vspan` and the same horizontal span.
+    #[must_use]
     pub fn with_vspan(self, vspan: Span) -> Self {
         Rect::new(
             Point::new(self.p0.x, vspan.start()),
@@ -1007,6 +1617,7 @@ impl Rect {
 
     /// Returns a [`Rect`] with the given `span` in the given `dir`, and the current span in the
     /// other direction.
+    #[must_use]
     pub fn with_span(self, span: Span, dir: Dir) -> Self {
         match dir {
             Dir::Vert => self.with_vspan(span),
@@ -1037,6 +1648,40 @@ impl Rect {
         self.width() * self.height()
     }
 
+    /// Returns the intersection of this rectangle with `other`,
+    /// or [`None`] if the two rectangles do not overlap.
+    ///
+    /// Rectangles that only share an edge or a corner do not overlap,
+    /// since their intersection has zero area.
+    /// Use [`Rect::intersection_inclusive`] to also obtain such intersections.
+    pub fn intersection(&self, other: Rect) -> Option<Rect> {
+        self.intersection_inclusive(other)
+            .filter(|r| r.width() > 0 && r.height() > 0)
+    }
+
+    /// Returns the intersection of this rectangle with `other`,
+    /// or [`None`] if the two rectangles are disjoint.
+    ///
+    /// Unlike [`Rect::intersection`], rectangles that only share an edge or a corner
+    /// intersect in a rectangle of zero width or height.
+    pub fn intersection_inclusive(&self, other: Rect) -> Option<Rect> {
+        let p0 = Point::new(self.p0.x.max(other.p0.x), self.p0.y.max(other.p0.y));
+        let p1 = Point::new(self.p1.x.min(other.p1.x), self.p1.y.min(other.p1.y));
+        if p0.x > p1.x || p0.y > p1.y {
+            return None;
+        }
+        Some(Rect { p0, p1 })
+    }
+
+    /// Returns the smallest rectangle containing both this rectangle and `other`.
+    #[must_use]
+    pub fn union_rect(&self, other: Rect) -> Rect {
+        Rect {
+            p0: Point::new(self.p0.x.min(other.p0.x), self.p0.y.min(other.p0.y)),
+            p1: Point::new(self.p1.x.max(other.p1.x), self.p1.y.max(other.p1.y)),
+        }
+    }
+
     /// Returns the lower edge of the rectangle in the [`Dir`] `dir.
     pub fn lower_edge(&self, dir: Dir) -> i64 {
         self.span(dir).start()
@@ -1131,7 +1776,11 @@ impl Rect {
     }
 
     /// Expands the rectangle by `amount` on all sides.
+    ///
+    /// Like other integer arithmetic, this panics on overflow in debug builds
+    /// and wraps in release builds. See [`Rect::checked_expand`] for a checked variant.
     #[inline]
+    #[must_use]
     pub fn expand(&self, amount: i64) -> Self {
         Self::new(
             Point::new(self.p0.x - amount, self.p0.y - amount),
@@ -1139,8 +1788,37 @@ impl Rect {
         )
     }
 
+    /// Expands the rectangle by `amount` on all sides,
+    /// or returns [`None`] if any coordinate overflows.
+    #[must_use]
+    pub fn checked_expand(&self, amount: i64) -> Option<Self> {
+        Some(Self::new(
+            Point::new(
+                self.p0.x.checked_sub(amount)?,
+                self.p0.y.checked_sub(amount)?,
+            ),
+            Point::new(
+                self.p1.x.checked_add(amount)?,
+                self.p1.y.checked_add(amount)?,
+            ),
+        ))
+    }
+
+    /// Translates the rectangle by `p`, or returns [`None`] if any coordinate overflows.
+    ///
+    /// The unchecked equivalent is [`TranslateOwned::translate_owned`](transform::TranslateOwned::translate_owned),
+    /// which panics on overflow in debug builds and wraps in release builds.
+    #[must_use]
+    pub fn checked_translate(&self, p: Point) -> Option<Self> {
+        Some(Self::new(
+            self.p0.checked_translated(p)?,
+            self.p1.checked_translated(p)?,
+        ))
+    }
+
     /// Shrinks the rectangle by `amount` on all sides.
     #[inline]
+    #[must_use]
     pub fn shrink(&self, amount: i64) -> Self {
         assert!(2 * amount <= self.width());
         assert!(2 * amount <= self.height());
@@ -1152,6 +1830,7 @@ impl Rect {
 
     /// Expands the rectangle by `amount` on both sides associated with the direction `dir`.
     #[inline]
+    #[must_use]
     pub fn expand_dir(&self, dir: Dir, amount: i64) -> Self {
         match dir {
             Dir::Horiz => Self::new(
@@ -1167,6 +1846,7 @@ impl Rect {
 
     /// Expands the rectangle by `amount` on the given side.
     #[inline]
+    #[must_use]
     pub fn expand_side(&self, side: Side, amount: i64) -> Self {
         match side {
             Side::Top => Self::new(
@@ -1188,6 +1868,42 @@ impl Rect {
         }
     }
 
+    /// Expands each side of the rectangle by the corresponding amount in `amounts`.
+    ///
+    /// Negative amounts move the corresponding side inward.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the resulting rectangle would have negative width or height.
+    #[must_use]
+    pub fn expand_sides(&self, amounts: Sides<i64>) -> Self {
+        let p0 = Point::new(
+            self.p0.x - amounts[Side::Left],
+            self.p0.y - amounts[Side::Bot],
+        );
+        let p1 = Point::new(
+            self.p1.x + amounts[Side::Right],
+            self.p1.y + amounts[Side::Top],
+        );
+        assert!(
+            p0.x <= p1.x && p0.y <= p1.y,
+            "expanding {self:?} by {amounts:?} would result in negative width or height"
+        );
+        Self { p0, p1 }
+    }
+
+    /// Shrinks each side of the rectangle by the corresponding amount in `amounts`.
+    ///
+    /// Negative amounts move the corresponding side outward.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the resulting rectangle would have negative width or height.
+    #[must_use]
+    pub fn shrink_sides(&self, amounts: Sides<i64>) -> Self {
+        self.expand_sides(amounts.map(|_, amount| -amount))
+    }
+
     /// Expands this rectangle by the given dimensions.
     ///
     /// The exact behavior depends on the provided [`ExpandMode`]:
@@ -1205,6 +1921,7 @@ impl Rect {
     /// the right edge by `dims.w()`.
     ///
     /// See [`Dims`] for more information.
+    #[must_use]
     pub fn expand_dims(self, dims: Dims, mode: ExpandMode) -> Self {
         use ExpandMode::*;
         let left = match mode {
@@ -1243,6 +1960,21 @@ impl Rect {
         }
     }
 
+    /// Returns the four corners of this rectangle in counterclockwise order,
+    /// starting from the lower-left corner.
+    ///
+    /// That is, the corners are returned in the order lower-left, lower-right,
+    /// upper-right, upper-left. Note that this differs from the order of [`Corner::all`].
+    #[inline]
+    pub fn corners(&self) -> [Point; 4] {
+        [
+            self.corner(Corner::LowerLeft),
+            self.corner(Corner::LowerRight),
+            self.corner(Corner::UpperRight),
+            self.corner(Corner::UpperLeft),
+        ]
+    }
+
     /// Grows this rectangle by a factor of 2 on the given [`Side`].
     ///
     /// Sometimes useful for half-track geometry.
@@ -1290,6 +2022,67 @@ impl Rect {
         Self::new(self.p0.snap_to_grid(grid), self.p1.snap_to_grid(grid))
     }
 
+    /// Snaps the corners of this rectangle to a grid of `gx` in the
+    /// x-direction and a grid of `gy` in the y-direction.
+    ///
+    /// Note that the rectangle may have zero area after snapping.
+    #[inline]
+    pub fn snap_to_grid_xy(&self, gx: i64, gy: i64) -> Self {
+        Self::new(
+            self.p0.snap_to_grid_xy(gx, gy),
+            self.p1.snap_to_grid_xy(gx, gy),
+        )
+    }
+
+    /// Snaps the corners of this rectangle to the given grid, rounding according to `mode`.
+    ///
+    /// Both corners are rounded in the same direction, so [`SnapMode::Down`]
+    /// and [`SnapMode::Up`] translate the rectangle toward negative or positive
+    /// infinity, respectively. Note that the rectangle may have zero area after snapping.
+    #[inline]
+    pub fn snap_to_grid_with(&self, grid: i64, mode: SnapMode) -> Self {
+        Self::new(
+            self.p0.snap_to_grid_with(grid, mode),
+            self.p1.snap_to_grid_with(grid, mode),
+        )
+    }
+
+    /// Returns an iterator over every point inside this rectangle, inclusive of its edges,
+    /// whose coordinates are both multiples of `grid`.
+    ///
+    /// Points are yielded row by row, from bottom to top and left to right within each row,
+    /// starting from the first grid point at or above and to the right of `p0`.
+    pub fn grid_points(&self, grid: i64) -> impl Iterator<Item = Point> {
+        let xs = self.grid_points_in_dir(Dir::Horiz, grid);
+        self.grid_points_in_dir(Dir::Vert, grid)
+            .flat_map(move |y| xs.clone().map(move |x| Point::new(x, y)))
+    }
+
+    /// Returns `nx * ny` copies of this rectangle, translated by multiples of `pitch`.
+    ///
+    /// The copy in column `i` and row `j` is translated by `(i * pitch.w(), j * pitch.h())`.
+    /// Copies are returned in row-major order, starting with this rectangle.
+    /// Copies overlap if `pitch` is smaller than the dimensions of this rectangle.
+    pub fn tile(&self, nx: usize, ny: usize, pitch: Dims) -> Vec<Rect> {
+        let mut tiles = Vec::with_capacity(nx * ny);
+        for j in 0..ny as i64 {
+            for i in 0..nx as i64 {
+                let mut rect = *self;
+                rect.translate(Point::new(i * pitch.w(), j * pitch.h()));
+                tiles.push(rect);
+            }
+        }
+        tiles
+    }
+
+    /// Returns an iterator over the multiples of `grid` within the span of this
+    /// rectangle in direction `dir`, inclusive of its edges, in increasing order.
+    pub fn grid_points_in_dir(&self, dir: Dir, grid: i64) -> impl Iterator<Item = i64> + Clone {
+        let span = self.span(dir);
+        let start = snap_to_grid_with(span.start(), grid, SnapMode::Up);
+        (start..=span.stop()).step_by(grid as usize)
+    }
+
     pub fn cutout(&self, clip: Rect) -> [Rect; 4] {
         let src = *self;
         let t_span = Span::new(clip.top(), src.top());
@@ -1319,6 +2112,24 @@ impl Trim<Rect> for Rect {
     }
 }
 
+/// A point of an axis-aligned rectangle that can be used to position it.
+///
+/// See [`Rect::from_dims_anchored`] for more information.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Hash, PartialEq, Eq)]
+pub enum Anchor {
+    /// One of the rectangle's corners.
+    Corner(Corner),
+    /// The rectangle's center.
+    Center,
+}
+
+impl From<Corner> for Anchor {
+    #[inline]
+    fn from(value: Corner) -> Self {
+        Self::Corner(value)
+    }
+}
+
 /// Specifies how to expand geometry.
 ///
 /// See [`Rect::expand_dims`] for more information.
@@ -1385,11 +2196,26 @@ pub enum Shape {
 
 impl Trim<Rect> for Shape {
     type Output = Self;
+
+    /// Trims the shape to fit within `bounds`.
+    ///
+    /// A concave polygon split into several pieces by `bounds` is kept as a single
+    /// polygon, with its pieces joined by zero-width edges along the boundary of `bounds`.
+    /// A `Shape` can only hold a single path, so only the first in-bounds piece of a path
+    /// that is split by `bounds` is kept.
+    ///
+    /// Use [`Shape::trim_split`] to obtain every piece as a separate shape.
     fn trim(&self, bounds: &Rect) -> Option<Self::Output> {
         match self {
             Self::Rect(r) => r.trim(bounds).map(Self::Rect),
+            Self::Polygon(p) => p
+                .clip_points(bounds)
+                .map(|points| Self::Polygon(Polygon { points })),
+            Self::Path(p) => p
+                .trim(bounds)
+                .and_then(|paths| paths.into_iter().next())
+                .map(Self::Path),
             Self::Point(r) => r.trim(bounds).map(Self::Point),
-            _ => todo!(),
         }
     }
 }
@@ -1429,9 +2255,43 @@ impl Shape {
             None
         }
     }
-}
 
-/// Common shape operations, dispatched from the [`Shape`] enum to its variants by [mod@enum_dispatch].
+    /// Trims the shape to fit within `bounds`, returning one shape per in-bounds piece.
+    ///
+    /// Rects and points produce at most one shape.
+    /// A concave polygon split by `bounds`, or a path that leaves and re-enters `bounds`,
+    /// produces one shape per piece, as described in the [`Trim`]
+    /// implementations of [`Polygon`] and [`Path`].
+    ///
+    /// Returns [`None`] if no part of the shape lies within `bounds`.
+    pub fn trim_split(&self, bounds: &Rect) -> Option<Vec<Self>> {
+        match self {
+            Self::Rect(r) => r.trim(bounds).map(|r| vec![Self::Rect(r)]),
+            Self::Polygon(p) => p
+                .trim(bounds)
+                .map(|polys| polys.into_iter().map(Self::Polygon).collect()),
+            Self::Path(p) => p
+                .trim(bounds)
+                .map(|paths| paths.into_iter().map(Self::Path).collect()),
+            Self::Point(r) => r.trim(bounds).map(|r| vec![Self::Point(r)]),
+        }
+    }
+
+    /// Returns the area covered by the shape.
+    ///
+    /// See [`Rect::area`], [`Polygon::area`], and [`Path::area`].
+    /// The area of a [`Point`] is zero.
+    pub fn area(&self) -> i64 {
+        match self {
+            Shape::Rect(rect) => rect.area(),
+            Shape::Polygon(poly) => poly.area(),
+            Shape::Path(path) => path.area(),
+            Shape::Point(_) => 0,
+        }
+    }
+}
+
+/// Common shape operations, dispatched from the [`Shape`] enum to its variants by [mod@enum_dispatch].
 #[enum_dispatch]
 pub trait ShapeTrait {
     /// Returns our "origin", an arbitrary [`Point`] on the shape.
@@ -1470,12 +2330,7 @@ impl ShapeTrait for Rect {
     fn to_poly(&self) -> Polygon {
         // Create a four-sided polygon, cloning our corners
         Polygon {
-            points: vec![
-                self.p0,
-                Point::new(self.p1.x, self.p0.y),
-                self.p1,
-                Point::new(self.p0.x, self.p1.y),
-            ],
+            points: self.corners().to_vec(),
         }
     }
 }
@@ -1547,36 +2402,129 @@ impl ShapeTrait for Path {
         // FIXME: always horizontal, at least for now
         Dir::Horiz
     }
+    /// Returns `true` if the path's outline contains `pt`.
+    ///
+    /// The outline is the same as that of [`Path::to_poly`]: if the width is odd, the extra
+    /// unit lies on the left side of the centerline, relative to the direction of travel,
+    /// and each segment extends past a bend far enough to cover the mitered corner.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the path is not Manhattan.
     fn contains(&self, pt: Point) -> bool {
-        // Break into segments, and check for intersection with each
-        // Probably not the most efficient way to do this, but a start.
-        // Only "Manhattan paths", i.e. those with segments solely running vertically or horizontally, are supported.
-        // FIXME: even with this method, there are some small pieces at corners which we'll miss.
-        // Whether these are relevant in real life, tbd.
-        let (points, width) = (&self.points, self.width);
-        let width = i64::try_from(width).unwrap(); // FIXME: probably store these signed, check them on creation
-        for k in 0..points.len() - 1 {
-            let rect = if points[k].x == points[k + 1].x {
-                Rect {
-                    p0: Point::new(points[k].x - width / 2, points[k].y),
-                    p1: Point::new(points[k].x + width / 2, points[k + 1].y),
-                }
-            } else if points[k].y == points[k + 1].y {
-                Rect {
-                    p0: Point::new(points[k].x, points[k].y - width / 2),
-                    p1: Point::new(points[k + 1].x, points[k].y + width / 2),
-                }
+        let mut points = self.points.clone();
+        points.dedup();
+        let width = i64::try_from(self.width).unwrap(); // FIXME: probably store these signed, check them on creation
+        let (left, right) = (width - width / 2, width / 2);
+
+        // The unit vector along segment `k`.
+        let dir = |k: usize| {
+            let (p, q) = (points[k], points[k + 1]);
+            assert!(p.x == q.x || p.y == q.y, "Unsupported Non-Manhattan Path");
+            Point::new((q.x - p.x).signum(), (q.y - p.y).signum())
+        };
+        // How far the outline extends past a bend, along a segment with direction `d`,
+        // if the neighboring segment continues in direction `turn`. The outer corner of a
+        // left turn lies on the right side of the path, and vice versa.
+        let overhang = |d: Point, turn: Point| {
+            if turn == Point::new(-d.y, d.x) {
+                right
+            } else if turn == Point::new(d.y, -d.x) {
+                left
             } else {
-                unimplemented!("Unsupported Non-Manhattan Path")
-            };
-            if rect.contains(pt) {
-                return true;
+                0
             }
-        }
-        false
+        };
+
+        (0..points.len().saturating_sub(1)).any(|k| {
+            let d = dir(k);
+            let start = if k > 0 {
+                overhang(d, Point::zero() - dir(k - 1))
+            } else {
+                0
+            };
+            let end = if k + 2 < points.len() {
+                overhang(d, dir(k + 1))
+            } else {
+                0
+            };
+
+            // Coordinates along the segment and along its left normal.
+            let along = |p: Point| p.x * d.x + p.y * d.y;
+            let across = |p: Point| p.y * d.x - p.x * d.y;
+            let (p, q) = (points[k], points[k + 1]);
+            (along(p) - start..=along(q) + end).contains(&along(pt))
+                && (across(p) - right..=across(p) + left).contains(&across(pt))
+        })
     }
+    /// Converts the path to its outline polygon.
+    ///
+    /// The outline is offset by half the path's width on either side of the centerline,
+    /// with mitered corners at each bend. If the width is odd, the extra unit lies on the
+    /// left side of the centerline, relative to the direction of travel. Paths have flush
+    /// ends, so the outline does not extend beyond the first and last points.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the path is not Manhattan, or if it has fewer than two distinct points.
     fn to_poly(&self) -> Polygon {
-        unimplemented!("Path::to_poly")
+        let mut points = self.points.clone();
+        points.dedup();
+        assert!(
+            points.len() >= 2,
+            "cannot convert a path with fewer than two distinct points to a polygon"
+        );
+
+        // The unit normal to the left of each segment.
+        let normals = points
+            .windows(2)
+            .map(|seg| {
+                let (dx, dy) = (seg[1].x - seg[0].x, seg[1].y - seg[0].y);
+                assert!(
+                    dx == 0 || dy == 0,
+                    "cannot convert non-Manhattan path segment from {:?} to {:?} to a polygon",
+                    seg[0],
+                    seg[1]
+                );
+                Point::new(-dy.signum(), dx.signum())
+            })
+            .collect::<Vec<_>>();
+
+        let width = i64::try_from(self.width).unwrap();
+        let (left, right) = (width - width / 2, width / 2);
+        let offset =
+            |p: Point, n: Point, scale: i64| Point::new(p.x + n.x * scale, p.y + n.y * scale);
+
+        // Walks one side of the centerline, offsetting by `dist` along the left normal.
+        let boundary = |dist: i64| {
+            let mut out = vec![offset(points[0], normals[0], dist)];
+            for k in 1..points.len() - 1 {
+                let (prev, next) = (normals[k - 1], normals[k]);
+                if prev == next {
+                    // Collinear segments; no vertex is needed.
+                    continue;
+                }
+                let sum = prev + next;
+                if sum == Point::zero() {
+                    // The path reverses direction.
+                    out.push(offset(points[k], prev, dist));
+                    out.push(offset(points[k], next, dist));
+                } else {
+                    // A right-angle bend, mitered at the corner.
+                    out.push(offset(points[k], sum, dist));
+                }
+            }
+            out.push(offset(
+                points[points.len() - 1],
+                normals[normals.len() - 1],
+                dist,
+            ));
+            out
+        };
+
+        let mut outline = boundary(left);
+        outline.extend(boundary(-right).into_iter().rev());
+        Polygon { points: outline }
     }
 }
 impl ShapeTrait for Point {
@@ -1728,6 +2676,36 @@ impl Dims {
     pub fn into_point(self) -> Point {
         Point::new(self.w(), self.h())
     }
+
+    /// Returns the area enclosed by these dimensions.
+    #[inline]
+    pub fn area(&self) -> i64 {
+        self.w * self.h
+    }
+
+    /// Returns the componentwise maximum of two [`Dims`].
+    ///
+    /// Unlike [`Ord::max`], which compares widths first,
+    /// the width and height of the result are chosen independently.
+    #[inline]
+    pub fn max(self, other: Self) -> Self {
+        Self {
+            w: self.w.max(other.w),
+            h: self.h.max(other.h),
+        }
+    }
+
+    /// Returns the componentwise minimum of two [`Dims`].
+    ///
+    /// Unlike [`Ord::min`], which compares widths first,
+    /// the width and height of the result are chosen independently.
+    #[inline]
+    pub fn min(self, other: Self) -> Self {
+        Self {
+            w: self.w.min(other.w),
+            h: self.h.min(other.h),
+        }
+    }
 }
 
 impl std::ops::Add<Dims> for Dims {
@@ -1857,9 +2835,7 @@ pub mod tests {
         // Test polygon-point containment of several flavors
 
         // Create a right triangle at the origin
-        let triangle = Polygon {
-            points: vec![Point::new(0, 0), Point::new(2, 0), Point::new(0, 2)],
-        };
+        let triangle = triangle();
         assert!(triangle.contains(Point::new(0, 0)));
         assert!(triangle.contains(Point::new(1, 0)));
         assert!(triangle.contains(Point::new(2, 0)));
@@ -1868,14 +2844,7 @@ pub mod tests {
         assert!(!triangle.contains(Point::new(2, 2)));
 
         // Create a 2:1 tall-ish diamond-shape
-        let diamond = Polygon {
-            points: vec![
-                Point::new(1, 0),
-                Point::new(2, 2),
-                Point::new(1, 4),
-                Point::new(0, 2),
-            ],
-        };
+        let diamond = diamond();
         assert!(!diamond.contains(Point::new(0, 0)));
         assert!(!diamond.contains(Point::new(100, 100)));
         // Check a few points through its vertical center
@@ -1916,6 +2885,642 @@ pub mod tests {
         assert!(!u.contains(Point::new(7, 9)));
     }
 
+    /// A right triangle at the origin, with vertices in counterclockwise order.
+    fn triangle() -> Polygon {
+        Polygon {
+            points: vec![Point::new(0, 0), Point::new(2, 0), Point::new(0, 2)],
+        }
+    }
+
+    /// A 2:1 tall-ish diamond, with vertices in counterclockwise order.
+    fn diamond() -> Polygon {
+        Polygon {
+            points: vec![
+                Point::new(1, 0),
+                Point::new(2, 2),
+                Point::new(1, 4),
+                Point::new(0, 2),
+            ],
+        }
+    }
+
+    #[test]
+    fn test_polygon_area() {
+        assert_eq!(triangle().area(), 2);
+        assert_eq!(diamond().area(), 4);
+        assert_eq!(u_polygon().area(), 52);
+
+        let mut reversed = diamond();
+        reversed.points.reverse();
+        assert_eq!(reversed.area(), 4);
+        assert_eq!(Polygon::default().area(), 0);
+    }
+
+    #[test]
+    fn test_polygon_centroid() {
+        // The exact centroid is (2/3, 2/3).
+        let triangle = triangle();
+        assert_eq!(triangle.centroid(), Point::new(1, 1));
+        assert!(triangle.contains(triangle.centroid()));
+
+        let diamond = diamond();
+        assert_eq!(diamond.centroid(), Point::new(1, 2));
+        assert!(diamond.contains(diamond.centroid()));
+
+        // The exact centroid is (5, 77/19), which lies in the thick base of the U.
+        let mut u = Polygon {
+            points: vec![
+                Point::new(0, 0),
+                Point::new(0, 10),
+                Point::new(2, 10),
+                Point::new(2, 6),
+                Point::new(8, 6),
+                Point::new(8, 10),
+                Point::new(10, 10),
+                Point::new(10, 0),
+            ],
+        };
+        assert_eq!(u.centroid(), Point::new(5, 4));
+        assert!(u.contains(u.centroid()));
+        u.ensure_ccw();
+        assert_eq!(u.centroid(), Point::new(5, 4));
+
+        // As documented on `Polygon::centroid`, the centroid of a non-convex polygon
+        // may lie outside it. The exact centroid of this U is (5, 53/13),
+        // which lies in the notch between its thin arms.
+        let u = u_polygon();
+        assert_eq!(u.centroid(), Point::new(5, 4));
+        assert!(!u.contains(u.centroid()));
+
+        let degenerate = Polygon {
+            points: vec![Point::new(0, 0), Point::new(3, 3), Point::new(6, 6)],
+        };
+        assert_eq!(degenerate.centroid(), Point::new(3, 3));
+    }
+
+    #[test]
+    fn test_polygon_orientation() {
+        assert!(!triangle().is_clockwise());
+        assert!(!diamond().is_clockwise());
+        assert!(u_polygon().is_clockwise());
+
+        let mut triangle = triangle();
+        triangle.points.reverse();
+        assert!(triangle.is_clockwise());
+        triangle.ensure_ccw();
+        assert!(!triangle.is_clockwise());
+        assert_eq!(triangle.area(), 2);
+
+        // Counterclockwise polygons are left unchanged.
+        let mut diamond = diamond();
+        diamond.ensure_ccw();
+        assert_eq!(diamond, self::diamond());
+
+        let mut u = u_polygon();
+        u.ensure_ccw();
+        assert!(!u.is_clockwise());
+        assert_eq!(u.area(), 52);
+    }
+
+    /// A U-shaped polygon inside a 10x10 square, as in [`test_polygon_contains`].
+    fn u_polygon() -> Polygon {
+        Polygon {
+            points: vec![
+                Point::new(0, 0),
+                Point::new(0, 10),
+                Point::new(2, 10),
+                Point::new(2, 2),
+                Point::new(8, 2),
+                Point::new(8, 10),
+                Point::new(10, 10),
+                Point::new(10, 0),
+            ],
+        }
+    }
+
+    #[test]
+    fn test_polygon_trim_inside() {
+        let u = u_polygon();
+        let bounds = Rect::new(Point::new(-1, -1), Point::new(11, 11));
+        assert_eq!(u.trim(&bounds), Some(vec![u.clone()]));
+        let bounds = Rect::new(Point::new(0, 0), Point::new(10, 10));
+        assert_eq!(u.trim(&bounds), Some(vec![u]));
+    }
+
+    #[test]
+    fn test_polygon_trim_outside() {
+        let u = u_polygon();
+        let bounds = Rect::new(Point::new(20, 20), Point::new(30, 30));
+        assert_eq!(u.trim(&bounds), None);
+        assert_eq!(Shape::Polygon(u).trim(&bounds), None);
+    }
+
+    #[test]
+    fn test_polygon_trim_straddling() {
+        // Clip the arms of the U at y = 5.
+        let u = u_polygon();
+        let bounds = Rect::new(Point::new(-5, -5), Point::new(15, 5));
+        let mut trimmed = u.trim(&bounds).unwrap();
+        assert_eq!(trimmed.len(), 1);
+        let trimmed = trimmed.remove(0);
+        assert_eq!(
+            trimmed.points,
+            vec![
+                Point::new(0, 0),
+                Point::new(0, 5),
+                Point::new(2, 5),
+                Point::new(2, 2),
+                Point::new(8, 2),
+                Point::new(8, 5),
+                Point::new(10, 5),
+                Point::new(10, 0),
+            ]
+        );
+        assert_eq!(trimmed.area(), 32);
+        assert!(trimmed.contains(Point::new(1, 4)));
+        assert!(!trimmed.contains(Point::new(1, 6)));
+        assert!(!trimmed.contains(Point::new(5, 4)));
+
+        // Cut the corner off a triangle, producing a new vertex on each crossed edge.
+        let triangle = Polygon {
+            points: vec![Point::new(0, 0), Point::new(4, 0), Point::new(0, 4)],
+        };
+        let bounds = Rect::new(Point::new(1, -1), Point::new(5, 5));
+        assert_eq!(
+            Shape::Polygon(triangle).trim(&bounds),
+            Some(Shape::Polygon(Polygon {
+                points: vec![Point::new(1, 0), Point::new(4, 0), Point::new(1, 3)],
+            }))
+        );
+    }
+
+    #[test]
+    fn test_polygon_trim_concave_split() {
+        // Keeping only the arms of the U splits it in two.
+        let u = u_polygon();
+        let bounds = Rect::new(Point::new(-5, 5), Point::new(15, 15));
+        assert_eq!(
+            u.trim(&bounds),
+            Some(vec![
+                Polygon {
+                    points: vec![
+                        Point::new(0, 5),
+                        Point::new(0, 10),
+                        Point::new(2, 10),
+                        Point::new(2, 5),
+                    ],
+                },
+                Polygon {
+                    points: vec![
+                        Point::new(8, 5),
+                        Point::new(8, 10),
+                        Point::new(10, 10),
+                        Point::new(10, 5),
+                    ],
+                },
+            ])
+        );
+
+        // Each arm is split off even if the clip boundary also crosses the base of the U.
+        let bounds = Rect::new(Point::new(1, 5), Point::new(9, 15));
+        let pieces = Shape::Polygon(u.clone()).trim_split(&bounds).unwrap();
+        assert_eq!(pieces.len(), 2);
+        assert_eq!(pieces.iter().map(|p| p.area()).sum::<i64>(), 10);
+
+        // Trimming the shape keeps both arms in a single polygon.
+        let joined = Shape::Polygon(u).trim(&bounds).unwrap();
+        assert_eq!(joined.area(), 10);
+        assert!(joined.contains(Point::new(1, 7)));
+        assert!(joined.contains(Point::new(9, 7)));
+    }
+
+    #[test]
+    fn test_path_trim() {
+        // An L-shaped path running up and then right.
+        let path = Path {
+            points: vec![Point::new(0, 0), Point::new(0, 10), Point::new(10, 10)],
+            width: 2,
+        };
+
+        let bounds = Rect::new(Point::new(-5, -5), Point::new(15, 15));
+        assert_eq!(path.trim(&bounds), Some(vec![path.clone()]));
+
+        let bounds = Rect::new(Point::new(-5, 5), Point::new(5, 15));
+        assert_eq!(
+            path.trim(&bounds),
+            Some(vec![Path {
+                points: vec![Point::new(0, 5), Point::new(0, 10), Point::new(5, 10)],
+                width: 2,
+            }])
+        );
+
+        let bounds = Rect::new(Point::new(20, 20), Point::new(30, 30));
+        assert_eq!(path.trim(&bounds), None);
+        assert_eq!(Shape::Path(path).trim(&bounds), None);
+    }
+
+    #[test]
+    fn test_path_trim_split() {
+        // A U-shaped path that leaves and re-enters the bounds.
+        let path = Path {
+            points: vec![
+                Point::new(0, 10),
+                Point::new(0, 0),
+                Point::new(10, 0),
+                Point::new(10, 10),
+            ],
+            width: 2,
+        };
+        let bounds = Rect::new(Point::new(-5, 5), Point::new(15, 15));
+        let pieces = vec![
+            Path {
+                points: vec![Point::new(0, 10), Point::new(0, 5)],
+                width: 2,
+            },
+            Path {
+                points: vec![Point::new(10, 5), Point::new(10, 10)],
+                width: 2,
+            },
+        ];
+        assert_eq!(path.trim(&bounds), Some(pieces.clone()));
+        assert_eq!(
+            Shape::Path(path.clone()).trim(&bounds),
+            Some(Shape::Path(pieces[0].clone()))
+        );
+        assert_eq!(
+            Shape::Path(path).trim_split(&bounds),
+            Some(pieces.into_iter().map(Shape::Path).collect())
+        );
+    }
+
+    /// Asserts that every point contained in `path` is also contained in `poly`.
+    fn assert_poly_covers_path(path: &Path, poly: &Polygon) {
+        let bbox = path.points.bbox();
+        for x in bbox.p0.x - 5..=bbox.p1.x + 5 {
+            for y in bbox.p0.y - 5..=bbox.p1.y + 5 {
+                let pt = Point::new(x, y);
+                if path.contains(pt) {
+                    assert!(
+                        poly.contains(pt),
+                        "{pt:?} is in the path but not the polygon"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_path_to_poly_straight() {
+        let path = Path {
+            points: vec![Point::new(0, 0), Point::new(10, 0)],
+            width: 4,
+        };
+        let poly = path.to_poly();
+        assert_eq!(poly.points.len(), 4);
+        assert_eq!(poly.area(), path.area());
+        assert_poly_covers_path(&path, &poly);
+        assert!(!poly.contains(Point::new(5, 3)));
+        assert!(!poly.contains(Point::new(11, 0)));
+    }
+
+    #[test]
+    fn test_path_to_poly_l_shape() {
+        let path = Path {
+            points: vec![
+                Point::new(0, 0),
+                Point::new(10, 0),
+                Point::new(10, 5),
+                Point::new(10, 10),
+            ],
+            width: 2,
+        };
+        let poly = path.to_poly();
+        assert_eq!(
+            poly.points,
+            vec![
+                Point::new(0, 1),
+                Point::new(9, 1),
+                Point::new(9, 10),
+                Point::new(11, 10),
+                Point::new(11, -1),
+                Point::new(0, -1),
+            ]
+        );
+        assert_poly_covers_path(&path, &poly);
+        // The mitered corner is covered by both the polygon and `Path::contains`.
+        assert!(poly.contains(Point::new(11, -1)));
+        assert!(path.contains(Point::new(11, -1)));
+        assert!(!poly.contains(Point::new(5, 5)));
+        assert!(!poly.contains(Point::new(12, 5)));
+    }
+
+    #[test]
+    fn test_path_to_poly_odd_width() {
+        let path = Path {
+            points: vec![Point::new(0, 0), Point::new(10, 0), Point::new(10, 10)],
+            width: 3,
+        };
+        let poly = path.to_poly();
+        // The extra unit lies to the left of the direction of travel.
+        assert_eq!(
+            poly.points,
+            vec![
+                Point::new(0, 2),
+                Point::new(8, 2),
+                Point::new(8, 10),
+                Point::new(11, 10),
+                Point::new(11, -1),
+                Point::new(0, -1),
+            ]
+        );
+        assert_poly_covers_path(&path, &poly);
+        assert!(path.contains(Point::new(5, 2)));
+        assert!(!path.contains(Point::new(5, -2)));
+
+        let straight = Path {
+            points: vec![Point::new(0, 0), Point::new(0, 10)],
+            width: 5,
+        };
+        assert_eq!(straight.to_poly().area(), straight.area());
+    }
+
+    #[test]
+    fn test_path_contains_matches_to_poly() {
+        for (points, width) in [
+            (
+                vec![Point::new(0, 0), Point::new(0, 10), Point::new(10, 10)],
+                3,
+            ),
+            (
+                vec![Point::new(10, 0), Point::new(0, 0), Point::new(0, -10)],
+                5,
+            ),
+            (
+                vec![
+                    Point::new(0, 10),
+                    Point::new(0, 0),
+                    Point::new(10, 0),
+                    Point::new(10, 10),
+                ],
+                3,
+            ),
+        ] {
+            let path = Path { points, width };
+            let poly = path.to_poly();
+            let bbox = path.points.bbox();
+            for x in bbox.p0.x - 5..=bbox.p1.x + 5 {
+                for y in bbox.p0.y - 5..=bbox.p1.y + 5 {
+                    let pt = Point::new(x, y);
+                    assert_eq!(
+                        path.contains(pt),
+                        poly.contains(pt),
+                        "{pt:?} disagrees for {path:?}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "non-Manhattan")]
+    fn test_path_to_poly_non_manhattan() {
+        Path {
+            points: vec![Point::new(0, 0), Point::new(10, 10)],
+            width: 2,
+        }
+        .to_poly();
+    }
+
+    #[test]
+    fn test_rect_intersection_disjoint() {
+        let a = Rect::new(Point::new(0, 0), Point::new(10, 10));
+        let b = Rect::new(Point::new(20, 0), Point::new(30, 10));
+        assert_eq!(a.intersection(b), None);
+        assert_eq!(a.intersection_inclusive(b), None);
+        assert_eq!(
+            a.union_rect(b),
+            Rect::new(Point::new(0, 0), Point::new(30, 10))
+        );
+    }
+
+    #[test]
+    fn test_rect_intersection_nested() {
+        let outer = Rect::new(Point::new(0, 0), Point::new(10, 10));
+        let inner = Rect::new(Point::new(2, 3), Point::new(5, 7));
+        assert_eq!(outer.intersection(inner), Some(inner));
+        assert_eq!(inner.intersection(outer), Some(inner));
+        assert_eq!(outer.union_rect(inner), outer);
+
+        let other = Rect::new(Point::new(5, 5), Point::new(15, 15));
+        assert_eq!(
+            outer.intersection(other),
+            Some(Rect::new(Point::new(5, 5), Point::new(10, 10)))
+        );
+    }
+
+    #[test]
+    fn test_rect_intersection_touching() {
+        let a = Rect::new(Point::new(0, 0), Point::new(10, 10));
+        let edge = Rect::new(Point::new(10, 2), Point::new(20, 8));
+        assert_eq!(a.intersection(edge), None);
+        assert_eq!(
+            a.intersection_inclusive(edge),
+            Some(Rect::new(Point::new(10, 2), Point::new(10, 8)))
+        );
+
+        let corner = Rect::new(Point::new(10, 10), Point::new(20, 20));
+        assert_eq!(a.intersection(corner), None);
+        assert_eq!(
+            a.intersection_inclusive(corner),
+            Some(Rect::from_point(Point::new(10, 10)))
+        );
+        assert_eq!(
+            a.union_rect(corner),
+            Rect::new(Point::new(0, 0), Point::new(20, 20))
+        );
+    }
+
+    #[test]
+    fn test_rect_expand_sides() {
+        let rect = Rect::new(Point::new(0, 0), Point::new(100, 50));
+        let amounts = Sides::new(10, -20, 5, -15);
+        let expanded = rect.expand_sides(amounts);
+        assert_eq!(expanded, Rect::new(Point::new(15, -5), Point::new(80, 60)));
+        assert_eq!(expanded.shrink_sides(amounts), rect);
+        assert_eq!(
+            rect.shrink_sides(amounts),
+            Rect::new(Point::new(-15, 5), Point::new(120, 40))
+        );
+        assert_eq!(rect.expand_sides(Sides::uniform(7)), rect.expand(7));
+        assert_eq!(rect.shrink_sides(Sides::uniform(7)), rect.shrink(7));
+
+        // Expanding and shrinking compose with snapping to a grid.
+        let snapped = rect.expand_sides(Sides::new(3, 12, -6, 0)).snap_to_grid(10);
+        assert_eq!(snapped.p0.snap_to_grid(10), snapped.p0);
+        assert_eq!(snapped.p1.snap_to_grid(10), snapped.p1);
+        assert_eq!(snapped, Rect::new(Point::new(0, 10), Point::new(110, 50)));
+        assert_eq!(
+            snapped.shrink_sides(Sides::new(10, 10, 0, 10)),
+            Rect::new(Point::new(10, 10), Point::new(100, 40))
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "negative width or height")]
+    fn test_rect_shrink_sides_negative() {
+        Rect::new(Point::new(0, 0), Point::new(100, 50)).shrink_sides(Sides::new(30, 0, 30, 0));
+    }
+
+    #[test]
+    fn test_snap_to_grid_with() {
+        for pos in -25..=25 {
+            let down = snap_to_grid_with(pos, 10, SnapMode::Down);
+            let up = snap_to_grid_with(pos, 10, SnapMode::Up);
+            assert_eq!(down.rem_euclid(10), 0);
+            assert_eq!(up.rem_euclid(10), 0);
+            assert!(down <= pos && pos - down < 10);
+            assert!(up >= pos && up - pos < 10);
+            assert_eq!(
+                snap_to_grid_with(pos, 10, SnapMode::Nearest),
+                snap_to_grid(pos, 10)
+            );
+        }
+
+        assert_eq!(snap_to_grid_with(-7, 5, SnapMode::Down), -10);
+        assert_eq!(snap_to_grid_with(-7, 5, SnapMode::Up), -5);
+        assert_eq!(snap_to_grid_with(-7, 5, SnapMode::Nearest), -5);
+        assert_eq!(snap_to_grid_with(-10, 5, SnapMode::Up), -10);
+        assert_eq!(snap_to_grid_with(15, 10, SnapMode::Nearest), 20);
+    }
+
+    #[test]
+    fn test_snap_to_grid_ties() {
+        // Even grids have exact ties, which round up for either sign.
+        assert_eq!(snap_to_grid(5, 10), 10);
+        assert_eq!(snap_to_grid(-5, 10), 0);
+        assert_eq!(snap_to_grid(-15, 10), -10);
+        assert_eq!(snap_to_grid(4, 10), 0);
+        assert_eq!(snap_to_grid(-4, 10), 0);
+        assert_eq!(snap_to_grid(-6, 10), -10);
+        assert_eq!(snap_to_grid(1, 2), 2);
+        assert_eq!(snap_to_grid(-1, 2), 0);
+
+        // Odd grids have no ties; `grid / 2` rounds down and `grid / 2 + 1` rounds up.
+        assert_eq!(snap_to_grid(2, 5), 0);
+        assert_eq!(snap_to_grid(3, 5), 5);
+        assert_eq!(snap_to_grid(-2, 5), 0);
+        assert_eq!(snap_to_grid(-3, 5), -5);
+        assert_eq!(snap_to_grid(-7, 5), -5);
+        assert_eq!(snap_to_grid(-8, 5), -10);
+
+        // Snapping commutes with translation by a multiple of the grid.
+        for grid in 1..=6 {
+            for pos in -20..=20 {
+                assert_eq!(
+                    snap_to_grid(pos - 6 * grid, grid),
+                    snap_to_grid(pos, grid) - 6 * grid
+                );
+            }
+        }
+
+        // A grid of 1 leaves every position unchanged.
+        for pos in -3..=3 {
+            assert_eq!(snap_to_grid(pos, 1), pos);
+        }
+    }
+
+    #[test]
+    fn test_rect_snap_to_grid_with() {
+        let rect = Rect::new(Point::new(-13, 4), Point::new(27, 31));
+        assert_eq!(
+            rect.snap_to_grid_with(10, SnapMode::Down),
+            Rect::new(Point::new(-20, 0), Point::new(20, 30))
+        );
+        assert_eq!(
+            rect.snap_to_grid_with(10, SnapMode::Up),
+            Rect::new(Point::new(-10, 10), Point::new(30, 40))
+        );
+        assert_eq!(
+            rect.snap_to_grid_with(10, SnapMode::Nearest),
+            rect.snap_to_grid(10)
+        );
+    }
+
+    #[test]
+    fn test_rect_grid_points() {
+        let rect = Rect::new(Point::new(0, 0), Point::new(100, 100));
+        assert_eq!(rect.grid_points(5).count(), 21 * 21);
+        assert_eq!(rect.grid_points_in_dir(Dir::Horiz, 5).count(), 21);
+        assert!(rect
+            .grid_points(5)
+            .all(|pt| rect.contains(pt) && pt.snap_to_grid(5) == pt));
+
+        let rect = Rect::new(Point::new(-3, 2), Point::new(12, 8));
+        assert_eq!(
+            rect.grid_points(5).collect::<Vec<_>>(),
+            vec![Point::new(0, 5), Point::new(5, 5), Point::new(10, 5)]
+        );
+        assert_eq!(
+            rect.grid_points_in_dir(Dir::Vert, 5).collect::<Vec<_>>(),
+            vec![5]
+        );
+    }
+
+    #[test]
+    fn test_rect_tile() {
+        let rect = Rect::new(Point::new(10, 20), Point::new(30, 30));
+        let tiles = rect.tile(3, 2, Dims::new(50, 40));
+        assert_eq!(tiles.len(), 6);
+        assert_eq!(tiles[0], rect);
+        assert_eq!(
+            tiles[2],
+            Rect::new(Point::new(110, 20), Point::new(130, 30))
+        );
+        assert_eq!(tiles[3], Rect::new(Point::new(10, 60), Point::new(30, 70)));
+        assert_eq!(
+            tiles[5],
+            Rect::new(Point::new(110, 60), Point::new(130, 70))
+        );
+        assert!(tiles.iter().all(|t| t.width() == 20 && t.height() == 10));
+
+        // Pitches smaller than the rectangle produce overlapping tiles.
+        let tiles = rect.tile(3, 2, Dims::new(5, 5));
+        assert_eq!(tiles.len(), 6);
+        assert_eq!(tiles[5], Rect::new(Point::new(20, 25), Point::new(40, 35)));
+        assert!(tiles[0].intersection(tiles[5]).is_some());
+
+        assert!(rect.tile(0, 2, Dims::new(50, 40)).is_empty());
+    }
+
+    #[test]
+    fn test_rect_grid_points_degenerate() {
+        let rect = Rect::new(Point::new(10, 15), Point::new(10, 15));
+        assert_eq!(
+            rect.grid_points(5).collect::<Vec<_>>(),
+            vec![Point::new(10, 15)]
+        );
+
+        let rect = Rect::new(Point::new(11, 15), Point::new(11, 15));
+        assert_eq!(rect.grid_points(5).count(), 0);
+
+        let rect = Rect::new(Point::new(1, 1), Point::new(4, 4));
+        assert_eq!(rect.grid_points(5).count(), 0);
+    }
+
+    #[test]
+    fn test_dims_max_min_area() {
+        let a = Dims::new(10, 40);
+        let b = Dims::new(30, 20);
+        assert_eq!(a.max(b), Dims::new(30, 40));
+        assert_eq!(a.min(b), Dims::new(10, 20));
+        assert_eq!(b.max(a), a.max(b));
+        assert_eq!(a.max(a), a);
+
+        assert_eq!(a.area(), 400);
+        assert_eq!(a.max(b).area(), 1_200);
+        assert_eq!(Dims::square(0).area(), 0);
+    }
+
     #[test]
     fn test_point_snap_to_grid() {
         let pt = Point::new(1, 1);
@@ -1926,4 +3531,259 @@ pub mod tests {
         let pt = pt.snap_to_grid(500);
         assert_eq!(pt, Point::new(1_000, 500));
     }
+
+    #[test]
+    fn test_dir_all() {
+        let dirs = Dir::all().collect::<Vec<_>>();
+        assert_eq!(dirs, vec![Dir::Horiz, Dir::Vert]);
+
+        let rect = Rect::new(Point::new(0, 0), Point::new(20, 10));
+        let lengths = Dir::all().map(|dir| rect.length(dir)).collect::<Vec<_>>();
+        assert_eq!(lengths, vec![20, 10]);
+    }
+
+    #[test]
+    fn test_dir_both() {
+        let rect = Rect::new(Point::new(-5, 0), Point::new(20, 10));
+        assert_eq!(Dir::both(|dir| rect.length(dir)), (25, 10));
+        assert_eq!(Dir::both(Dir::other), (Dir::Vert, Dir::Horiz));
+    }
+
+    #[test]
+    fn test_side_all() {
+        let sides = Side::all().collect::<Vec<_>>();
+        assert_eq!(sides, vec![Side::Top, Side::Right, Side::Bot, Side::Left]);
+
+        let mut values = Sides::new(1, 2, 3, 4);
+        values[Side::Bot] = 5;
+        let values = values.iter().collect::<Vec<_>>();
+        assert_eq!(
+            values,
+            vec![
+                (Side::Top, &1),
+                (Side::Right, &2),
+                (Side::Bot, &5),
+                (Side::Left, &4)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_corner_all() {
+        let corners = Corner::all().collect::<Vec<_>>();
+        assert_eq!(corners.len(), 4);
+        assert_eq!(
+            corners,
+            vec![
+                Corner::LowerLeft,
+                Corner::LowerRight,
+                Corner::UpperLeft,
+                Corner::UpperRight
+            ]
+        );
+    }
+
+    #[test]
+    fn test_rect_corners() {
+        let rect = Rect::new(Point::new(-10, 5), Point::new(20, 15));
+        assert_eq!(
+            rect.corners(),
+            [
+                Point::new(-10, 5),
+                Point::new(20, 5),
+                Point::new(20, 15),
+                Point::new(-10, 15),
+            ]
+        );
+        assert_eq!(rect.to_poly().points, rect.corners().to_vec());
+    }
+
+    #[test]
+    fn test_point_rotate_about() {
+        let center = Point::new(10, 20);
+        let pt = Point::new(13, 21);
+
+        assert_eq!(pt.rotate_about(center, 0), pt);
+        assert_eq!(pt.rotate_about(center, 1), Point::new(9, 23));
+        assert_eq!(pt.rotate_about(center, 2), Point::new(7, 19));
+        assert_eq!(pt.rotate_about(center, 3), Point::new(11, 17));
+        assert_eq!(pt.rotate_about(center, 4), pt);
+
+        assert_eq!(pt.rotate_about(center, -1), pt.rotate_about(center, 3));
+        assert_eq!(pt.rotate_about(center, -6), pt.rotate_about(center, 2));
+        assert_eq!(center.rotate_about(center, 1), center);
+    }
+
+    #[test]
+    fn test_shape_area() {
+        let rect = Rect::new(Point::new(-5, 0), Point::new(5, 4));
+        assert_eq!(Shape::Rect(rect).area(), 40);
+        assert_eq!(Shape::Polygon(rect.to_poly()).area(), 40);
+
+        // An L-shaped polygon, listed clockwise.
+        let l = Polygon {
+            points: vec![
+                Point::new(0, 0),
+                Point::new(0, 10),
+                Point::new(2, 10),
+                Point::new(2, 2),
+                Point::new(8, 2),
+                Point::new(8, 0),
+            ],
+        };
+        assert_eq!(Shape::Polygon(l).area(), 20 + 12);
+
+        assert_eq!(Shape::Point(Point::new(3, 4)).area(), 0);
+    }
+
+    #[test]
+    fn test_path_area() {
+        let points = vec![Point::new(0, 0), Point::new(10, 0), Point::new(10, 5)];
+
+        let even = Path {
+            points: points.clone(),
+            width: 4,
+        };
+        assert_eq!(even.length(), 15);
+        assert_eq!(Shape::Path(even).area(), 60);
+
+        let odd = Path { points, width: 3 };
+        assert_eq!(Shape::Path(odd).area(), 45);
+
+        let diagonal = Path {
+            points: vec![Point::new(0, 0), Point::new(3, 4)],
+            width: 1,
+        };
+        assert_eq!(diagonal.area(), 5);
+
+        let single = Path {
+            points: vec![Point::new(1, 1)],
+            width: 2,
+        };
+        assert_eq!(single.area(), 0);
+    }
+
+    #[test]
+    fn test_span_contains_point() {
+        let span = Span::new(-10, 20);
+        assert!(span.contains_point(0));
+        assert!(span.contains_point(-10));
+        assert!(span.contains_point(20));
+        assert!(!span.contains_point(-11));
+        assert!(!span.contains_point(21));
+
+        assert!(span.contains_point_exclusive(0));
+        assert!(span.contains_point_exclusive(-9));
+        assert!(span.contains_point_exclusive(19));
+        assert!(!span.contains_point_exclusive(-10));
+        assert!(!span.contains_point_exclusive(20));
+
+        let point = Span::from_point(5);
+        assert!(point.contains_point(5));
+        assert!(!point.contains_point_exclusive(5));
+    }
+
+    #[test]
+    fn test_point_snap_to_grid_xy() {
+        let pt = Point::new(7, 7).snap_to_grid_xy(5, 10);
+        assert_eq!(pt, Point::new(5, 10));
+
+        let pt = Point::new(-7, -7).snap_to_grid_xy(5, 10);
+        assert_eq!(pt, Point::new(-5, -10));
+
+        let pt = Point::new(-13, 24).snap_to_grid_xy(10, 5);
+        assert_eq!(pt, Point::new(-10, 25));
+
+        // Equal grids behave like `snap_to_grid`.
+        let pt = Point::new(-260, 999);
+        assert_eq!(pt.snap_to_grid_xy(500, 500), pt.snap_to_grid(500));
+    }
+
+    #[test]
+    fn test_rect_snap_to_grid_xy() {
+        let rect = Rect::new(Point::new(-13, -4), Point::new(22, 9));
+        let rect = rect.snap_to_grid_xy(10, 4);
+        assert_eq!(rect, Rect::new(Point::new(-10, -4), Point::new(20, 8)));
+    }
+
+    #[test]
+    fn test_span_checked_arithmetic() {
+        let span = Span::new(i64::MAX - 10, i64::MAX - 5);
+        assert_eq!(
+            span.checked_translate(5),
+            Some(Span::new(i64::MAX - 5, i64::MAX))
+        );
+        assert_eq!(span.checked_translate(6), None);
+        assert_eq!(
+            span.checked_expand(true, 5),
+            Some(Span::new(i64::MAX - 10, i64::MAX))
+        );
+        assert_eq!(span.checked_expand(true, 6), None);
+        assert_eq!(span.checked_expand_all(6), None);
+
+        let span = Span::new(i64::MIN + 5, 0);
+        assert_eq!(span.checked_translate(-6), None);
+        assert_eq!(span.checked_expand(false, 6), None);
+        assert_eq!(span.checked_expand_all(5), Some(Span::new(i64::MIN, 5)));
+        assert_eq!(span.checked_expand_all(6), None);
+    }
+
+    #[test]
+    fn test_rect_checked_arithmetic() {
+        let rect = Rect::new(Point::new(0, 0), Point::new(i64::MAX - 1, 10));
+        assert_eq!(
+            rect.checked_translate(Point::new(1, -1)),
+            Some(Rect::new(Point::new(1, -1), Point::new(i64::MAX, 9)))
+        );
+        assert_eq!(rect.checked_translate(Point::new(2, 0)), None);
+        assert_eq!(
+            rect.checked_expand(1),
+            Some(Rect::new(Point::new(-1, -1), Point::new(i64::MAX, 11)))
+        );
+        assert_eq!(rect.checked_expand(2), None);
+
+        let rect = Rect::new(Point::new(i64::MIN, 0), Point::new(0, 0));
+        assert_eq!(rect.checked_translate(Point::new(-1, 0)), None);
+        assert_eq!(rect.checked_expand(1), None);
+        assert_eq!(rect.checked_expand(0), Some(rect));
+    }
+
+    #[test]
+    fn test_rect_from_dims_anchored() {
+        let dims = Dims::new(40, 20);
+        let at = Point::new(100, 200);
+        assert_eq!(
+            Rect::from_dims_anchored(dims, Corner::LowerLeft, at),
+            Rect::new(Point::new(100, 200), Point::new(140, 220))
+        );
+        assert_eq!(
+            Rect::from_dims_anchored(dims, Corner::LowerRight, at),
+            Rect::new(Point::new(60, 200), Point::new(100, 220))
+        );
+        assert_eq!(
+            Rect::from_dims_anchored(dims, Corner::UpperLeft, at),
+            Rect::new(Point::new(100, 180), Point::new(140, 200))
+        );
+        assert_eq!(
+            Rect::from_dims_anchored(dims, Corner::UpperRight, at),
+            Rect::new(Point::new(60, 180), Point::new(100, 200))
+        );
+        assert_eq!(
+            Rect::from_dims_anchored(dims, Anchor::Center, at),
+            Rect::new(Point::new(80, 190), Point::new(120, 210))
+        );
+
+        for corner in Corner::all() {
+            let rect = Rect::from_dims_anchored(dims, corner, at);
+            assert_eq!(rect.corner(corner), at);
+            assert_eq!(rect.dims(), dims);
+        }
+    }
+
+    #[test]
+    fn test_rect_from_dims_anchored_odd_center() {
+        let rect = Rect::from_dims_anchored(Dims::new(5, 3), Anchor::Center, Point::new(-10, 0));
+        assert_eq!(rect, Rect::new(Point::new(-12, -1), Point::new(-7, 2)));
+        assert_eq!(rect.dims(), Dims::new(5, 3));
+    }
 }
@@ -5,6 +5,7 @@ use std::hash::Hash;
 use serde::{Deserialize, Serialize};
 
 use crate::transform::Transformation;
+use crate::Dir;
 
 /// A named orientation.
 #[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
@@ -138,6 +139,101 @@ impl Orientation {
         Self::default()
     }
 
+    /// Returns the orientation that rotates by 90 degrees counter-clockwise.
+    ///
+    /// To rotate an existing orientation, use [`Orientation::r90`].
+    #[inline]
+    pub fn rot90() -> Self {
+        Named::R90.into()
+    }
+
+    /// Returns the orientation that rotates by 180 degrees.
+    ///
+    /// To rotate an existing orientation, use [`Orientation::r180`].
+    #[inline]
+    pub fn rot180() -> Self {
+        Named::R180.into()
+    }
+
+    /// Returns the orientation that rotates by 270 degrees counter-clockwise.
+    ///
+    /// To rotate an existing orientation, use [`Orientation::r270`].
+    #[inline]
+    pub fn rot270() -> Self {
+        Named::R270.into()
+    }
+
+    /// Returns the orientation that mirrors about the x-axis.
+    ///
+    /// Equivalent to [`Named::ReflectVert`].
+    #[inline]
+    pub fn mx() -> Self {
+        Named::ReflectVert.into()
+    }
+
+    /// Returns the orientation that mirrors about the y-axis.
+    ///
+    /// Equivalent to [`Named::ReflectHoriz`].
+    #[inline]
+    pub fn my() -> Self {
+        Named::ReflectHoriz.into()
+    }
+
+    /// Returns the orientation that mirrors about the x-axis,
+    /// then rotates by 90 degrees counter-clockwise.
+    ///
+    /// Equivalent to [`Named::FlipYx`].
+    #[inline]
+    pub fn mx_r90() -> Self {
+        Named::FlipYx.into()
+    }
+
+    /// Returns the orientation that mirrors about the y-axis,
+    /// then rotates by 90 degrees counter-clockwise.
+    ///
+    /// Equivalent to [`Named::FlipMinusYx`].
+    #[inline]
+    pub fn my_r90() -> Self {
+        Named::FlipMinusYx.into()
+    }
+
+    /// Returns this orientation followed by a reflection about the axis
+    /// through the origin running in direction `axis`.
+    ///
+    /// Reflecting about [`Dir::Horiz`] (the x-axis) is equivalent to [`Named::ReflectVert`];
+    /// reflecting about [`Dir::Vert`] (the y-axis) is equivalent to [`Named::ReflectHoriz`].
+    #[inline]
+    pub fn reflect(self, axis: Dir) -> Self {
+        self.compose(match axis {
+            Dir::Horiz => Self::mx(),
+            Dir::Vert => Self::my(),
+        })
+    }
+
+    /// Returns this orientation followed by `quarter_turns` counter-clockwise
+    /// rotations by 90 degrees.
+    ///
+    /// Negative values rotate clockwise.
+    #[inline]
+    pub fn rotate(self, quarter_turns: i64) -> Self {
+        self.compose(Self {
+            reflect_vert: false,
+            angle: 90. * quarter_turns.rem_euclid(4) as f64,
+        })
+    }
+
+    /// Returns the orientation equivalent to applying this orientation, then `other`.
+    ///
+    /// Composing orientations is consistent with cascading their transformations:
+    /// the transformation of `a.compose(b)` is `Transformation::cascade(b.into(), a.into())`.
+    /// Note that composition is not commutative.
+    #[inline]
+    pub fn compose(self, other: impl Into<Orientation>) -> Self {
+        let mut o = self;
+        o.apply(other);
+        o
+    }
+
     /// Applies the reflection and rotation specified in
     /// [`Orientation`] `o` to this orientation.
     pub fn apply(&mut self, o: impl Into<Orientation>) {
@@ -271,6 +367,139 @@ mod tests {
 
     use super::*;
 
+    #[test]
+    fn test_compose_reflections() {
+        use Named::*;
+        let o = Orientation::from(ReflectVert).compose(ReflectHoriz);
+        assert_eq!(o, Orientation::rot180());
+        let o = Orientation::from(ReflectVert).compose(ReflectVert);
+        assert_eq!(o, Orientation::identity());
+        let o = Orientation::from(ReflectVert).compose(FlipYx);
+        assert_eq!(o, Orientation::rot90());
+        let o = Orientation::from(FlipYx).compose(ReflectVert);
+        assert_eq!(o, Orientation::rot270());
+
+        // Composing any two reflections yields a rotation.
+        for a in Orientation::all_rectangular() {
+            for b in Orientation::all_rectangular() {
+                if a.reflect_vert && b.reflect_vert {
+                    assert!(!a.compose(b).reflect_vert);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_compose_associative() {
+        let all = Orientation::all_rectangular();
+        for a in all {
+            for b in all {
+                for c in all {
+                    assert_eq!(
+                        a.compose(b).compose(c),
+                        a.compose(b.compose(c)),
+                        "composition is not associative for {a:?}, {b:?}, {c:?}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_compose_matches_cascade() {
+        use crate::transform::Transform;
+        use crate::Point;
+
+        let pts = [Point::new(3, 7), Point::new(-12, 5)];
+        for a in Orientation::all_rectangular() {
+            for b in Orientation::all_rectangular() {
+                let composed = Transformation::from(a.compose(b));
+                let cascaded = Transformation::cascade(b.into(), a.into());
+                for p in pts {
+                    assert_eq!(p.transform(composed), p.transform(cascaded));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_rotation_constructors() {
+        let mut o = Orientation::identity();
+        o.r90();
+        assert_eq!(o, Orientation::rot90());
+        o.r90();
+        assert_eq!(o, Orientation::rot180());
+        o.r90();
+        assert_eq!(o, Orientation::rot270());
+        assert_eq!(
+            Orientation::rot90().compose(Orientation::rot270()),
+            Orientation::identity()
+        );
+    }
+
+    #[test]
+    fn test_named_constructors() {
+        use crate::transform::Transform;
+        use crate::Point;
+
+        let p = Point::new(2, 1);
+        let cases = [
+            (Orientation::identity(), Point::new(2, 1)),
+            (Orientation::rot90(), Point::new(-1, 2)),
+            (Orientation::rot180(), Point::new(-2, -1)),
+            (Orientation::rot270(), Point::new(1, -2)),
+            (Orientation::mx(), Point::new(2, -1)),
+            (Orientation::my(), Point::new(-2, 1)),
+            (Orientation::mx_r90(), Point::new(1, 2)),
+            (Orientation::my_r90(), Point::new(-1, -2)),
+        ];
+        let mut set = HashSet::new();
+        for (o, expected) in cases {
+            assert_eq!(p.transform(o.into()), expected, "wrong image for {o:?}");
+            set.insert(OrientationBytes::from(o));
+        }
+        assert_eq!(set.len(), 8);
+
+        assert_eq!(Orientation::mx(), Orientation::from(Named::ReflectVert));
+        assert_eq!(Orientation::my(), Orientation::from(Named::ReflectHoriz));
+        assert_eq!(Orientation::mx_r90(), Orientation::from(Named::FlipYx));
+        assert_eq!(Orientation::my_r90(), Orientation::from(Named::FlipMinusYx));
+    }
+
+    #[test]
+    fn test_reflect_rotate() {
+        assert_eq!(
+            Orientation::identity().reflect(Dir::Horiz),
+            Orientation::mx()
+        );
+        assert_eq!(
+            Orientation::identity().reflect(Dir::Vert),
+            Orientation::my()
+        );
+        assert_eq!(Orientation::mx().rotate(1), Orientation::mx_r90());
+        assert_eq!(Orientation::my().rotate(1), Orientation::my_r90());
+        assert_eq!(Orientation::identity().rotate(2), Orientation::rot180());
+        assert_eq!(Orientation::identity().rotate(-1), Orientation::rot270());
+        assert_eq!(Orientation::identity().rotate(7), Orientation::rot270());
+        assert_eq!(Orientation::identity().rotate(-4), Orientation::identity());
+
+        for o in Orientation::all_rectangular() {
+            for dir in [Dir::Horiz, Dir::Vert] {
+                assert_eq!(o.reflect(dir).reflect(dir), o);
+            }
+            assert_eq!(o.rotate(1).rotate(-1), o);
+            assert_eq!(o.rotate(3), o.compose(Orientation::rot270()));
+            assert_eq!(
+                o.reflect(Dir::Horiz).reflect(Dir::Vert),
+                o.compose(Orientation::rot180())
+            );
+
+            let mut r = o;
+            r.r90();
+            assert_eq!(o.rotate(1), r);
+        }
+    }
+
     #[test]
     fn test_named_all_rectangular() {
         let opts: [OrientationBytes; 8] = Named::all_rectangular()
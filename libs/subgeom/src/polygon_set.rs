@@ -0,0 +1,220 @@
+//! Point-in-polygon queries against many polygons.
+//!
+//! A [`PolygonSet`] buckets its polygons into a uniform grid of cells
+//! according to their bounding boxes, so that each query only tests
+//! the polygons whose bounding boxes overlap the cell containing the point.
+
+use super::bbox::{Bbox, BboxBuilder, BoundBox};
+use super::{Point, Polygon, ShapeTrait};
+
+/// A collection of [`Polygon`]s indexed for fast point containment queries.
+#[derive(Debug, Clone)]
+pub struct PolygonSet {
+    polygons: Vec<Polygon>,
+    bboxes: Vec<Bbox>,
+    /// The bounding box of all polygons.
+    bbox: Bbox,
+    /// The width and height of each grid cell.
+    cell: (i64, i64),
+    /// The number of grid cells in the x and y directions.
+    dims: (usize, usize),
+    /// The indices of the polygons overlapping each cell, in row-major order.
+    cells: Vec<Vec<usize>>,
+}
+
+impl PolygonSet {
+    /// Creates a new [`PolygonSet`] containing the given polygons.
+    ///
+    /// The grid has roughly one cell per polygon.
+    /// Polygons with no vertices are never considered to contain any point.
+    pub fn new(polygons: Vec<Polygon>) -> Self {
+        let bboxes: Vec<Bbox> = polygons.iter().map(|poly| poly.points.bbox()).collect();
+        let bbox = BboxBuilder::new().add_all(bboxes.iter().copied()).build();
+
+        let n = ((polygons.len() as f64).sqrt().ceil() as usize).max(1);
+        let (dims, cell) = if bbox.is_empty() {
+            ((0, 0), (1, 1))
+        } else {
+            (
+                (n, n),
+                (bbox.width() / n as i64 + 1, bbox.height() / n as i64 + 1),
+            )
+        };
+
+        let mut set = Self {
+            polygons,
+            bboxes,
+            bbox,
+            cell,
+            dims,
+            cells: vec![Vec::new(); dims.0 * dims.1],
+        };
+
+        for (i, bbox) in set.bboxes.iter().enumerate() {
+            if bbox.is_empty() {
+                continue;
+            }
+            let (x0, y0) = set.cell_index(bbox.p0);
+            let (x1, y1) = set.cell_index(bbox.p1);
+            for y in y0..=y1 {
+                for x in x0..=x1 {
+                    set.cells[y * dims.0 + x].push(i);
+                }
+            }
+        }
+
+        set
+    }
+
+    /// Returns the polygons in the set.
+    #[inline]
+    pub fn polygons(&self) -> &[Polygon] {
+        &self.polygons
+    }
+
+    /// Returns the number of polygons in the set.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.polygons.len()
+    }
+
+    /// Returns `true` if the set contains no polygons.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.polygons.is_empty()
+    }
+
+    /// Returns the grid cell containing `pt`, which must lie within the set's bounding box.
+    fn cell_index(&self, pt: Point) -> (usize, usize) {
+        (
+            (((pt.x - self.bbox.p0.x) / self.cell.0) as usize).min(self.dims.0 - 1),
+            (((pt.y - self.bbox.p0.y) / self.cell.1) as usize).min(self.dims.1 - 1),
+        )
+    }
+
+    /// Returns an iterator over the indices of the polygons containing `pt`, in increasing order.
+    ///
+    /// Containment is determined by [`Polygon::contains`](ShapeTrait::contains).
+    pub fn containing(&self, pt: Point) -> impl Iterator<Item = usize> + '_ {
+        let candidates: &[usize] = if self.bbox.contains(pt) {
+            let (x, y) = self.cell_index(pt);
+            &self.cells[y * self.dims.0 + x]
+        } else {
+            &[]
+        };
+        candidates
+            .iter()
+            .copied()
+            .filter(move |&i| self.bboxes[i].contains(pt) && self.polygons[i].contains(pt))
+    }
+
+    /// Returns `true` if any polygon in the set contains `pt`.
+    pub fn contains_any(&self, pt: Point) -> bool {
+        self.containing(pt).next().is_some()
+    }
+
+    /// Returns whether any polygon in the set contains each of the given points.
+    pub fn query_points(&self, pts: &[Point]) -> Vec<bool> {
+        pts.iter().map(|&pt| self.contains_any(pt)).collect()
+    }
+
+    /// Like [`PolygonSet::query_points`], but splits the points across up to `threads` threads.
+    ///
+    /// Results are returned in the same order as `pts`.
+    pub fn query_points_parallel(&self, pts: &[Point], threads: usize) -> Vec<bool> {
+        let chunk_size = pts.len().div_ceil(threads.max(1)).max(1);
+        std::thread::scope(|s| {
+            let handles = pts
+                .chunks(chunk_size)
+                .map(|chunk| s.spawn(move || self.query_points(chunk)))
+                .collect::<Vec<_>>();
+            handles
+                .into_iter()
+                .flat_map(|handle| handle.join().expect("query thread panicked"))
+                .collect()
+        })
+    }
+}
+
+impl FromIterator<Polygon> for PolygonSet {
+    fn from_iter<T: IntoIterator<Item = Polygon>>(iter: T) -> Self {
+        Self::new(iter.into_iter().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A small linear congruential generator, so that tests are deterministic.
+    struct Lcg(u64);
+
+    impl Lcg {
+        fn next(&mut self, max: i64) -> i64 {
+            self.0 = self
+                .0
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            ((self.0 >> 33) % max as u64) as i64
+        }
+    }
+
+    fn polygons() -> Vec<Polygon> {
+        let mut rng = Lcg(42);
+        (0..200)
+            .map(|i| {
+                let (x, y) = (rng.next(1000) - 200, rng.next(1000) - 200);
+                let (w, h) = (rng.next(100) + 1, rng.next(100) + 1);
+                let points = match i % 3 {
+                    0 => vec![
+                        Point::new(x, y),
+                        Point::new(x + w, y),
+                        Point::new(x + w, y + h),
+                        Point::new(x, y + h),
+                    ],
+                    1 => vec![Point::new(x, y), Point::new(x + w, y), Point::new(x, y + h)],
+                    _ => vec![
+                        Point::new(x, y + h / 2),
+                        Point::new(x + w / 2, y),
+                        Point::new(x + w, y + h / 2),
+                        Point::new(x + w / 2, y + h),
+                    ],
+                };
+                Polygon { points }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn matches_brute_force() {
+        let polygons = polygons();
+        let set = PolygonSet::new(polygons.clone());
+        let pts = (-250..950)
+            .step_by(7)
+            .flat_map(|x| (-250..950).step_by(11).map(move |y| Point::new(x, y)))
+            .collect::<Vec<_>>();
+
+        let expected = pts
+            .iter()
+            .map(|&pt| polygons.iter().any(|poly| poly.contains(pt)))
+            .collect::<Vec<_>>();
+        assert!(expected.iter().any(|&hit| hit));
+        assert!(expected.iter().any(|&hit| !hit));
+        assert_eq!(set.query_points(&pts), expected);
+        assert_eq!(set.query_points_parallel(&pts, 4), expected);
+
+        for &pt in pts.iter().step_by(13) {
+            let expected = (0..polygons.len())
+                .filter(|&i| polygons[i].contains(pt))
+                .collect::<Vec<_>>();
+            assert_eq!(set.containing(pt).collect::<Vec<_>>(), expected);
+        }
+    }
+
+    #[test]
+    fn empty_set() {
+        let set = PolygonSet::new(vec![Polygon::default()]);
+        assert!(!set.contains_any(Point::zero()));
+        assert_eq!(set.query_points_parallel(&[], 4), Vec::<bool>::new());
+    }
+}
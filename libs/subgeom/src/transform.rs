@@ -9,7 +9,7 @@ use crate::orientation::wrap_angle;
 
 /// A 2x2 rotation-matrix and two-entry translation vector,
 /// used for relative movement of [Point]s and [Shape](super::Shape)s.
-#[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub struct Transformation {
     /// The transformation matrix represented in row-major order.
     pub a: [[f64; 2]; 2],
@@ -40,6 +40,13 @@ impl Transformation {
             b: [0., 0.],
         }
     }
+    /// Returns a scaling by `x` in the x-dimension and by `y` in the y-dimension.
+    pub fn scale(x: f64, y: f64) -> Self {
+        Self {
+            a: [[x, 0.], [0., y]],
+            b: [0., 0.],
+        }
+    }
     /// Returns a reflection about the x-axis.
     pub fn reflect_vert() -> Self {
         Self {
@@ -95,6 +102,62 @@ impl Transformation {
         Self { a, b }
     }
 
+    /// Returns the transformation that applies `self`, then `next`.
+    ///
+    /// Transforming an object by `t.then(u)` is equivalent to transforming it by `t`,
+    /// then transforming the result by `u`. This is equivalent to
+    /// [`Transformation::cascade(next, self)`](Transformation::cascade).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use subgeom::transform::{Transform, Transformation};
+    /// use subgeom::Point;
+    ///
+    /// let reflect = Transformation::reflect_vert();
+    /// let translate = Transformation::translate(1., 1.);
+    /// let p = Point::new(1, 1);
+    ///
+    /// assert_eq!(p.transform(reflect.then(translate)), Point::new(2, 0));
+    /// assert_eq!(p.transform(translate.then(reflect)), Point::new(2, -2));
+    /// ```
+    #[inline]
+    pub fn then(self, next: Transformation) -> Transformation {
+        Transformation::cascade(next, self)
+    }
+
+    /// Returns the inverse of this transformation.
+    ///
+    /// Transforming an object by a transformation and then by its inverse
+    /// leaves the object unchanged (up to rounding of integer coordinates).
+    /// This maps objects in a placed instance's coordinate frame back to the parent frame.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the transformation matrix is singular.
+    pub fn inverse(&self) -> Transformation {
+        let [[a00, a01], [a10, a11]] = self.a;
+        let det = a00 * a11 - a01 * a10;
+        assert!(det != 0., "cannot invert a singular transformation");
+        let a = [[a11 / det, -a01 / det], [-a10 / det, a00 / det]];
+        let b = matvec(&a, &self.b);
+        Self {
+            a,
+            b: [-b[0], -b[1]],
+        }
+    }
+
+    /// Returns the factor by which this transformation scales lengths.
+    ///
+    /// Computed as the square root of the absolute value of the determinant of the
+    /// transformation matrix. For uniform scaling, this is exactly the scale factor;
+    /// for non-uniform scaling, this is the geometric mean of the scale factors
+    /// along each axis. Rotations and reflections have a magnification of 1.
+    pub fn magnification(&self) -> f64 {
+        let det = self.a[0][0] * self.a[1][1] - self.a[0][1] * self.a[1][0];
+        det.abs().sqrt()
+    }
+
     pub fn offset_point(&self) -> Point {
         Point {
             x: self.b[0].round() as i64,
@@ -102,6 +165,29 @@ impl Transformation {
         }
     }
 
+    /// Returns the [`Orientation`] equivalent to this transformation,
+    /// if this transformation is a pure orientation.
+    ///
+    /// A pure orientation is a rotation, optionally preceded by a vertical reflection,
+    /// with no translation or scaling. Comparisons use a small tolerance
+    /// to account for floating point error.
+    pub fn as_orientation(&self) -> Option<Orientation> {
+        const EPS: f64 = 1e-9;
+        let [[a00, a01], [a10, a11]] = self.a;
+        let is_unit = |x: f64, y: f64| (x * x + y * y - 1.).abs() < EPS;
+
+        let no_translation = self.b.iter().all(|b| b.abs() < EPS);
+        // The columns of the matrix must be orthogonal unit vectors.
+        let orthonormal =
+            is_unit(a00, a10) && is_unit(a01, a11) && (a00 * a01 + a10 * a11).abs() < EPS;
+
+        if no_translation && orthonormal {
+            Some(self.orientation())
+        } else {
+            None
+        }
+    }
+
     pub fn orientation(&self) -> Orientation {
         let reflect_vert = self.a[0][0].signum() != self.a[1][1].signum();
         let sin = self.a[1][0];
@@ -119,6 +205,16 @@ impl Transformation {
     }
 }
 
+impl std::fmt::Display for Transformation {
+    /// Formats the transformation as a 2x3 matrix `[a | b]`,
+    /// with the transformation matrix on the left and the translation on the right.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let [[a00, a01], [a10, a11]] = self.a;
+        let [b0, b1] = self.b;
+        write!(f, "[{a00} {a01} | {b0}; {a10} {a11} | {b1}]")
+    }
+}
+
 impl<T> From<T> for Transformation
 where
     T: Into<Orientation>,
@@ -246,10 +342,12 @@ impl Transform for Polygon {
     }
 }
 impl Transform for Path {
+    /// Transforms the points of the path, scaling its width by the
+    /// [magnification](Transformation::magnification) of `trans`.
     fn transform(&self, trans: Transformation) -> Self {
         Path {
             points: self.points.iter().map(|p| p.transform(trans)).collect(),
-            width: self.width,
+            width: (self.width as f64 * trans.magnification()).round() as usize,
         }
     }
 }
@@ -310,6 +408,20 @@ impl Scalable for Point {
     }
 }
 
+impl Scalable for Path {
+    /// Scales the points of the path, along with its width.
+    ///
+    /// If the scale factors differ between dimensions, the width is scaled by
+    /// their geometric mean, consistent with [`Transformation::magnification`].
+    fn scale(&mut self, p: Point) {
+        for pt in self.points.iter_mut() {
+            pt.scale(p);
+        }
+        let factor = ((p.x as f64) * (p.y as f64)).abs().sqrt();
+        self.width = (self.width as f64 * factor).round() as usize;
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -342,6 +454,91 @@ mod tests {
         }
     }
 
+    #[test]
+    fn inverse_round_trips_points() {
+        let pts = [Point::new(0, 0), Point::new(13, -7), Point::new(-250, 1000)];
+        let locs = [Point::new(0, 0), Point::new(520, -130), Point::new(-3, 17)];
+        for orientation in Named::all_rectangular() {
+            for loc in locs {
+                let tf = Transformation::with_loc_and_orientation(loc, orientation);
+                let inv = tf.inverse();
+                for p in pts {
+                    assert_eq!(
+                        p.transform(tf).transform(inv),
+                        p,
+                        "inverse failed for orientation {orientation:?} at {loc:?}"
+                    );
+                    assert_eq!(p.transform(inv).transform(tf), p);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn inverse_of_cascade() {
+        let p = Point::new(1, 1);
+        let parent = Transformation::with_loc_and_orientation(Point::new(10, 20), Named::R90);
+        let child = Transformation::cascade(
+            Transformation::reflect_vert(),
+            Transformation::translate(1., 1.),
+        );
+        let tf = Transformation::cascade(parent, child);
+        assert_eq!(p.transform(tf).transform(tf.inverse()), p);
+
+        // The inverse of a cascade is the cascade of the inverses in reverse order.
+        let inv = Transformation::cascade(child.inverse(), parent.inverse());
+        assert_eq!(p.transform(tf).transform(inv), p);
+        assert_eq!(
+            Transformation::identity().inverse(),
+            Transformation::identity()
+        );
+    }
+
+    fn mixed_transformations() -> Vec<Transformation> {
+        vec![
+            Transformation::rotate(90.),
+            Transformation::reflect_vert(),
+            Transformation::translate(3., -7.),
+            Transformation::with_loc_and_orientation(Point::new(-4, 9), Named::FlipYx),
+            Transformation::with_loc_and_orientation(Point::new(12, 5), Named::R270),
+        ]
+    }
+
+    #[test]
+    fn then_applies_self_first() {
+        let pts = [Point::new(0, 0), Point::new(1, 1), Point::new(-13, 6)];
+        for t in mixed_transformations() {
+            for u in mixed_transformations() {
+                for p in pts {
+                    assert_eq!(p.transform(t.then(u)), p.transform(t).transform(u));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn then_is_associative() {
+        let pts = [Point::new(0, 0), Point::new(1, 1), Point::new(-13, 6)];
+        let tfs = mixed_transformations();
+        for &a in tfs.iter() {
+            for &b in tfs.iter() {
+                for &c in tfs.iter() {
+                    let left = a.then(b).then(c);
+                    let right = a.then(b.then(c));
+                    for p in pts {
+                        assert_eq!(p.transform(left), p.transform(right));
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "singular")]
+    fn inverse_of_singular_transformation() {
+        Transformation::scale(0., 1.).inverse();
+    }
+
     #[test]
     fn transformation_loc_and_orientation_preserves_components() {
         let pt = Point::new(8930, 730);
@@ -365,6 +562,86 @@ mod tests {
         }
     }
 
+    #[test]
+    fn transformation_as_orientation() {
+        for orientation in Named::all_rectangular() {
+            let tf = Transformation::from(orientation);
+            assert_eq!(
+                tf.as_orientation(),
+                Some(orientation.into()),
+                "incorrect orientation for {orientation:?}"
+            );
+        }
+        assert_eq!(
+            Transformation::identity().as_orientation(),
+            Some(Orientation::identity())
+        );
+
+        let translated = Transformation::with_loc_and_orientation(Point::new(10, 0), Named::R90);
+        assert_eq!(translated.as_orientation(), None);
+        assert_eq!(Transformation::scale(2., 2.).as_orientation(), None);
+        assert_eq!(Transformation::scale(1., 2.).as_orientation(), None);
+    }
+
+    #[test]
+    fn transformation_serde_round_trip() {
+        let pts = [Point::new(0, 0), Point::new(13, -7), Point::new(-250, 1000)];
+        for orientation in Named::all_rectangular() {
+            let tf = Transformation::with_loc_and_orientation(Point::new(520, -130), orientation);
+            let serialized = serde_json::to_string(&tf).unwrap();
+            let restored: Transformation = serde_json::from_str(&serialized).unwrap();
+            for pt in pts {
+                assert_eq!(pt.transform(restored), pt.transform(tf));
+            }
+        }
+    }
+
+    #[test]
+    fn transformation_display() {
+        let tf = Transformation::translate(10., -5.);
+        assert_eq!(tf.to_string(), "[1 0 | 10; 0 1 | -5]");
+
+        let tf = Transformation::reflect_vert();
+        assert_eq!(tf.to_string(), "[1 0 | 0; 0 -1 | 0]");
+    }
+
+    #[test]
+    fn path_transform_scales_width() {
+        let path = Path {
+            points: vec![Point::new(0, 0), Point::new(10, 0), Point::new(10, 5)],
+            width: 4,
+        };
+
+        let scaled = path.transform(Transformation::scale(2., 2.));
+        assert_eq!(
+            scaled.points,
+            vec![Point::new(0, 0), Point::new(20, 0), Point::new(20, 10)]
+        );
+        assert_eq!(scaled.width, 8);
+
+        // Rotations and translations preserve the width.
+        let tf = Transformation::with_loc_and_orientation(Point::new(3, 7), Named::R90);
+        assert_eq!(path.transform(tf).width, 4);
+
+        // Non-uniform scaling uses the geometric mean.
+        let scaled = path.transform(Transformation::scale(2., 8.));
+        assert_eq!(scaled.width, 16);
+    }
+
+    #[test]
+    fn path_scale_scales_width() {
+        let mut path = Path {
+            points: vec![Point::new(1, 1), Point::new(1, 6)],
+            width: 3,
+        };
+        path.scale(Point::new(2, 2));
+        assert_eq!(path.points, vec![Point::new(2, 2), Point::new(2, 12)]);
+        assert_eq!(path.width, 6);
+
+        path.scale(Point::new(1, 4));
+        assert_eq!(path.width, 12);
+    }
+
     #[test]
     fn point_transformations_work() {
         let pt = Point::new(2, 1);
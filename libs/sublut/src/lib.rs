@@ -2,23 +2,96 @@ use derive_builder::Builder;
 use serde::{Deserialize, Serialize};
 use splines::{Key, Spline};
 
-// TODO verify that length of keys and values match, and that k1 is sorted
 #[derive(Debug, Default, Clone, Eq, PartialEq, Builder, Serialize, Deserialize)]
-#[builder(pattern = "owned")]
-pub struct Lut1<K1, V> {
+#[builder(pattern = "owned", build_fn(validate = "Self::validate"))]
+pub struct Lut1<K1, V>
+where
+    K1: PartialOrd,
+{
+    /// Keys, which must be strictly increasing.
     k1: Vec<K1>,
+    /// Values, with one value per key in `k1`.
     values: Vec<V>,
 }
 
+impl<K1, V> Lut1Builder<K1, V>
+where
+    K1: PartialOrd,
+{
+    /// Checks that the keys are strictly increasing
+    /// and that the number of values matches the number of keys.
+    fn validate(&self) -> Result<(), String> {
+        if self.k1.as_deref().map(is_sorted) == Some(false) {
+            return Err("LUT keys k1 must be strictly increasing".to_string());
+        }
+        if let (Some(k1), Some(values)) = (&self.k1, &self.values) {
+            if values.len() != k1.len() {
+                return Err(format!(
+                    "LUT has {} values, but k1 has {}",
+                    values.len(),
+                    keys(k1.len())
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
 #[derive(Debug, Default, Clone, Eq, PartialEq, Builder, Serialize, Deserialize)]
-#[builder(pattern = "owned")]
-pub struct Lut2<K1, K2, V> {
+#[builder(pattern = "owned", build_fn(validate = "Self::validate"))]
+pub struct Lut2<K1, K2, V>
+where
+    K1: PartialOrd,
+    K2: PartialOrd,
+{
+    /// Row keys, which must be strictly increasing.
     k1: Vec<K1>,
+    /// Column keys, which must be strictly increasing.
     k2: Vec<K2>,
-    // row major order
+    /// Values in row major order.
+    ///
+    /// There must be one row per key in `k1`,
+    /// and each row must have one value per key in `k2`.
     values: Vec<Vec<V>>,
 }
 
+impl<K1, K2, V> Lut2Builder<K1, K2, V>
+where
+    K1: PartialOrd,
+    K2: PartialOrd,
+{
+    /// Checks that the keys are strictly increasing
+    /// and that the dimensions of the values match the number of keys.
+    fn validate(&self) -> Result<(), String> {
+        if self.k1.as_deref().map(is_sorted) == Some(false) {
+            return Err("LUT keys k1 must be strictly increasing".to_string());
+        }
+        if self.k2.as_deref().map(is_sorted) == Some(false) {
+            return Err("LUT keys k2 must be strictly increasing".to_string());
+        }
+        if let (Some(k1), Some(values)) = (&self.k1, &self.values) {
+            if values.len() != k1.len() {
+                return Err(format!(
+                    "LUT has {} rows, but k1 has {}",
+                    values.len(),
+                    keys(k1.len())
+                ));
+            }
+        }
+        if let (Some(k2), Some(values)) = (&self.k2, &self.values) {
+            if let Some((i, row)) = values.iter().enumerate().find(|(_, r)| r.len() != k2.len()) {
+                return Err(format!(
+                    "LUT row {} has {} values, but k2 has {}",
+                    i,
+                    row.len(),
+                    keys(k2.len())
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
 #[derive(Debug, Default, Clone, Copy, Eq, PartialEq)]
 pub enum Extrapolation {
     #[default]
@@ -26,7 +99,34 @@ pub enum Extrapolation {
     RoundUp,
 }
 
-impl<K1, K2, V> Lut2<K1, K2, V> {
+/// Specifies how [`FloatLut2::getf_with`] interpolates between keys.
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq)]
+pub enum InterpMode {
+    /// Linearly interpolate between keys.
+    ///
+    /// Lookups outside the range of keys fail.
+    #[default]
+    Linear,
+    /// Return the value at the closest keys, as in [`Lut2::get_nearest`].
+    Nearest,
+    /// Clamp the lookup keys to the range of keys, then linearly interpolate.
+    ClampedLinear,
+}
+
+impl<K1, V> Lut1<K1, V>
+where
+    K1: PartialOrd,
+{
+    pub fn builder() -> Lut1Builder<K1, V> {
+        Default::default()
+    }
+}
+
+impl<K1, K2, V> Lut2<K1, K2, V>
+where
+    K1: PartialOrd,
+    K2: PartialOrd,
+{
     pub fn builder() -> Lut2Builder<K1, K2, V> {
         Default::default()
     }
@@ -47,31 +147,142 @@ where
     }
 }
 
+impl<V> Lut2<f64, f64, V> {
+    /// Returns the value at the key pair closest to `(k1, k2)`.
+    ///
+    /// Unlike [`Lut2::get`], keys outside the range of the table are
+    /// clamped to the nearest key. Ties are broken in favor of the smaller key.
+    /// Returns [`None`] if the table is empty.
+    pub fn get_nearest(&self, k1: f64, k2: f64) -> Option<&V> {
+        let i1 = nearest_idx(&self.k1, k1)?;
+        let i2 = nearest_idx(&self.k2, k2)?;
+        self.values.get(i1)?.get(i2)
+    }
+}
+
+/// Formats a number of keys for error messages, such as `1 key` or `3 keys`.
+fn keys(n: usize) -> String {
+    if n == 1 {
+        "1 key".to_string()
+    } else {
+        format!("{n} keys")
+    }
+}
+
+/// Returns `true` if `keys` is strictly increasing.
+fn is_sorted<K: PartialOrd>(keys: &[K]) -> bool {
+    keys.windows(2).all(|w| w[0] < w[1])
+}
+
+/// Returns the index of the element of the sorted slice `keys` closest to `k`.
+fn nearest_idx(keys: &[f64], k: f64) -> Option<usize> {
+    let i = keys.partition_point(|x| *x < k);
+    if i == 0 {
+        return (!keys.is_empty()).then_some(0);
+    }
+    if i == keys.len() {
+        return Some(i - 1);
+    }
+    if k - keys[i - 1] <= keys[i] - k {
+        Some(i - 1)
+    } else {
+        Some(i)
+    }
+}
+
+impl FloatLut1 {
+    fn spline(&self) -> Spline<f64, f64> {
+        Spline::from_vec(
+            self.k1
+                .iter()
+                .copied()
+                .zip(self.values.iter().copied())
+                .map(|(k, v)| Key::new(k, v, splines::Interpolation::Linear))
+                .collect(),
+        )
+    }
+
+    /// Linearly interpolates the value at `k1`.
+    ///
+    /// Returns [`None`] if `k1` is outside the range of keys in the table.
+    /// A table with a single key returns its sole value for any `k1`.
+    pub fn getf(&self, k1: f64) -> Option<f64> {
+        match (self.k1.as_slice(), self.values.as_slice()) {
+            ([], _) | (_, []) => None,
+            ([_], [v]) => Some(*v),
+            _ => self.spline().sample(k1),
+        }
+    }
+
+    /// Like [`FloatLut1::getf`], but clamps `k1` to the range of keys in the table.
+    ///
+    /// Returns [`None`] only if the table is empty.
+    pub fn getf_clamped(&self, k1: f64) -> Option<f64> {
+        self.spline().clamped_sample(k1)
+    }
+
+    pub fn getf_extrapolate(&self, mut k1: f64, extrapolate: Extrapolation) -> Option<f64> {
+        if extrapolate == Extrapolation::RoundUp {
+            k1 = k1.max(*self.k1.first()?);
+        }
+
+        self.getf(k1)
+    }
+}
+
 impl FloatLut2 {
     pub fn getf(&self, k1: f64, k2: f64) -> Option<f64> {
+        self.interp(k1, k2, Spline::sample)
+    }
+
+    /// Looks up the value at `(k1, k2)`, interpolating according to `mode`.
+    ///
+    /// With [`InterpMode::Linear`], this is equivalent to [`FloatLut2::getf`].
+    /// With [`InterpMode::Nearest`] or [`InterpMode::ClampedLinear`],
+    /// returns [`None`] only if the table is empty.
+    pub fn getf_with(&self, k1: f64, k2: f64, mode: InterpMode) -> Option<f64> {
+        match mode {
+            InterpMode::Linear => self.getf(k1, k2),
+            InterpMode::Nearest => self.get_nearest(k1, k2).copied(),
+            InterpMode::ClampedLinear => self.interp(k1, k2, Spline::clamped_sample),
+        }
+    }
+
+    /// Interpolates along each row, then along the column of interpolated values,
+    /// using `sample` to sample each spline.
+    fn interp(
+        &self,
+        k1: f64,
+        k2: f64,
+        sample: impl Fn(&Spline<f64, f64>, f64) -> Option<f64>,
+    ) -> Option<f64> {
         let interp1 = (0..self.k1.len())
             .map(|i| {
-                Spline::from_vec(
-                    self.k2
-                        .iter()
-                        .copied()
-                        .zip(self.values.get(i)?.iter().copied())
-                        .map(|(k, v)| Key::new(k, v, splines::Interpolation::Linear))
-                        .collect(),
+                sample(
+                    &Spline::from_vec(
+                        self.k2
+                            .iter()
+                            .copied()
+                            .zip(self.values.get(i)?.iter().copied())
+                            .map(|(k, v)| Key::new(k, v, splines::Interpolation::Linear))
+                            .collect(),
+                    ),
+                    k2,
                 )
-                .sample(k2)
             })
             .collect::<Option<Vec<f64>>>()?;
 
-        Spline::from_vec(
-            self.k1
-                .iter()
-                .copied()
-                .zip(interp1.into_iter())
-                .map(|(k, v)| Key::new(k, v, splines::Interpolation::Linear))
-                .collect(),
+        sample(
+            &Spline::from_vec(
+                self.k1
+                    .iter()
+                    .copied()
+                    .zip(interp1.into_iter())
+                    .map(|(k, v)| Key::new(k, v, splines::Interpolation::Linear))
+                    .collect(),
+            ),
+            k1,
         )
-        .sample(k1)
     }
 
     pub fn getf_extrapolate(
@@ -127,5 +338,212 @@ mod tests {
         assert!(float_eq!(lut.getf(5., 2.5).unwrap(), 7., r2nd <= 1e-8));
         assert!(float_eq!(lut.getf(6.5, 1.5).unwrap(), 3.75, r2nd <= 1e-8));
         assert_eq!(lut.getf(4.5, 2.5), None);
+
+        // Out of range lookups in each mode.
+        assert_eq!(lut.getf_with(4.5, 2.5, InterpMode::Linear), None);
+        assert_eq!(lut.getf_with(8., 0., InterpMode::Linear), None);
+        assert_eq!(lut.getf_with(4.5, 2.4, InterpMode::Nearest), Some(5.));
+        assert_eq!(lut.getf_with(8., 0., InterpMode::Nearest), Some(3.));
+        assert!(float_eq!(
+            lut.getf_with(4.5, 2.5, InterpMode::ClampedLinear).unwrap(),
+            7.,
+            r2nd <= 1e-8
+        ));
+        assert!(float_eq!(
+            lut.getf_with(6.5, 10., InterpMode::ClampedLinear).unwrap(),
+            7.5,
+            r2nd <= 1e-8
+        ));
+        assert!(float_eq!(
+            lut.getf_with(8., 0., InterpMode::ClampedLinear).unwrap(),
+            3.,
+            r2nd <= 1e-8
+        ));
+
+        // In range lookups agree with `getf`, except in nearest mode.
+        assert_eq!(
+            lut.getf_with(6.5, 1.5, InterpMode::Linear),
+            lut.getf(6.5, 1.5)
+        );
+        assert!(float_eq!(
+            lut.getf_with(6.5, 1.5, InterpMode::ClampedLinear).unwrap(),
+            3.75,
+            r2nd <= 1e-8
+        ));
+        assert_eq!(lut.getf_with(6.5, 1.5, InterpMode::Nearest), Some(2.));
+
+        let empty = FloatLut2::default();
+        assert_eq!(empty.getf_with(0., 0., InterpMode::Nearest), None);
+        assert_eq!(empty.getf_with(0., 0., InterpMode::ClampedLinear), None);
+    }
+
+    #[test]
+    fn test_lut_mismatched_rows() {
+        let err = Lut2::<u64, u64, u64>::builder()
+            .k1(vec![5, 6, 7])
+            .k2(vec![1, 2, 3])
+            .values(vec![vec![1, 5, 9], vec![2, 4, 8]])
+            .build()
+            .unwrap_err();
+        assert!(err.to_string().contains("2 rows, but k1 has 3 keys"));
+    }
+
+    #[test]
+    fn test_lut1_mismatched_values() {
+        let err = FloatLut1::builder()
+            .k1(vec![1., 2., 3.])
+            .values(vec![1., 5.])
+            .build()
+            .unwrap_err();
+        assert!(err.to_string().contains("2 values, but k1 has 3 keys"));
+
+        let err = Lut1::<u64, u64>::builder()
+            .k1(vec![1])
+            .values(vec![1, 5])
+            .build()
+            .unwrap_err();
+        assert!(err.to_string().contains("2 values, but k1 has 1 key"));
+    }
+
+    #[test]
+    fn test_lut_mismatched_columns() {
+        let err = FloatLut2::builder()
+            .k1(vec![5., 6., 7.])
+            .k2(vec![1., 2., 3.])
+            .values(vec![vec![1., 5., 9.], vec![2., 4.], vec![3., 6., 7.]])
+            .build()
+            .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("row 1 has 2 values, but k2 has 3 keys"));
+    }
+
+    #[test]
+    fn test_lut_unsorted_keys() {
+        let err = FloatLut2::builder()
+            .k1(vec![7., 6., 5.])
+            .k2(vec![1., 2., 3.])
+            .values(vec![vec![1., 5., 9.], vec![2., 4., 8.], vec![3., 6., 7.]])
+            .build()
+            .unwrap_err();
+        assert!(err.to_string().contains("k1 must be strictly increasing"));
+
+        let err = Lut2::<u64, u64, u64>::builder()
+            .k1(vec![5, 6, 7])
+            .k2(vec![1, 2, 2])
+            .values(vec![vec![1, 5, 9], vec![2, 4, 8], vec![3, 6, 7]])
+            .build()
+            .unwrap_err();
+        assert!(err.to_string().contains("k2 must be strictly increasing"));
+
+        let err = FloatLut1::builder()
+            .k1(vec![1., 3., 2.])
+            .values(vec![1., 5., 9.])
+            .build()
+            .unwrap_err();
+        assert!(err.to_string().contains("k1 must be strictly increasing"));
+
+        let err = Lut1::<u64, u64>::builder()
+            .k1(vec![1, 1])
+            .values(vec![1, 5])
+            .build()
+            .unwrap_err();
+        assert!(err.to_string().contains("k1 must be strictly increasing"));
+    }
+
+    #[test]
+    fn test_lut_nearest() {
+        let lut = Lut2::<f64, f64, &str>::builder()
+            .k1(vec![5., 6., 7.])
+            .k2(vec![1., 2., 3.])
+            .values(vec![
+                vec!["a", "b", "c"],
+                vec!["d", "e", "f"],
+                vec!["g", "h", "i"],
+            ])
+            .build()
+            .unwrap();
+
+        assert_eq!(lut.get_nearest(5., 2.), Some(&"b"));
+        // `get` would select the next largest keys, (6, 2).
+        assert_eq!(lut.get_nearest(5.4, 1.2), Some(&"a"));
+        assert_eq!(lut.get_nearest(5.6, 1.8), Some(&"e"));
+        // Ties go to the smaller key.
+        assert_eq!(lut.get_nearest(6.5, 2.5), Some(&"e"));
+        // Out of range keys are clamped.
+        assert_eq!(lut.get_nearest(0., 10.), Some(&"c"));
+        assert_eq!(lut.get_nearest(100., -10.), Some(&"g"));
+
+        let empty = Lut2::<f64, f64, &str>::default();
+        assert_eq!(empty.get_nearest(0., 0.), None);
+
+        let lut = Lut2::<u64, u64, u64>::builder()
+            .k1(vec![5, 6, 7])
+            .k2(vec![1, 2, 3])
+            .values(vec![vec![1, 5, 9], vec![2, 4, 8], vec![3, 6, 7]])
+            .build()
+            .unwrap();
+        let flut = Lut2::<f64, f64, u64>::builder()
+            .k1(vec![5., 6., 7.])
+            .k2(vec![1., 2., 3.])
+            .values(vec![vec![1, 5, 9], vec![2, 4, 8], vec![3, 6, 7]])
+            .build()
+            .unwrap();
+        // Both agree on exact keys.
+        assert_eq!(lut.get(&6, &2), flut.get_nearest(6., 2.));
+        assert_eq!(flut.get_nearest(6.2, 2.9), Some(&8));
+    }
+
+    #[test]
+    fn test_lut1_f64() {
+        let lut = FloatLut1::builder()
+            .k1(vec![1., 2., 3.])
+            .values(vec![1., 5., 9.])
+            .build()
+            .unwrap();
+
+        assert!(float_eq!(lut.getf(2.).unwrap(), 5., r2nd <= 1e-8));
+        assert!(float_eq!(lut.getf(2.5).unwrap(), 7., r2nd <= 1e-8));
+        assert!(float_eq!(lut.getf(1.25).unwrap(), 2., r2nd <= 1e-8));
+        assert_eq!(lut.getf(0.5), None);
+        assert_eq!(lut.getf(3.5), None);
+
+        assert!(float_eq!(
+            lut.getf_extrapolate(0.5, Extrapolation::RoundUp).unwrap(),
+            1.,
+            r2nd <= 1e-8
+        ));
+        assert_eq!(lut.getf_extrapolate(0.5, Extrapolation::None), None);
+
+        assert!(float_eq!(lut.getf_clamped(0.5).unwrap(), 1., r2nd <= 1e-8));
+        assert!(float_eq!(lut.getf_clamped(2.5).unwrap(), 7., r2nd <= 1e-8));
+        assert!(float_eq!(lut.getf_clamped(3.5).unwrap(), 9., r2nd <= 1e-8));
+    }
+
+    #[test]
+    fn test_lut1_f64_edge_cases() {
+        let lut = FloatLut1::builder()
+            .k1(vec![-1., 1.])
+            .values(vec![4., 2.])
+            .build()
+            .unwrap();
+        assert!(float_eq!(lut.getf(-1.).unwrap(), 4., r2nd <= 1e-8));
+        assert!(float_eq!(lut.getf(0.).unwrap(), 3., r2nd <= 1e-8));
+        assert!(float_eq!(lut.getf(0.5).unwrap(), 2.5, r2nd <= 1e-8));
+        assert_eq!(lut.getf(-1.5), None);
+        assert_eq!(lut.getf(1.5), None);
+
+        let single = FloatLut1::builder()
+            .k1(vec![2.])
+            .values(vec![3.])
+            .build()
+            .unwrap();
+        assert_eq!(single.getf(2.), Some(3.));
+        assert_eq!(single.getf(2.5), Some(3.));
+        assert_eq!(single.getf_clamped(2.5), Some(3.));
+
+        let empty = FloatLut1::default();
+        assert_eq!(empty.getf(0.), None);
+        assert_eq!(empty.getf_clamped(0.), None);
     }
 }
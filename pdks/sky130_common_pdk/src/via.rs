@@ -75,7 +75,7 @@ impl Sky130Pdk {
             Dims::square(space),
         );
         let generator = MaxViaArray::new(dims, bot, top, via, params.expand, 5);
-        let elems = generator.draw(params.bot, params.top);
+        let elems = generator.try_draw(params.bot, params.top)?;
 
         if (bot_name, top_name) == ("poly", "li1") {
             ctx.draw_rect(
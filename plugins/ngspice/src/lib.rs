@@ -6,9 +6,9 @@ use anyhow::{bail, Result};
 use spice_rawfile::Rawfile;
 use substrate::error::ErrorSource;
 use substrate::verification::simulation::{
-    AcAnalysis, AcData, Analysis, AnalysisData, AnalysisType, DcAnalysis, DcData, OpAnalysis,
-    OpData, Quantity, RealSignal, ScalarSignal, SimInput, SimOutput, Simulator, SimulatorOpts,
-    SweepMode, TranAnalysis, TranData,
+    AcAnalysis, AcData, Analysis, AnalysisData, AnalysisType, DcAnalysis, DcData,
+    NamedTerminalPathBuf, OpAnalysis, OpData, Quantity, RealSignal, Save, SaveItem, ScalarSignal,
+    SimInput, SimOutput, Simulator, SimulatorOpts, SweepMode, TranAnalysis, TranData,
 };
 use templates::{render_netlist, NetlistCtx};
 
@@ -16,6 +16,12 @@ pub(crate) mod templates;
 #[cfg(test)]
 mod tests;
 
+/// The ngspice simulator plugin.
+///
+/// Keys of [`SimInput::ic`] and [`NoiseAnalysis::output`] separate instance names with `.`,
+/// as in `dut.x` for node `x` within instance `dut`.
+///
+/// [`NoiseAnalysis::output`]: substrate::verification::simulation::NoiseAnalysis::output
 pub struct Ngspice {}
 
 impl Simulator for Ngspice {
@@ -60,19 +66,80 @@ impl Simulator for Ngspice {
         &self,
         path: &substrate::schematic::signal::NamedSignalPathBuf,
     ) -> String {
-        use std::fmt::Write;
-        let mut s = String::new();
-        s.push_str("v(");
-        for inst in path.insts.iter() {
-            s.push_str(inst);
-            s.push('.');
-        }
-        s.push_str(&path.signal);
-        if let Some(idx) = path.idx {
-            write!(&mut s, "[{idx}]").expect("failed to write node voltage string");
-        }
-        s.push(')');
-        s
+        node_voltage_string(path)
+    }
+}
+
+/// Formats the path through the given instances as an ngspice hierarchical name prefix,
+/// such as `xdut.xinv.`.
+///
+/// Substrate netlists each instance with an `X` prefix, and ngspice lowercases names.
+fn instance_path_prefix(insts: &[impl AsRef<str>]) -> String {
+    let mut s = String::new();
+    for inst in insts {
+        s.push('x');
+        s.push_str(&inst.as_ref().to_lowercase());
+        s.push('.');
+    }
+    s
+}
+
+/// Formats the given signal path as an ngspice node voltage vector, such as `v(xdut.out)`.
+///
+/// ngspice lowercases all vector names, so the signal name is lowercased as well.
+fn node_voltage_string(path: &substrate::schematic::signal::NamedSignalPathBuf) -> String {
+    use std::fmt::Write;
+    let mut s = String::new();
+    s.push_str("v(");
+    s.push_str(&instance_path_prefix(&path.insts));
+    s.push_str(&path.signal.to_lowercase());
+    if let Some(idx) = path.idx {
+        write!(&mut s, "[{idx}]").expect("failed to write node voltage string");
+    }
+    s.push(')');
+    s
+}
+
+/// Formats the given node name as a node voltage, as in [`node_voltage_string`].
+///
+/// Used for initial conditions and noise outputs. Hierarchical names are separated by `.`, so `dut.x` refers to node `x`
+/// within instance `dut` and is formatted as `v(xdut.x)`.
+/// Names that are already of the form `v(...)` are left unchanged.
+fn named_node_string(node: &str) -> String {
+    if node.starts_with("v(") && node.ends_with(')') {
+        return node.to_string();
+    }
+    let mut insts = node.split('.').map(Into::into).collect::<Vec<_>>();
+    // `split` always yields at least one item.
+    let signal = insts.pop().unwrap();
+    node_voltage_string(&substrate::schematic::signal::NamedSignalPathBuf {
+        insts,
+        signal,
+        idx: None,
+    })
+}
+
+/// Formats the given terminal path as an ngspice device current vector,
+/// such as `@m.xdut.m0[id]` for the drain current of MOSFET `M0` in instance `dut`.
+///
+/// Within subcircuits, ngspice names devices by their type letter followed by their
+/// hierarchical path. MOSFET and BJT currents are selected by terminal (e.g. `id` or `ic`);
+/// diodes report `id`, and other devices report the current through the device, `i`.
+fn terminal_current_string(path: &NamedTerminalPathBuf) -> String {
+    let device = path.device.to_lowercase();
+    let kind = device.chars().next().unwrap_or_default();
+    let param = match kind {
+        'm' | 'q' => format!("i{}", path.terminal.to_lowercase()),
+        'd' => "id".to_string(),
+        _ => "i".to_string(),
+    };
+    if path.insts.is_empty() {
+        format!("@{device}[{param}]")
+    } else {
+        format!(
+            "@{kind}.{}{device}[{param}]",
+            instance_path_prefix(&path.insts)
+        )
     }
 }
 
@@ -88,13 +155,57 @@ fn get_directives(input: &SimInput) -> Vec<String> {
     if let Some(t) = input.opts.tnom {
         directives.push(format!(".options tnom={t}"));
     }
+    if let Some(gmin) = input.opts.gmin {
+        directives.push(format!(".options gmin={gmin}"));
+    }
+    if let Some(reltol) = input.opts.reltol {
+        directives.push(format!(".options reltol={reltol}"));
+    }
+    // ngspice calls the absolute current tolerance `abstol`.
+    if let Some(iabstol) = input.opts.iabstol {
+        directives.push(format!(".options abstol={iabstol}"));
+    }
+    // Sort the initial conditions so that netlists are deterministic.
+    let mut ic = input.ic.iter().collect::<Vec<_>>();
+    ic.sort_by_key(|(node, _)| *node);
+    for (node, value) in ic {
+        directives.push(format!(".ic {}={value}", named_node_string(node)));
+    }
+    if let Save::Signals(ref signals) = input.save {
+        // Sort the saves so that netlists are deterministic.
+        let mut saves = signals
+            .iter()
+            .map(|item| match item {
+                SaveItem::NodeVoltage(path) => format!(".save {}", node_voltage_string(path)),
+                SaveItem::DeviceCurrent(path) => {
+                    format!(".save {}", terminal_current_string(path))
+                }
+                SaveItem::Raw(s) => format!(".save {s}"),
+            })
+            .collect::<Vec<_>>();
+        saves.sort();
+        directives.extend(saves);
+    }
+    // Sort the options so that netlists are deterministic.
+    let mut other = input.opts.other.iter().collect::<Vec<_>>();
+    other.sort();
+    for (key, value) in other {
+        directives.push(format!(".options {key}={value}"));
+    }
     directives
 }
 
 fn analysis_line(input: &Analysis) -> Result<String> {
     Ok(match input {
         Analysis::Op(_) => String::from(".op"),
-        Analysis::Tran(a) => format!(".tran {} {} {}", a.step, a.stop, a.start),
+        Analysis::Tran(a) => {
+            if let Some(strobe) = a.strobe_period {
+                log::warn!(
+                    "ngspice does not support strobing; ignoring strobe period of {strobe} and saving every time step"
+                );
+            }
+            format!(".tran {} {} {}", a.step, a.stop, a.start)
+        }
         Analysis::Ac(a) => format!(
             ".ac {} {} {} {}",
             fmt_sweep_mode(a.sweep),
@@ -103,6 +214,16 @@ fn analysis_line(input: &Analysis) -> Result<String> {
             a.fstop
         ),
         Analysis::Dc(a) => format!(".dc {} {} {} {}", a.sweep, a.start, a.stop, a.step),
+        // Noise results are not parsed, so they are returned as `AnalysisData::Other`.
+        Analysis::Noise(a) => format!(
+            ".noise {} {} {} {} {} {}",
+            named_node_string(&a.output),
+            a.input_source,
+            fmt_sweep_mode(a.sweep),
+            a.points,
+            a.fstart,
+            a.fstop
+        ),
         Analysis::MonteCarlo(_) => {
             bail!("ngspice plugin does not support Monte Carlo analyses");
         }
@@ -131,13 +252,18 @@ fn arrange_rawfile(input: &SimInput, raw: Rawfile) -> Result<Vec<AnalysisData>>
     let mut out = vec![AnalysisData::Other; input.analyses.len()];
     for an in raw.analyses {
         let t = atype(&an);
-        let (idx, ian) = input
+        if t == AnalysisType::Other {
+            // Analyses that Substrate does not model are left as `AnalysisData::Other`.
+            continue;
+        }
+        if let Some((idx, ian)) = input
             .analyses
             .iter()
             .enumerate()
             .find(|(_, a)| a.analysis_type() == t)
-            .unwrap();
-        out[idx] = parse_analysis(ian, an)?;
+        {
+            out[idx] = parse_analysis(ian, an)?;
+        }
     }
     Ok(out)
 }
@@ -153,7 +279,7 @@ fn atype(raw: &spice_rawfile::parser::Analysis) -> AnalysisType {
     } else if name.contains("dc transfer") {
         AnalysisType::Dc
     } else {
-        panic!("unknown analysis type {name}")
+        AnalysisType::Other
     }
 }
 
@@ -165,6 +291,7 @@ fn parse_analysis(input: &Analysis, output: RawAnalysis) -> Result<AnalysisData>
         Analysis::Tran(tran) => AnalysisData::Tran(parse_tran(tran, output)),
         Analysis::Op(op) => AnalysisData::Op(parse_op(op, output)),
         Analysis::Dc(dc) => AnalysisData::Dc(parse_dc(dc, output)),
+        Analysis::Noise(_) => AnalysisData::Other,
         Analysis::MonteCarlo(_) => bail!("ngspice plugin does not support Monte Carlo analyses"),
     })
 }
@@ -259,6 +386,7 @@ fn parse_qty(name: &str) -> Quantity {
         "frequency" => Quantity::Frequency,
         "time" => Quantity::Time,
         "temp" | "temp-sweep" | "temperature" => Quantity::Temperature,
+        "notype" => Quantity::Unknown,
         _ => panic!("unknown quantity"),
     }
 }
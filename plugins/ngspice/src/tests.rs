@@ -1,11 +1,18 @@
+use std::collections::{HashMap, HashSet};
 use std::path::PathBuf;
 
+use spice_rawfile::parser::{Analysis as RawAnalysis, Data};
+use spice_rawfile::Rawfile;
+use substrate::schematic::signal::NamedSignalPathBuf;
+use substrate::units::{SiPrefix, SiValue};
 use substrate::verification::simulation::{
-    AcAnalysis, Analysis, AnalysisType, DcAnalysis, OpAnalysis, SimInput, Simulator, SimulatorOpts,
+    AcAnalysis, Analysis, AnalysisData, AnalysisType, DcAnalysis, NamedTerminalPathBuf,
+    NoiseAnalysis, OpAnalysis, Save, SaveItem, SimInput, SimOpts, Simulator, SimulatorOpts,
     SweepMode, TranAnalysis,
 };
 
-use crate::Ngspice;
+use crate::templates::{render_netlist, NetlistCtx};
+use crate::{arrange_rawfile, atype, get_analyses, get_directives, node_voltage_string, Ngspice};
 
 pub(crate) const TEST_BUILD_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/build");
 pub(crate) const EXAMPLES_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/examples");
@@ -61,3 +68,210 @@ fn vdivider_test() {
     assert_eq!(out.data[2].analysis_type(), AnalysisType::Ac);
     assert_eq!(out.data[3].analysis_type(), AnalysisType::Dc);
 }
+
+#[test]
+fn unknown_analysis_type() {
+    let noise = RawAnalysis {
+        title: "* unknown analysis test",
+        date: "",
+        plotname: "Noise Spectral Density Curves",
+        flags: "real",
+        num_variables: 0,
+        num_points: 0,
+        variables: Vec::new(),
+        data: Data::Real(Vec::new()),
+    };
+    assert_eq!(atype(&noise), AnalysisType::Other);
+
+    let input = SimInput {
+        analyses: vec![Analysis::Op(OpAnalysis {})],
+        ..Default::default()
+    };
+    let raw = Rawfile {
+        analyses: vec![noise],
+    };
+    let out = arrange_rawfile(&input, raw).unwrap();
+    assert_eq!(out.len(), 1);
+    assert_eq!(out[0].analysis_type(), AnalysisType::Other);
+    assert!(matches!(out[0], AnalysisData::Other));
+}
+
+#[test]
+fn noise_analysis_line() {
+    let noise = NoiseAnalysis::builder()
+        .output("vout")
+        .input_source("vin")
+        .fstart(1f64)
+        .fstop(1e9f64)
+        .points(10)
+        .sweep(SweepMode::Dec)
+        .build()
+        .unwrap();
+    let lines = get_analyses(&[Analysis::Noise(noise)]).unwrap();
+    assert_eq!(lines, [".noise v(vout) vin dec 10 1 1000000000"]);
+}
+
+#[test]
+fn noise_analysis_hierarchical_output() {
+    let noise = NoiseAnalysis::builder()
+        .output("dut.Inv.OUT")
+        .input_source("vin")
+        .fstart(1f64)
+        .fstop(1e9f64)
+        .points(10)
+        .sweep(SweepMode::Dec)
+        .build()
+        .unwrap();
+    let lines = get_analyses(&[Analysis::Noise(noise)]).unwrap();
+    assert_eq!(lines, [".noise v(xdut.xinv.out) vin dec 10 1 1000000000"]);
+}
+
+#[test]
+fn initial_conditions() {
+    let input = SimInput {
+        work_dir: PathBuf::from(TEST_BUILD_PATH).join("initial_conditions/sim/"),
+        ic: HashMap::from_iter([
+            ("out".to_string(), SiValue::new(1, SiPrefix::None)),
+            ("dut.x".to_string(), SiValue::new(0, SiPrefix::None)),
+            ("v(in)".to_string(), SiValue::new(500, SiPrefix::Milli)),
+        ]),
+        analyses: vec![Analysis::Op(OpAnalysis {})],
+        ..Default::default()
+    };
+    let deck = render_deck(&input);
+    assert!(deck.contains(&format!(
+        ".ic v(xdut.x)={}\n.ic v(out)={}\n.ic v(in)={}\n",
+        SiValue::new(0, SiPrefix::None),
+        SiValue::new(1, SiPrefix::None),
+        SiValue::new(500, SiPrefix::Milli),
+    )));
+}
+
+/// Renders the netlist for the given input, returning its contents.
+fn render_deck(input: &SimInput) -> String {
+    std::fs::create_dir_all(&input.work_dir).unwrap();
+    let analyses = get_analyses(&input.analyses).unwrap();
+    let directives = get_directives(input);
+    let ctx = NetlistCtx {
+        libs: &input.libs,
+        includes: &input.includes,
+        directives: &directives,
+        analyses: &analyses,
+    };
+    let path = render_netlist(ctx, &input.work_dir).unwrap();
+    let deck = std::fs::read_to_string(path).unwrap();
+    println!("{deck}");
+    deck
+}
+
+#[test]
+fn tolerance_options() {
+    let input = SimInput {
+        work_dir: PathBuf::from(TEST_BUILD_PATH).join("tolerance_options/sim/"),
+        opts: SimOpts {
+            gmin: Some(1e-12),
+            reltol: Some(1e-4),
+            iabstol: Some(1e-13),
+            ..Default::default()
+        },
+        analyses: vec![Analysis::Op(OpAnalysis {})],
+        ..Default::default()
+    };
+    let deck = render_deck(&input);
+    assert!(deck.contains(&format!(".options gmin={}", 1e-12)));
+    assert!(deck.contains(&format!(".options reltol={}", 1e-4)));
+    assert!(deck.contains(&format!(".options abstol={}", 1e-13)));
+}
+
+#[test]
+fn other_options() {
+    let input = SimInput {
+        work_dir: PathBuf::from(TEST_BUILD_PATH).join("other_options/sim/"),
+        opts: SimOpts {
+            other: HashMap::from_iter([
+                ("itl1".to_string(), "500".to_string()),
+                ("method".to_string(), "gear".to_string()),
+            ]),
+            ..Default::default()
+        },
+        analyses: vec![Analysis::Op(OpAnalysis {})],
+        ..Default::default()
+    };
+    let deck = render_deck(&input);
+    assert!(deck.contains(".options itl1=500\n.options method=gear\n"));
+}
+
+#[test]
+fn save_directives() {
+    let mut input = SimInput {
+        work_dir: PathBuf::from(TEST_BUILD_PATH).join("save_directives/sim/"),
+        save: Save::Signals(HashSet::from_iter([
+            SaveItem::NodeVoltage(NamedSignalPathBuf {
+                insts: vec!["dut".into()],
+                signal: "out".into(),
+                idx: None,
+            }),
+            SaveItem::DeviceCurrent(NamedTerminalPathBuf {
+                insts: vec!["dut".into()],
+                device: "m0".into(),
+                terminal: "d".into(),
+            }),
+        ])),
+        analyses: vec![Analysis::Op(OpAnalysis {})],
+        ..Default::default()
+    };
+    // Raw names are passed to ngspice verbatim.
+    input.save.add("i(vdd)");
+    let deck = render_deck(&input);
+    assert!(deck.contains(".save @m.xdut.m0[id]\n.save i(vdd)\n.save v(xdut.out)\n"));
+}
+
+#[test]
+fn node_voltage_strings_are_lowercase() {
+    let path = NamedSignalPathBuf {
+        insts: vec!["DUT".into(), "Inv".into()],
+        signal: "OUT".into(),
+        idx: Some(1),
+    };
+    assert_eq!(node_voltage_string(&path), "v(xdut.xinv.out[1])");
+}
+
+#[test]
+fn save_device_current() {
+    let path = PathBuf::from(EXAMPLES_PATH).join("save_current_tb.spice");
+    let work_dir = PathBuf::from(TEST_BUILD_PATH).join("save_current_tb/sim/");
+    let input = SimInput {
+        work_dir,
+        save: Save::Signals(HashSet::from_iter([
+            SaveItem::NodeVoltage(NamedSignalPathBuf {
+                insts: vec!["dut".into()],
+                signal: "mid".into(),
+                idx: None,
+            }),
+            SaveItem::DeviceCurrent(NamedTerminalPathBuf {
+                insts: vec!["dut".into()],
+                device: "M0".into(),
+                terminal: "d".into(),
+            }),
+        ])),
+        analyses: vec![Analysis::Op(OpAnalysis {})],
+        includes: vec![path],
+        ..Default::default()
+    };
+    let opts = SimulatorOpts {
+        opts: Default::default(),
+    };
+
+    let simulator = Ngspice::new(opts).unwrap();
+    let out = simulator.simulate(input).unwrap();
+    let data = match &out.data[0] {
+        AnalysisData::Op(data) => &data.data,
+        _ => panic!("expected op data"),
+    };
+
+    // The MOSFET is in saturation, so Id = kp / 2 * (Vgs - Vto)^2 = 24.5uA.
+    let id = data["@m.xdut.m0[id]"].value;
+    assert!((id - 24.5e-6).abs() < 1e-8, "unexpected drain current {id}");
+    let vmid = data["v(xdut.mid)"].value;
+    assert!((vmid - 1.7755).abs() < 1e-4, "unexpected voltage {vmid}");
+}
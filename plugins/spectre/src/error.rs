@@ -0,0 +1,53 @@
+//! Errors produced when Spectre fails.
+
+use substrate::error::SubstrateError;
+use substrate::verification::simulation::error::SimulatorError;
+
+/// The number of trailing lines of Spectre's output included in a [`SimulatorError`].
+pub const LOG_TAIL_LINES: usize = 20;
+
+/// Classifies a failed Spectre run from Spectre's log and error output.
+///
+/// The returned error contains the last [`LOG_TAIL_LINES`] lines of Spectre's output,
+/// and is returned from
+/// [`Simulator::simulate`](substrate::verification::simulation::Simulator::simulate)
+/// as [`substrate::error::ErrorSource::Simulator`].
+pub fn from_log(log: &str) -> SimulatorError {
+    let lines = log.lines().collect::<Vec<_>>();
+    let tail = lines[lines.len().saturating_sub(LOG_TAIL_LINES)..].join("\n");
+
+    let log = log.to_lowercase();
+    let matches = |signatures: &[&str]| signatures.iter().any(|sig| log.contains(sig));
+
+    if matches(&[
+        "unable to checkout",
+        "unable to check out",
+        "unable to obtain license",
+        "license checkout",
+        "no license",
+    ]) {
+        SimulatorError::License(tail)
+    } else if matches(&["syntax error", "(sfe-"]) {
+        SimulatorError::Syntax(tail)
+    } else if matches(&[
+        "no convergence",
+        "did not converge",
+        "failed to converge",
+        "no dc solution found",
+    ]) {
+        SimulatorError::Nonconvergence(tail)
+    } else {
+        SimulatorError::Other(tail)
+    }
+}
+
+/// Converts an error from running Spectre into a Substrate error.
+///
+/// [`SimulatorError`]s become [`substrate::error::ErrorSource::Simulator`];
+/// all other errors become [`substrate::error::ErrorSource::Anyhow`].
+pub(crate) fn into_substrate_error(err: anyhow::Error) -> SubstrateError {
+    match err.downcast::<SimulatorError>() {
+        Ok(err) => err.into(),
+        Err(err) => err.into(),
+    }
+}
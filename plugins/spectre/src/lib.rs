@@ -10,10 +10,12 @@ use psfparser::analysis::ac::AcData as PsfAcData;
 use psfparser::analysis::dc::DcData as PsfDcData;
 use psfparser::analysis::transient::TransientData;
 use serde::Serialize;
+use substrate::error::ErrorSource;
 use substrate::verification::simulation::{
-    AcData, Analysis, AnalysisData, AnalysisType, ComplexSignal, DcData, MonteCarloData, OpData,
-    OutputFormat, Quantity, RealSignal, Save, ScalarSignal, SimInput, SimOutput, Simulator,
-    SimulatorOpts, SweepMode, TranData, Variations,
+    AcData, Analysis, AnalysisData, AnalysisType, ComplexSignal, DcData, MonteCarloAnalysis,
+    MonteCarloData, NamedTerminalPathBuf, NoiseData, OpData, OutputFormat, Quantity, RealSignal,
+    Save, SaveItem, ScalarSignal, SimInput, SimOutput, Simulator, SimulatorOpts, SweepMode,
+    TranData, Variations,
 };
 use templates::{render_netlist, NetlistCtx};
 use tera::{Context, Tera};
@@ -104,15 +106,59 @@ fn op_conv(parsed_data: PsfDcData) -> OpData {
     }
 }
 
+fn noise_conv(parsed_data: PsfDcData) -> Result<NoiseData> {
+    let mut data = match parsed_data {
+        PsfDcData::Sweep(data) => data,
+        PsfDcData::Op(_) => bail!("expected noise sweep, found an op analysis"),
+    };
+    let mut signal = |name: &str| match data.signals.remove(name) {
+        Some(values) => Ok(RealSignal {
+            values,
+            quantity: Quantity::Unknown,
+        }),
+        None => bail!("noise analysis output is missing signal `{name}`"),
+    };
+    let output = signal("out")?;
+    let input = signal("in")?;
+    Ok(NoiseData {
+        output,
+        input,
+        freq: RealSignal {
+            values: data.param.1,
+            quantity: Quantity::Frequency,
+        },
+    })
+}
+
 fn analysis_name(prefix: &str, num: usize) -> String {
     format!("{prefix}_{num}")
 }
 
+pub mod error;
 pub(crate) mod templates;
 #[cfg(test)]
 mod tests;
 
-pub struct Spectre {}
+/// The [`SimulatorOpts`] key for the number of Spectre processes
+/// that Monte Carlo iterations are split across.
+///
+/// Defaults to 1, which runs all iterations in a single process.
+pub const MONTE_CARLO_WORKERS: &str = "monte_carlo_workers";
+
+pub struct Spectre {
+    monte_carlo_workers: usize,
+}
+
+/// Returns the output file prefix of each run of the given Monte Carlo analysis, in order.
+///
+/// Spectre names the output of run `n` of analysis `name` with the prefix `{name}-{n:0>3}_{name}`.
+/// Runs are numbered starting from the analysis' first run.
+pub(crate) fn monte_carlo_prefixes(name: &str, analysis: &MonteCarloAnalysis) -> Vec<String> {
+    let first_run = analysis.first_run.unwrap_or(1);
+    (first_run..first_run + analysis.num_iterations)
+        .map(|iter| format!("{}-{:0>3}_{}", name, iter, name))
+        .collect()
+}
 
 struct SpectreOutputParser<'a> {
     raw_output_dir: &'a Path,
@@ -137,8 +183,7 @@ impl<'a> SpectreOutputParser<'a> {
             let mut data = Vec::new();
             for i in 0..analysis.analyses.len() {
                 let mut mc_data = Vec::new();
-                for iter in 1..analysis.num_iterations + 1 {
-                    let new_prefix = format!("{}-{:0>3}_{}", name, iter, name);
+                for new_prefix in monte_carlo_prefixes(&name, analysis) {
                     mc_data.push(self.parse_analysis(
                         &new_prefix,
                         i,
@@ -155,13 +200,16 @@ impl<'a> SpectreOutputParser<'a> {
                 AnalysisType::Ac => {
                     format!("{}.ac", name)
                 }
+                AnalysisType::Noise => {
+                    format!("{}.noise", name)
+                }
                 AnalysisType::Tran => {
                     format!("{}.tran.tran", name)
                 }
                 AnalysisType::Dc | AnalysisType::Op => {
                     format!("{}.dc", name)
                 }
-                _ => bail!("spectre plugin only supports transient, ac, and dc simulations"),
+                _ => bail!("spectre plugin only supports transient, ac, dc, and noise simulations"),
             };
             let psf_path = self.raw_output_dir.join(file_name);
 
@@ -180,7 +228,10 @@ impl<'a> SpectreOutputParser<'a> {
                     AnalysisType::Tran => tran_conv(TransientData::from_ascii(&ast)).into(),
                     AnalysisType::Dc => dc_conv(PsfDcData::from_ast(&ast)).into(),
                     AnalysisType::Op => op_conv(PsfDcData::from_ast(&ast)).into(),
-                    _ => bail!("spectre plugin only supports transient, ac, and dc simulations"),
+                    AnalysisType::Noise => noise_conv(PsfDcData::from_ast(&ast))?.into(),
+                    _ => bail!(
+                        "spectre plugin only supports transient, ac, dc, and noise simulations"
+                    ),
                 })
             }
         }
@@ -228,16 +279,55 @@ fn generate_paths(work_dir: impl AsRef<Path>) -> Paths {
 fn save_directives(input: &SimInput, directives: &mut Vec<String>) {
     match &input.save {
         Save::Signals(s) => {
-            directives.reserve(s.len());
-            for s in s {
-                directives.push(format!("save \"{s}\""));
-            }
+            // Sort the saves so that netlists are deterministic.
+            let mut saves = s.iter().map(save_line).collect::<Vec<_>>();
+            saves.sort();
+            directives.extend(saves);
         }
         Save::All => directives.push("opsaveall options save=allpub".to_string()),
         Save::None => directives.push("opsavenone options save=none".to_string()),
     }
 }
 
+fn save_line(item: &SaveItem) -> String {
+    match item {
+        SaveItem::NodeVoltage(path) => format!("save \"{}\"", node_voltage_string(path)),
+        SaveItem::DeviceCurrent(path) => format!("save {}", terminal_current_string(path)),
+        SaveItem::Raw(s) => format!("save \"{s}\""),
+    }
+}
+
+/// Formats the given node path as a Spectre node name, such as `Xdut.Xinv.out[0]`.
+fn node_voltage_string(path: &substrate::schematic::signal::NamedSignalPathBuf) -> String {
+    use std::fmt::Write;
+
+    let mut s = String::new();
+    for inst in path.insts.iter() {
+        s.push('X');
+        s.push_str(inst);
+        s.push('.');
+    }
+    s.push_str(&path.signal);
+    if let Some(idx) = path.idx {
+        write!(&mut s, "[{idx}]").expect("failed to write node voltage string");
+    }
+    s
+}
+
+/// Formats the given terminal path as a Spectre terminal name, such as `Xdut.M0:d`.
+fn terminal_current_string(path: &NamedTerminalPathBuf) -> String {
+    let mut s = String::new();
+    for inst in path.insts.iter() {
+        s.push('X');
+        s.push_str(inst);
+        s.push('.');
+    }
+    s.push_str(&path.device);
+    s.push(':');
+    s.push_str(&path.terminal);
+    s
+}
+
 fn temp_directives(input: &SimInput, directives: &mut Vec<String>) {
     if let Some(t) = input.opts.temp {
         directives.push(format!("settemp alter param=temp value={t}"));
@@ -247,6 +337,31 @@ fn temp_directives(input: &SimInput, directives: &mut Vec<String>) {
     }
 }
 
+fn tolerance_directives(input: &SimInput, directives: &mut Vec<String>) {
+    let opts = [
+        ("gmin", input.opts.gmin),
+        ("reltol", input.opts.reltol),
+        ("iabstol", input.opts.iabstol),
+    ];
+    for (name, value) in opts {
+        if let Some(value) = value {
+            directives.push(format!("set{name} options {name}={value}"));
+        }
+    }
+}
+
+fn other_directives(input: &SimInput, directives: &mut Vec<String>) {
+    // Sort the options so that netlists are deterministic.
+    let mut opts = input.opts.other.iter().collect::<Vec<_>>();
+    opts.sort();
+    for (key, value) in opts {
+        directives.push(format!("set{key} options {key}={value}"));
+    }
+}
+
+/// Writes a `.ic` directive for the initial conditions in `input`.
+///
+/// Node names are written verbatim.
 fn ic_directives(input: &SimInput, directives: &mut Vec<String>) {
     use std::fmt::Write;
     if input.ic.is_empty() {
@@ -262,16 +377,15 @@ fn ic_directives(input: &SimInput, directives: &mut Vec<String>) {
     directives.push(ic);
 }
 
-pub fn run_spectre(input: &SimInput) -> Result<Vec<AnalysisData>> {
-    let work_dir = &input.work_dir;
-    let paths = generate_paths(work_dir);
-
-    std::fs::create_dir_all(&input.work_dir)?;
+/// Writes the top-level Spectre netlist for the given input to `path`.
+fn write_netlist(input: &SimInput, path: impl AsRef<Path>) -> Result<()> {
     let analyses = get_analyses(&input.analyses)?;
 
     let mut spectre_directives = vec!["oppreserveall options preserve_inst=all".to_string()];
     save_directives(input, &mut spectre_directives);
     temp_directives(input, &mut spectre_directives);
+    tolerance_directives(input, &mut spectre_directives);
+    other_directives(input, &mut spectre_directives);
 
     let mut spice_directives = Vec::new();
     ic_directives(input, &mut spice_directives);
@@ -283,15 +397,24 @@ pub fn run_spectre(input: &SimInput) -> Result<Vec<AnalysisData>> {
         spice_directives: &spice_directives,
         analyses: &analyses,
     };
-    render_netlist(ctx, &paths.top_netlist_path)?;
+    render_netlist(ctx, path)?;
+    Ok(())
+}
+
+pub fn run_spectre(input: &SimInput) -> Result<Vec<AnalysisData>> {
+    let work_dir = &input.work_dir;
+    let paths = generate_paths(work_dir);
+
+    std::fs::create_dir_all(&input.work_dir)?;
+    write_netlist(input, &paths.top_netlist_path)?;
 
     write_run_script(&paths, input)?;
     let mut perms = std::fs::metadata(&paths.run_script_path)?.permissions();
     perms.set_mode(0o755);
     std::fs::set_permissions(&paths.run_script_path, perms)?;
 
-    let out_file = std::fs::File::create(paths.stdout_path)?;
-    let err_file = std::fs::File::create(paths.stderr_path)?;
+    let out_file = std::fs::File::create(&paths.stdout_path)?;
+    let err_file = std::fs::File::create(&paths.stderr_path)?;
 
     let status = Command::new("/bin/bash")
         .arg(&paths.run_script_path)
@@ -301,12 +424,144 @@ pub fn run_spectre(input: &SimInput) -> Result<Vec<AnalysisData>> {
         .status()?;
 
     if !status.success() {
-        bail!("Spectre exited unsuccessfully");
+        // Spectre may fail before it starts writing its log, so include stderr as well.
+        let mut log = std::fs::read_to_string(&paths.log_path).unwrap_or_default();
+        log.push_str(&std::fs::read_to_string(&paths.stderr_path).unwrap_or_default());
+        return Err(error::from_log(&log).into());
     }
 
     SpectreOutputParser::new(&paths.raw_output_dir).parse_analyses(input)
 }
 
+/// Runs `input`, splitting its Monte Carlo iterations across up to `workers` Spectre processes.
+///
+/// Each shard runs a contiguous range of iterations (using `firstrun` and `numruns`)
+/// in its own subdirectory of the input's working directory.
+/// The first shard also runs all analyses that are not Monte Carlo analyses.
+/// Shard results are merged in iteration order, so the output matches that of [`run_spectre`].
+pub fn run_spectre_sharded(input: &SimInput, workers: usize) -> Result<Vec<AnalysisData>> {
+    let shards = shard_inputs(input, workers);
+    if shards.len() <= 1 {
+        return run_spectre(input);
+    }
+
+    let outputs = std::thread::scope(|s| {
+        let handles = shards
+            .iter()
+            .map(|shard| s.spawn(move || run_spectre(shard)))
+            .collect::<Vec<_>>();
+        handles
+            .into_iter()
+            .map(|handle| handle.join().expect("Spectre worker thread panicked"))
+            .collect::<Result<Vec<_>>>()
+    })?;
+
+    merge_shards(outputs)
+}
+
+/// Splits `num_runs` runs starting at `first_run` into `shards` contiguous ranges.
+///
+/// Returns the first run and number of runs of each shard, in order.
+/// Earlier shards receive any remaining runs.
+pub(crate) fn partition_runs(
+    first_run: usize,
+    num_runs: usize,
+    shards: usize,
+) -> Vec<(usize, usize)> {
+    let mut start = first_run;
+    (0..shards)
+        .map(|shard| {
+            let len = num_runs / shards + usize::from(shard < num_runs % shards);
+            let range = (start, len);
+            start += len;
+            range
+        })
+        .collect()
+}
+
+/// Splits `input` into at most `workers` inputs that together run its Monte Carlo iterations.
+///
+/// Every shard runs at least one iteration of each Monte Carlo analysis.
+/// Only the first shard keeps the analyses that are not Monte Carlo analyses.
+///
+/// Sharded results only match a serial run if every Monte Carlo analysis is seeded.
+/// If any Monte Carlo analysis is unseeded, `input` is returned unsplit.
+pub(crate) fn shard_inputs(input: &SimInput, workers: usize) -> Vec<SimInput> {
+    let unseeded = input.analyses.iter().any(|analysis| {
+        matches!(
+            analysis,
+            Analysis::MonteCarlo(MonteCarloAnalysis { seed: None, .. })
+        )
+    });
+    if unseeded && workers > 1 {
+        log::warn!("running unseeded Monte Carlo analyses in a single Spectre process");
+        return vec![input.clone()];
+    }
+
+    let num_shards = input
+        .analyses
+        .iter()
+        .filter_map(|analysis| match analysis {
+            Analysis::MonteCarlo(analysis) => Some(analysis.num_iterations),
+            _ => None,
+        })
+        .min()
+        .unwrap_or(0)
+        .min(workers);
+
+    (0..num_shards)
+        .map(|shard| {
+            let mut shard_input = input.clone();
+            shard_input.work_dir = input.work_dir.join(format!("shard_{shard}"));
+            if shard > 0 {
+                shard_input
+                    .analyses
+                    .retain(|analysis| matches!(analysis, Analysis::MonteCarlo(_)));
+            }
+            for analysis in shard_input.analyses.iter_mut() {
+                if let Analysis::MonteCarlo(analysis) = analysis {
+                    let (first_run, num_runs) = partition_runs(
+                        analysis.first_run.unwrap_or(1),
+                        analysis.num_iterations,
+                        num_shards,
+                    )[shard];
+                    analysis.first_run = Some(first_run);
+                    analysis.num_iterations = num_runs;
+                }
+            }
+            shard_input
+        })
+        .collect()
+}
+
+/// Merges the outputs of the inputs produced by [`shard_inputs`].
+///
+/// The iterations of each Monte Carlo analysis are concatenated in shard order.
+pub(crate) fn merge_shards(outputs: Vec<Vec<AnalysisData>>) -> Result<Vec<AnalysisData>> {
+    let mut outputs = outputs.into_iter();
+    let mut merged = outputs.next().unwrap_or_default();
+
+    for output in outputs {
+        let mut output = output.into_iter();
+        for data in merged.iter_mut() {
+            if let AnalysisData::MonteCarlo(mc) = data {
+                let shard = match output.next() {
+                    Some(AnalysisData::MonteCarlo(shard)) => shard,
+                    _ => bail!("Monte Carlo shard outputs do not match"),
+                };
+                for (iters, shard_iters) in mc.data.iter_mut().zip(shard.data) {
+                    iters.extend(shard_iters);
+                }
+            }
+        }
+        if output.next().is_some() {
+            bail!("Monte Carlo shard outputs do not match");
+        }
+    }
+
+    Ok(merged)
+}
+
 fn output_format_name<'a>(input: &SimInput, format: &'a OutputFormat) -> &'a str {
     let all_tran = input
         .analyses
@@ -363,18 +618,33 @@ fn write_run_script(paths: &Paths, input: &SimInput) -> Result<()> {
 }
 
 impl Simulator for Spectre {
-    fn new(_opts: SimulatorOpts) -> substrate::error::Result<Self>
+    fn new(opts: SimulatorOpts) -> substrate::error::Result<Self>
     where
         Self: Sized,
     {
-        Ok(Self {})
+        let monte_carlo_workers = match opts.opts.get(MONTE_CARLO_WORKERS) {
+            Some(workers) => workers
+                .parse::<usize>()
+                .ok()
+                .filter(|&n| n > 0)
+                .ok_or_else(|| {
+                    ErrorSource::InvalidArgs(format!(
+                        "`{MONTE_CARLO_WORKERS}` must be a positive integer, but was `{workers}`"
+                    ))
+                })?,
+            None => 1,
+        };
+        Ok(Self {
+            monte_carlo_workers,
+        })
     }
 
     fn simulate(&self, input: SimInput) -> substrate::error::Result<SimOutput> {
         if input.analyses.is_empty() {
             return Ok(SimOutput { data: Vec::new() });
         }
-        let data = run_spectre(&input)?;
+        let data = run_spectre_sharded(&input, self.monte_carlo_workers)
+            .map_err(error::into_substrate_error)?;
         Ok(SimOutput { data })
     }
 
@@ -382,19 +652,7 @@ impl Simulator for Spectre {
         &self,
         path: &substrate::schematic::signal::NamedSignalPathBuf,
     ) -> String {
-        use std::fmt::Write;
-
-        let mut s = String::new();
-        for inst in path.insts.iter() {
-            s.push('X');
-            s.push_str(inst);
-            s.push('.');
-        }
-        s.push_str(&path.signal);
-        if let Some(idx) = path.idx {
-            write!(&mut s, "[{idx}]").expect("failed to write node voltage string");
-        }
-        s
+        node_voltage_string(path)
     }
 }
 
@@ -431,7 +689,21 @@ fn analysis_line(input: &Analysis, prefix: &str, num: usize) -> Result<String> {
                 "{name} ac start={} stop={} {}",
                 a.fstart,
                 a.fstop,
-                fmt_sweep_mode(a.sweep, a.points),
+                fmt_sweep_mode(a.sweep, a.points, a.fstart, a.fstop)?,
+            );
+            for (k, v) in a.opts.iter() {
+                write!(&mut line, " {}={}", k, v).unwrap();
+            }
+            line
+        }
+        Analysis::Noise(a) => {
+            let mut line = format!(
+                "{name} ({} 0) noise start={} stop={} {} iprobe={}",
+                a.output,
+                a.fstart,
+                a.fstop,
+                fmt_sweep_mode(a.sweep, a.points, a.fstart, a.fstop)?,
+                a.input_source,
             );
             for (k, v) in a.opts.iter() {
                 write!(&mut line, " {}={}", k, v).unwrap();
@@ -495,17 +767,18 @@ fn analysis_line(input: &Analysis, prefix: &str, num: usize) -> Result<String> {
     })
 }
 
-fn fmt_sweep_mode(mode: SweepMode, points: usize) -> String {
-    match mode {
+fn fmt_sweep_mode(mode: SweepMode, points: usize, start: f64, stop: f64) -> Result<String> {
+    Ok(match mode {
         SweepMode::Dec => format!("dec={points}"),
         SweepMode::Oct => {
-            // Oct isn't directly supported by Spectre; use a log sweep instead.
-            log::warn!(
-                "Unsupported sweep mode `{:?}`; using a log sweep instead",
-                mode
-            );
-            format!("log={points}")
+            // Spectre has no octave sweeps, so use a log sweep with the same
+            // number of steps per octave. The `log` parameter is the total number of steps.
+            if start <= 0. || stop <= start {
+                bail!("octave sweeps require 0 < start < stop, but got start={start}, stop={stop}");
+            }
+            let steps = (points as f64 * (stop / start).log2()).round().max(1.);
+            format!("log={steps}")
         }
         SweepMode::Lin => format!("lin={points}"),
-    }
+    })
 }
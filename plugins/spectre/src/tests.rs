@@ -1,13 +1,22 @@
+use std::collections::{HashMap, HashSet};
 use std::path::PathBuf;
 
 use approx::abs_diff_eq;
 use statrs::statistics::Statistics;
+use substrate::error::ErrorSource;
+use substrate::schematic::signal::NamedSignalPathBuf;
+use substrate::verification::simulation::error::SimulatorError;
 use substrate::verification::simulation::{
-    AcAnalysis, Analysis, AnalysisType, MonteCarloAnalysis, OpAnalysis, SimInput, Simulator,
-    SimulatorOpts, SweepMode, TranAnalysis, Variations,
+    AcAnalysis, Analysis, AnalysisData, AnalysisType, MonteCarloAnalysis, MonteCarloData,
+    NamedTerminalPathBuf, NoiseAnalysis, OpAnalysis, OpData, Quantity, Save, SaveItem,
+    ScalarSignal, SimInput, SimOpts, Simulator, SimulatorOpts, SweepMode, TranAnalysis, Variations,
 };
 
-use crate::Spectre;
+use crate::error::{from_log, into_substrate_error, LOG_TAIL_LINES};
+use crate::{
+    analysis_line, merge_shards, monte_carlo_prefixes, noise_conv, partition_runs, shard_inputs,
+    write_netlist, Spectre, TOP_NETLIST_NAME,
+};
 
 pub(crate) const TEST_BUILD_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/build");
 pub(crate) const EXAMPLES_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/examples");
@@ -127,3 +136,332 @@ fn vdivider_test() {
     assert!(abs_diff_eq!(vout_avg, 0.6, epsilon = 0.004));
     assert!(abs_diff_eq!(vout_stddev, 0.08, epsilon = 0.002));
 }
+
+#[test]
+fn tolerance_options() {
+    let work_dir = PathBuf::from(TEST_BUILD_PATH).join("tolerance_options/sim/");
+    std::fs::create_dir_all(&work_dir).unwrap();
+    let path = work_dir.join(TOP_NETLIST_NAME);
+    let input = SimInput {
+        work_dir,
+        opts: SimOpts {
+            gmin: Some(1e-12),
+            reltol: Some(1e-4),
+            iabstol: Some(1e-13),
+            ..Default::default()
+        },
+        analyses: vec![Analysis::Op(OpAnalysis {})],
+        ..Default::default()
+    };
+    write_netlist(&input, &path).unwrap();
+
+    let deck = std::fs::read_to_string(&path).unwrap();
+    println!("{deck}");
+    assert!(deck.contains(&format!("setgmin options gmin={}", 1e-12)));
+    assert!(deck.contains(&format!("setreltol options reltol={}", 1e-4)));
+    assert!(deck.contains(&format!("setiabstol options iabstol={}", 1e-13)));
+}
+
+#[test]
+fn other_options() {
+    let work_dir = PathBuf::from(TEST_BUILD_PATH).join("other_options/sim/");
+    std::fs::create_dir_all(&work_dir).unwrap();
+    let path = work_dir.join(TOP_NETLIST_NAME);
+    let input = SimInput {
+        work_dir,
+        opts: SimOpts {
+            other: HashMap::from_iter([
+                ("maxiters".to_string(), "500".to_string()),
+                ("pivotdc".to_string(), "yes".to_string()),
+            ]),
+            ..Default::default()
+        },
+        analyses: vec![Analysis::Op(OpAnalysis {})],
+        ..Default::default()
+    };
+    write_netlist(&input, &path).unwrap();
+
+    let deck = std::fs::read_to_string(&path).unwrap();
+    println!("{deck}");
+    assert!(deck.contains("setmaxiters options maxiters=500\nsetpivotdc options pivotdc=yes\n"));
+}
+
+#[test]
+fn octave_sweep() {
+    let ac = |fstart: f64, fstop: f64, points: usize| {
+        Analysis::Ac(AcAnalysis {
+            fstart,
+            fstop,
+            points,
+            sweep: SweepMode::Oct,
+            opts: HashMap::new(),
+        })
+    };
+
+    // 1 kHz to 1.024 MHz is exactly 10 octaves.
+    let line = analysis_line(&ac(1e3, 1.024e6, 8), "analysis", 0).unwrap();
+    assert!(
+        line.ends_with("ac start=1000 stop=1024000 log=80"),
+        "{line}"
+    );
+
+    // A decade is about 3.32 octaves.
+    let line = analysis_line(&ac(1., 10., 10), "analysis", 0).unwrap();
+    assert!(line.ends_with("log=33"), "{line}");
+
+    // Sweeps spanning less than one step still take a single step.
+    let line = analysis_line(&ac(1., 1.01, 2), "analysis", 0).unwrap();
+    assert!(line.ends_with("log=1"), "{line}");
+
+    assert!(analysis_line(&ac(0., 1e6, 10), "analysis", 0).is_err());
+    assert!(analysis_line(&ac(1e6, 1e3, 10), "analysis", 0).is_err());
+}
+
+#[test]
+fn save_directives() {
+    let work_dir = PathBuf::from(TEST_BUILD_PATH).join("save_directives/sim/");
+    std::fs::create_dir_all(&work_dir).unwrap();
+    let path = work_dir.join(TOP_NETLIST_NAME);
+    let mut input = SimInput {
+        work_dir,
+        save: Save::Signals(HashSet::from_iter([
+            SaveItem::NodeVoltage(NamedSignalPathBuf {
+                insts: vec!["dut".into()],
+                signal: "out".into(),
+                idx: Some(1),
+            }),
+            SaveItem::DeviceCurrent(NamedTerminalPathBuf {
+                insts: vec!["dut".into(), "inv".into()],
+                device: "M0".into(),
+                terminal: "d".into(),
+            }),
+        ])),
+        analyses: vec![Analysis::Op(OpAnalysis {})],
+        ..Default::default()
+    };
+    // Raw names are passed to Spectre verbatim.
+    input.save.add("vout");
+    write_netlist(&input, &path).unwrap();
+
+    let deck = std::fs::read_to_string(&path).unwrap();
+    println!("{deck}");
+    assert!(deck.contains("save \"Xdut.out[1]\"\nsave \"vout\"\nsave Xdut.Xinv.M0:d\n"));
+}
+
+#[test]
+fn noise_analysis_line() {
+    let noise = NoiseAnalysis::builder()
+        .output("vout")
+        .input_source("vin")
+        .fstart(1f64)
+        .fstop(1e9f64)
+        .points(10)
+        .sweep(SweepMode::Dec)
+        .build()
+        .unwrap();
+    let line = analysis_line(&Analysis::Noise(noise), "analysis", 0).unwrap();
+    assert_eq!(
+        line,
+        "analysis_0 (vout 0) noise start=1 stop=1000000000 dec=10 iprobe=vin"
+    );
+}
+
+#[test]
+fn parse_noise_psf() {
+    let path = PathBuf::from(EXAMPLES_PATH).join("noise.noise");
+    let psf = std::fs::read_to_string(path).unwrap();
+    let ast = psfparser::ascii::frontend::parse(&psf).unwrap();
+    let data = noise_conv(psfparser::analysis::dc::DcData::from_ast(&ast)).unwrap();
+
+    assert_eq!(data.freq.values, [1., 10., 100.]);
+    assert_eq!(data.output.values, [4e-8, 2e-8, 1e-8]);
+    assert_eq!(data.input.values, [2e-9, 1e-9, 5e-10]);
+}
+
+#[test]
+fn partition_monte_carlo_runs() {
+    assert_eq!(partition_runs(1, 10, 3), [(1, 4), (5, 3), (8, 3)]);
+    assert_eq!(partition_runs(5, 4, 2), [(5, 2), (7, 2)]);
+    assert_eq!(partition_runs(1, 3, 1), [(1, 3)]);
+}
+
+/// Produces the output Spectre would give for `analyses`,
+/// where each Monte Carlo run is labeled by its run number.
+fn mock_output(analyses: &[Analysis]) -> Vec<AnalysisData> {
+    let op = |value: f64| {
+        AnalysisData::Op(OpData {
+            data: HashMap::from_iter([(
+                "vout".to_string(),
+                ScalarSignal {
+                    value,
+                    quantity: Quantity::Voltage,
+                },
+            )]),
+        })
+    };
+    analyses
+        .iter()
+        .map(|analysis| match analysis {
+            Analysis::MonteCarlo(mc) => {
+                let first_run = mc.first_run.unwrap_or(1);
+                let data = (0..mc.analyses.len())
+                    .map(|i| {
+                        (first_run..first_run + mc.num_iterations)
+                            .map(|run| op((10 * run + i) as f64))
+                            .collect()
+                    })
+                    .collect();
+                AnalysisData::MonteCarlo(MonteCarloData { data })
+            }
+            _ => op(-1.),
+        })
+        .collect()
+}
+
+#[test]
+fn merge_monte_carlo_shards() {
+    let mc = |num_iterations| {
+        Analysis::MonteCarlo(
+            MonteCarloAnalysis::builder()
+                .variations(Variations::Mismatch)
+                .num_iterations(num_iterations)
+                .seed(1234)
+                .analyses(vec![
+                    Analysis::Op(OpAnalysis::new()),
+                    Analysis::Op(OpAnalysis::new()),
+                ])
+                .build()
+                .unwrap(),
+        )
+    };
+    let input = SimInput {
+        work_dir: PathBuf::from("sim"),
+        analyses: vec![Analysis::Op(OpAnalysis::new()), mc(10), mc(5)],
+        ..Default::default()
+    };
+
+    let shards = shard_inputs(&input, 3);
+    assert_eq!(shards.len(), 3);
+    assert_eq!(shards[0].analyses.len(), 3);
+    assert_eq!(shards[1].analyses.len(), 2);
+    assert_eq!(shards[2].work_dir, PathBuf::from("sim/shard_2"));
+    let runs = shards
+        .iter()
+        .map(|shard| match shard.analyses.last().unwrap() {
+            Analysis::MonteCarlo(mc) => (mc.first_run.unwrap(), mc.num_iterations),
+            _ => panic!("expected a Monte Carlo analysis"),
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(runs, [(1, 2), (3, 2), (5, 1)]);
+
+    let outputs = shards
+        .iter()
+        .map(|shard| mock_output(&shard.analyses))
+        .collect();
+    let merged = merge_shards(outputs).unwrap();
+    assert_eq!(merged, mock_output(&input.analyses));
+
+    // Monte Carlo analyses with fewer iterations than workers limit the number of shards.
+    assert_eq!(shard_inputs(&input, 8).len(), 5);
+}
+
+#[test]
+fn monte_carlo_shard_output_prefixes() {
+    let mc = MonteCarloAnalysis::builder()
+        .variations(Variations::Mismatch)
+        .num_iterations(3)
+        .seed(1234)
+        .first_run(5)
+        .analyses(vec![Analysis::Op(OpAnalysis::new())])
+        .build()
+        .unwrap();
+    assert_eq!(
+        monte_carlo_prefixes("analysis1", &mc),
+        [
+            "analysis1-005_analysis1",
+            "analysis1-006_analysis1",
+            "analysis1-007_analysis1",
+        ]
+    );
+
+    let mc = MonteCarloAnalysis {
+        first_run: None,
+        ..mc
+    };
+    assert_eq!(
+        monte_carlo_prefixes("analysis1", &mc),
+        [
+            "analysis1-001_analysis1",
+            "analysis1-002_analysis1",
+            "analysis1-003_analysis1",
+        ]
+    );
+}
+
+#[test]
+fn unseeded_monte_carlo_runs_serially() {
+    let input = SimInput {
+        work_dir: PathBuf::from("sim"),
+        analyses: vec![Analysis::MonteCarlo(
+            MonteCarloAnalysis::builder()
+                .variations(Variations::Mismatch)
+                .num_iterations(10)
+                .analyses(vec![Analysis::Op(OpAnalysis::new())])
+                .build()
+                .unwrap(),
+        )],
+        ..Default::default()
+    };
+    assert_eq!(shard_inputs(&input, 4), [input]);
+}
+
+#[test]
+fn classify_spectre_errors() {
+    let log = "\
+Circuit inventory:
+              nodes 3
+ERROR (SPECTRE-16080): No DC solution found (no convergence).
+
+The values for those nodes that did not converge on the last Newton iteration are given below.
+    I(vdd:p) = 1.2 mA
+";
+    let err = from_log(log);
+    assert_eq!(
+        err,
+        SimulatorError::Nonconvergence(log.trim_end().to_string())
+    );
+
+    let err =
+        from_log("ERROR (SFE-874): \"sim.top.spice\" 12: Unexpected end of line. Expected `)'.\n");
+    assert!(matches!(err, SimulatorError::Syntax(_)));
+
+    let err = from_log("*Error* Unable to checkout license for Spectre.\n");
+    assert!(matches!(err, SimulatorError::License(_)));
+
+    let log = (0..100).map(|i| format!("line {i}\n")).collect::<String>();
+    let err = from_log(&log);
+    assert!(matches!(err, SimulatorError::Other(_)));
+    assert_eq!(err.log().lines().count(), LOG_TAIL_LINES);
+    assert!(err.log().ends_with("line 99"));
+}
+
+#[test]
+fn spectre_errors_surface_through_substrate() {
+    let err = into_substrate_error(SimulatorError::Syntax("bad netlist".to_string()).into());
+    assert!(matches!(
+        err.source(),
+        ErrorSource::Simulator(SimulatorError::Syntax(log)) if log == "bad netlist"
+    ));
+
+    let err = into_substrate_error(
+        anyhow::Error::from(SimulatorError::License("no license".to_string()))
+            .context("running shard 1"),
+    );
+    assert!(matches!(
+        err.source(),
+        ErrorSource::Simulator(SimulatorError::License(_))
+    ));
+
+    let err = into_substrate_error(anyhow::anyhow!("failed to read output"));
+    assert!(matches!(err.source(), ErrorSource::Anyhow(_)));
+}
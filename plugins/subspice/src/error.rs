@@ -4,6 +4,8 @@ use thiserror::Error;
 pub enum Error {
     #[error("parse error")]
     Parse,
+    #[error("subcircuit dependency cycle: {}", .0.join(" -> "))]
+    DependencyCycle(Vec<String>),
     #[error("io error: {0}")]
     Io(#[from] std::io::Error),
 }
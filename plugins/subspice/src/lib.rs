@@ -1,5 +1,9 @@
+use std::collections::{HashMap, HashSet};
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
 use error::{Error, Result};
-use parser::{SpiceLine, SubcktLine};
+use parser::{InstanceLine, PinDirection, SpiceLine, SubcktLine};
 use serde::Serialize;
 
 pub mod error;
@@ -10,6 +14,27 @@ pub struct ParsedSpice<'a> {
     pub lines: Vec<SpiceLine<'a>>,
 }
 
+/// The subcircuits of a netlist and all of the files it (transitively) includes.
+///
+/// Produced by [`ParsedSpice::resolve_includes`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct ResolvedSpice {
+    pub subcircuits: Vec<ResolvedSubckt>,
+    /// Included files that could not be found.
+    pub missing: Vec<PathBuf>,
+}
+
+/// A subcircuit definition, along with the file that defined it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ResolvedSubckt {
+    pub name: String,
+    pub ports: Vec<String>,
+    /// The included file in which the subcircuit was defined.
+    ///
+    /// `None` if the subcircuit was defined in the top-level netlist.
+    pub source: Option<PathBuf>,
+}
+
 /// Parse the given rawfile data.
 pub fn parse<T>(input: &T) -> Result<ParsedSpice<'_>>
 where
@@ -21,6 +46,22 @@ where
     }
 }
 
+impl ResolvedSpice {
+    /// Return an iterator over the resolved subcircuit definitions.
+    pub fn subcircuits(&self) -> impl Iterator<Item = &ResolvedSubckt> {
+        self.subcircuits.iter()
+    }
+
+    /// Return the resolved subcircuit definition with the given name.
+    ///
+    /// If multiple files define a subcircuit with the same name,
+    /// the first definition encountered is returned.
+    pub fn subcircuit_named(&self, name: impl AsRef<str>) -> Option<&ResolvedSubckt> {
+        let name = name.as_ref();
+        self.subcircuits().find(|ckt| ckt.name == name)
+    }
+}
+
 impl<'a> ParsedSpice<'a> {
     /// Return an iterator over the lines in the parsed SPICE netlist.
     pub fn lines(&self) -> impl Iterator<Item = &SpiceLine> {
@@ -44,4 +85,199 @@ impl<'a> ParsedSpice<'a> {
         let name = name.as_ref();
         self.subcircuits().find(|ckt| ckt.name == name)
     }
+
+    /// Recursively parse the files included by this netlist via `.include`
+    /// and `.lib` directives, and collect the subcircuits they define.
+    ///
+    /// Relative paths in the top-level netlist are resolved against `base_dir`;
+    /// relative paths in included files are resolved against the directory of
+    /// the including file. `.lib` sections are not filtered: all subcircuits
+    /// in a library file are collected.
+    ///
+    /// Each file is parsed at most once, so include cycles terminate.
+    /// Files that do not exist are recorded in [`ResolvedSpice::missing`]
+    /// rather than returned as errors.
+    pub fn resolve_includes(&self, base_dir: impl AsRef<Path>) -> Result<ResolvedSpice> {
+        let mut resolved = ResolvedSpice::default();
+        let mut visited = HashSet::new();
+        resolve(self, base_dir.as_ref(), None, &mut visited, &mut resolved)?;
+        Ok(resolved)
+    }
+
+    /// Return the pin directions declared by `.PININFO` comments
+    /// in the body of the subcircuit with the given name.
+    ///
+    /// Pins are listed as `name:code` pairs, eg. `*.PININFO a:I y:O vdd:P`.
+    /// Pins with unrecognized direction codes, and pins not mentioned in any
+    /// `.PININFO` comment, are omitted.
+    pub fn pin_directions(&self, name: impl AsRef<str>) -> HashMap<&str, PinDirection> {
+        let name = name.as_ref();
+        let mut directions = HashMap::new();
+        let mut in_subckt = false;
+        for line in self.lines.iter() {
+            match line {
+                SpiceLine::Subckt(subckt) => in_subckt = subckt.name == name,
+                SpiceLine::Ends(_) => in_subckt = false,
+                SpiceLine::Comment(comment) if in_subckt => {
+                    let comment = *comment;
+                    let pins = match comment.get(..8) {
+                        Some(prefix) if prefix.eq_ignore_ascii_case(".pininfo") => &comment[8..],
+                        _ => continue,
+                    };
+                    for pin in pins.split_whitespace() {
+                        if let Some((pin, code)) = pin.rsplit_once(':') {
+                            if let Some(direction) = PinDirection::from_code(code) {
+                                directions.insert(pin, direction);
+                            }
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+        directions
+    }
+
+    /// Return an iterator over the subcircuit instances in the netlist.
+    pub fn instances(&self) -> impl Iterator<Item = &InstanceLine> {
+        self.lines.iter().filter_map(|line| line.instance())
+    }
+
+    /// Return a map from the name of each subcircuit to the names
+    /// of the subcircuits it instantiates.
+    ///
+    /// Every subcircuit defined in the netlist has an entry, even if it instantiates
+    /// nothing. Instantiated subcircuits are listed once each, in order of first use,
+    /// and may include subcircuits that are not defined in the netlist.
+    /// Instances outside of any subcircuit definition are ignored.
+    pub fn dependency_graph(&self) -> HashMap<&str, Vec<&str>> {
+        let mut graph: HashMap<&str, Vec<&str>> = HashMap::new();
+        let mut current = None;
+        for line in self.lines.iter() {
+            match line {
+                SpiceLine::Subckt(subckt) => {
+                    graph.entry(subckt.name).or_default();
+                    current = Some(subckt.name);
+                }
+                SpiceLine::Ends(_) => current = None,
+                SpiceLine::Instance(inst) => {
+                    if let Some(parent) = current {
+                        let deps = graph.entry(parent).or_default();
+                        if !deps.contains(&inst.subckt) {
+                            deps.push(inst.subckt);
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+        graph
+    }
+
+    /// Return the names of the subcircuits defined in the netlist,
+    /// ordered such that each subcircuit appears after all subcircuits it instantiates.
+    ///
+    /// Subcircuits that are instantiated but not defined in the netlist are omitted.
+    /// Returns [`Error::DependencyCycle`] if a subcircuit (transitively) instantiates itself.
+    pub fn topological_order(&self) -> Result<Vec<&str>> {
+        let graph = self.dependency_graph();
+        let mut order = Vec::with_capacity(graph.len());
+        let mut done = HashSet::new();
+        let mut stack = Vec::new();
+
+        for subckt in self.subcircuits() {
+            visit(subckt.name, &graph, &mut done, &mut stack, &mut order)?;
+        }
+
+        Ok(order)
+    }
+}
+
+/// Collects the subcircuits of `parsed`, which was read from `source`,
+/// and of all files it includes.
+fn resolve(
+    parsed: &ParsedSpice,
+    dir: &Path,
+    source: Option<&Path>,
+    visited: &mut HashSet<PathBuf>,
+    resolved: &mut ResolvedSpice,
+) -> Result<()> {
+    for line in parsed.lines() {
+        let include = match line {
+            SpiceLine::Subckt(subckt) => {
+                resolved.subcircuits.push(ResolvedSubckt {
+                    name: subckt.name.to_string(),
+                    ports: subckt.ports.iter().map(|port| port.to_string()).collect(),
+                    source: source.map(Path::to_path_buf),
+                });
+                continue;
+            }
+            SpiceLine::Include(path) => path,
+            SpiceLine::Lib {
+                path,
+                section: Some(_),
+            } => path,
+            _ => continue,
+        };
+
+        let path = dir.join(include);
+        let key = path.canonicalize().unwrap_or_else(|_| path.clone());
+        if visited.contains(&key) {
+            continue;
+        }
+
+        let data = match std::fs::read_to_string(&path) {
+            Ok(data) => data,
+            Err(e) if e.kind() == ErrorKind::NotFound => {
+                resolved.missing.push(path);
+                continue;
+            }
+            Err(e) => return Err(e.into()),
+        };
+        visited.insert(key);
+
+        let included = parse(&data)?;
+        let dir = path.parent().unwrap_or(dir);
+        resolve(&included, dir, Some(&path), visited, resolved)?;
+    }
+    Ok(())
+}
+
+/// Visits `node` in a depth-first traversal of `graph`, appending it to `order`
+/// after all of its dependencies.
+///
+/// `stack` holds the path of nodes currently being visited, and is used to detect cycles.
+fn visit<'a>(
+    node: &'a str,
+    graph: &HashMap<&'a str, Vec<&'a str>>,
+    done: &mut HashSet<&'a str>,
+    stack: &mut Vec<&'a str>,
+    order: &mut Vec<&'a str>,
+) -> Result<()> {
+    if done.contains(node) {
+        return Ok(());
+    }
+    if let Some(start) = stack.iter().position(|&n| n == node) {
+        let mut cycle = stack[start..]
+            .iter()
+            .map(|n| n.to_string())
+            .collect::<Vec<_>>();
+        cycle.push(node.to_string());
+        return Err(Error::DependencyCycle(cycle));
+    }
+    let deps = match graph.get(node) {
+        Some(deps) => deps,
+        // Subcircuits that are not defined in the netlist have no known dependencies.
+        None => return Ok(()),
+    };
+
+    stack.push(node);
+    for &dep in deps {
+        visit(dep, graph, done, stack, order)?;
+    }
+    stack.pop();
+
+    done.insert(node);
+    order.push(node);
+    Ok(())
 }
@@ -1,10 +1,10 @@
 use std::str;
 
 use nom::branch::alt;
-use nom::bytes::complete::{tag_no_case, take_till, take_till1};
+use nom::bytes::complete::{tag, tag_no_case, take_till, take_till1};
 use nom::character::complete::{line_ending, multispace0, space0, space1};
 use nom::character::streaming::char;
-use nom::combinator::opt;
+use nom::combinator::{opt, verify};
 use nom::multi::{many0, many1};
 use nom::sequence::{delimited, pair, preceded, tuple};
 use nom::IResult;
@@ -16,6 +16,18 @@ mod tests;
 #[derive(Debug, Clone, PartialEq, Serialize)]
 pub enum SpiceLine<'a> {
     Subckt(SubcktLine<'a>),
+    /// The end of a subcircuit definition, with an optional subcircuit name.
+    Ends(Option<&'a str>),
+    Instance(InstanceLine<'a>),
+    /// An `.include` directive, with surrounding quotes removed from the path.
+    Include(&'a str),
+    /// A `.lib` directive.
+    ///
+    /// Within library files, `.lib` directives with no section name mark the start of a section.
+    Lib {
+        path: &'a str,
+        section: Option<&'a str>,
+    },
     Comment(&'a str),
     Other,
 }
@@ -27,6 +39,13 @@ impl<'a> SpiceLine<'a> {
             _ => None,
         }
     }
+
+    pub fn instance(&self) -> Option<&InstanceLine> {
+        match self {
+            SpiceLine::Instance(line) => Some(line),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Eq, PartialEq, Serialize)]
@@ -35,6 +54,39 @@ pub struct SubcktLine<'a> {
     pub ports: Vec<&'a str>,
 }
 
+/// The direction of a subcircuit pin, as declared by a `.PININFO` comment.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Serialize)]
+pub enum PinDirection {
+    Input,
+    Output,
+    /// A bidirectional, power, or ground pin.
+    InOut,
+}
+
+impl PinDirection {
+    /// Parses a `.PININFO` direction code:
+    /// `I` (input), `O` (output), `B` (bidirectional), `P` (power), or `G` (ground).
+    pub fn from_code(code: &str) -> Option<Self> {
+        match code.to_ascii_uppercase().as_str() {
+            "I" => Some(Self::Input),
+            "O" => Some(Self::Output),
+            "B" | "P" | "G" => Some(Self::InOut),
+            _ => None,
+        }
+    }
+}
+
+/// A subcircuit instance (`X`) line.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize)]
+pub struct InstanceLine<'a> {
+    pub name: &'a str,
+    pub ports: Vec<&'a str>,
+    /// The name of the instantiated subcircuit.
+    pub subckt: &'a str,
+    /// Parameter assignments following the subcircuit name, eg. `w=1`.
+    pub params: Vec<&'a str>,
+}
+
 fn is_newline(c: char) -> bool {
     c == '\n' || c == '\r'
 }
@@ -94,6 +146,72 @@ fn subckt_line(input: &str) -> IResult<&str, SpiceLine> {
     Ok((input, SpiceLine::Subckt(SubcktLine { name, ports })))
 }
 
+fn ends_line(input: &str) -> IResult<&str, SpiceLine> {
+    let (input, (_, name)) = pair(tag_no_case(".ends"), opt(subckt_name))(input)?;
+    Ok((input, SpiceLine::Ends(name)))
+}
+
+fn is_param(token: &str) -> bool {
+    token.contains('=') || token.eq_ignore_ascii_case("params:")
+}
+
+fn instance_line(input: &str) -> IResult<&str, SpiceLine> {
+    let (input, (name, tokens)) = pair(
+        verify(ident, |name: &str| {
+            name.starts_with('x') || name.starts_with('X')
+        }),
+        verify(many1(preceded(spice_space1, ident)), |tokens: &[&str]| {
+            !is_param(tokens[0])
+        }),
+    )(input)?;
+
+    let split = tokens
+        .iter()
+        .position(|token| is_param(token))
+        .unwrap_or(tokens.len());
+    let (positional, params) = tokens.split_at(split);
+    let (subckt, ports) = positional.split_last().unwrap();
+
+    Ok((
+        input,
+        SpiceLine::Instance(InstanceLine {
+            name,
+            ports: ports.to_vec(),
+            subckt,
+            params: params.to_vec(),
+        }),
+    ))
+}
+
+fn quoted(quote: &'static str) -> impl FnMut(&str) -> IResult<&str, &str> {
+    move |input| {
+        delimited(
+            tag(quote),
+            take_till(|c| quote.starts_with(c) || is_newline(c)),
+            tag(quote),
+        )(input)
+    }
+}
+
+fn path(input: &str) -> IResult<&str, &str> {
+    preceded(spice_space1, alt((quoted("\""), quoted("'"), ident)))(input)
+}
+
+fn include_line(input: &str) -> IResult<&str, SpiceLine> {
+    let (input, (_, path)) =
+        pair(alt((tag_no_case(".include"), tag_no_case(".inc"))), path)(input)?;
+    Ok((input, SpiceLine::Include(path)))
+}
+
+fn lib_line(input: &str) -> IResult<&str, SpiceLine> {
+    let (input, (_, path, section)) = tuple((
+        tag_no_case(".lib"),
+        path,
+        opt(preceded(spice_space1, ident)),
+    ))(input)?;
+    Ok((input, SpiceLine::Lib { path, section }))
+}
+
 fn comment_line(input: &str) -> IResult<&str, SpiceLine> {
     let (input, (_, _, comment, _)) =
         tuple((space0, tag_no_case("*"), take_till(is_newline), line_ending))(input)?;
@@ -101,12 +219,20 @@ fn comment_line(input: &str) -> IResult<&str, SpiceLine> {
 }
 
 fn other_line(input: &str) -> IResult<&str, SpiceLine> {
-    let (input, _) = pair(ident, many0(preceded(ident, spice_space1)))(input)?;
+    let (input, _) = pair(ident, many0(preceded(spice_space1, ident)))(input)?;
     Ok((input, SpiceLine::Other))
 }
 
 fn spice_line(input: &str) -> IResult<&str, SpiceLine> {
-    alt((subckt_line, comment_line, other_line))(input)
+    alt((
+        subckt_line,
+        ends_line,
+        instance_line,
+        include_line,
+        lib_line,
+        comment_line,
+        other_line,
+    ))(input)
 }
 
 pub(crate) fn parse_spice(input: &str) -> IResult<&str, Vec<SpiceLine>> {
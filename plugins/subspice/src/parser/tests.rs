@@ -1,7 +1,8 @@
 use std::path::PathBuf;
 
+use crate::error::Error;
 use crate::parse;
-use crate::parser::SubcktLine;
+use crate::parser::{InstanceLine, PinDirection, SubcktLine};
 
 pub(crate) const EXAMPLES_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/examples");
 
@@ -122,3 +123,125 @@ fn test_sram() {
         }
     );
 }
+
+const SPICE_CHAIN: &str = r#"
+.subckt top a y vdd vss
+Xbuf0 a mid vdd vss buffer
+Xbuf1 mid
++ y vdd vss buffer
+.ends top
+
+.subckt buffer a y vdd vss
+Xinv0 a ab vdd vss inverter
+Xinv1 ab y vdd vss inverter w=2
+.ends buffer
+
+.subckt inverter a y vdd vss
+M0 y a vss vss nmos
+M1 y a vdd vdd pmos
+.ends inverter
+"#;
+
+#[test]
+fn test_instances() {
+    let parsed = parse(&SPICE_CHAIN).unwrap();
+    assert_eq!(parsed.instances().count(), 4);
+    assert_eq!(
+        parsed.instances().nth(1).unwrap(),
+        &InstanceLine {
+            name: "Xbuf1",
+            ports: vec!["mid", "y", "vdd", "vss"],
+            subckt: "buffer",
+            params: vec![],
+        }
+    );
+    assert_eq!(
+        parsed.instances().nth(3).unwrap(),
+        &InstanceLine {
+            name: "Xinv1",
+            ports: vec!["ab", "y", "vdd", "vss"],
+            subckt: "inverter",
+            params: vec!["w=2"],
+        }
+    );
+}
+
+#[test]
+fn test_dependency_graph() {
+    let parsed = parse(&SPICE_CHAIN).unwrap();
+    let graph = parsed.dependency_graph();
+    assert_eq!(graph.len(), 3);
+    assert_eq!(graph["top"], vec!["buffer"]);
+    assert_eq!(graph["buffer"], vec!["inverter"]);
+    assert!(graph["inverter"].is_empty());
+
+    assert_eq!(
+        parsed.topological_order().unwrap(),
+        vec!["inverter", "buffer", "top"]
+    );
+}
+
+#[test]
+fn test_dependency_cycle() {
+    let spice = r#"
+.subckt a x
+X0 x b
+.ends a
+
+.subckt b x
+X0 x c
+.ends b
+
+.subckt c x
+X0 x a
+.ends c
+"#;
+    let parsed = parse(&spice).unwrap();
+    assert!(matches!(
+        parsed.topological_order(),
+        Err(Error::DependencyCycle(cycle)) if cycle == vec!["a", "b", "c", "a"]
+    ));
+}
+
+#[test]
+fn test_resolve_includes() {
+    let dir = PathBuf::from(EXAMPLES_PATH).join("includes");
+    let data = std::fs::read_to_string(dir.join("top.spice")).unwrap();
+    let parsed = parse(&data).unwrap();
+    assert!(parsed.subcircuit_named("inverter").is_none());
+
+    let resolved = parsed.resolve_includes(&dir).unwrap();
+    assert_eq!(resolved.subcircuits().count(), 3);
+    let inverter = resolved.subcircuit_named("inverter").unwrap();
+    assert_eq!(inverter.ports, vec!["a", "y", "vdd", "vss"]);
+    assert_eq!(
+        inverter.source.as_deref(),
+        Some(dir.join("lib/devices.spice").as_path())
+    );
+    assert_eq!(resolved.subcircuit_named("top").unwrap().source, None);
+    assert!(resolved.subcircuit_named("buffer").is_some());
+    assert_eq!(resolved.missing, vec![dir.join("missing.spice")]);
+}
+
+#[test]
+fn test_pin_directions() {
+    let spice = r#"
+.subckt inverter a y vdd vss
+*.PININFO a:I y:O vdd:P vss:G
+M0 y a vss vss nmos
+M1 y a vdd vdd pmos
+.ends inverter
+
+.subckt other a
+*.PININFO a:O
+.ends other
+"#;
+    let parsed = parse(&spice).unwrap();
+    let directions = parsed.pin_directions("inverter");
+    assert_eq!(directions.len(), 4);
+    assert_eq!(directions["a"], PinDirection::Input);
+    assert_eq!(directions["y"], PinDirection::Output);
+    assert_eq!(directions["vdd"], PinDirection::InOut);
+    assert_eq!(directions["vss"], PinDirection::InOut);
+    assert!(parsed.pin_directions("missing").is_empty());
+}
@@ -12,6 +12,9 @@ pub enum Error {
 
     #[error("invalid params")]
     InvalidParams,
+
+    #[error("component does not provide a testbench")]
+    TestbenchUnsupported,
 }
 
 /// A result for the SubComponent API.
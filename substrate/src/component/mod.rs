@@ -6,10 +6,11 @@ use std::fmt::Display;
 use arcstr::ArcStr;
 use serde::{Deserialize, Serialize};
 
-use crate::data::SubstrateCtx;
+use crate::data::{fnv1a_hash, SubstrateCtx};
 use crate::error::{ErrorSource, Result};
 use crate::layout::context::LayoutCtx;
 use crate::schematic::context::SchematicCtx;
+use crate::verification::simulation::testbench::ComponentTestbench;
 use crate::verification::timing::context::TimingCtx;
 
 pub mod error;
@@ -103,6 +104,13 @@ pub trait Component: Any {
         // Thus, the default is to return no timing constraints, rather than an error.
         Ok(())
     }
+
+    /// Returns a testbench for verifying this component, if it provides one.
+    ///
+    /// Bundled testbenches are run by [`SubstrateCtx::simulate_component`].
+    fn testbench(&self) -> Option<Box<dyn ComponentTestbench>> {
+        None
+    }
 }
 
 /// An empty type for components that are not parametrized.
@@ -120,3 +128,13 @@ where
     x.serialize(&mut s).unwrap();
     s.take_buffer()
 }
+
+/// Hashes the serialized representation of the given parameters.
+///
+/// Uses the FNV-1a hash, so hashes do not change across Rust versions.
+pub(crate) fn hash_params<T>(x: &T) -> u64
+where
+    T: Serialize,
+{
+    fnv1a_hash([serialize_params(x).as_slice()])
+}
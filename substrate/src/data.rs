@@ -1,19 +1,23 @@
+use std::any::Any;
 use std::collections::{HashMap, HashSet};
 use std::fs::File;
 use std::io::Write;
+use std::panic::AssertUnwindSafe;
 use std::path::{Path, PathBuf};
-use std::sync::{Arc, RwLock, RwLockReadGuard, RwLockWriteGuard};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard};
+use std::thread::ThreadId;
 
 use serde::{Deserialize, Serialize};
 use tempdir::TempDir;
 
-use crate::component::{Component, View};
+use crate::component::{hash_params, serialize_params, Component, View};
 use crate::deps::arcstr::ArcStr;
 use crate::digital::context::{DigitalCtx, DigitalData};
 use crate::digital::module::{DigitalModule, DigitalModuleKey, Instance as DigitalInstance};
 use crate::digital::{DigitalComponent, Interface};
 use crate::error::{with_err_context, ErrorContext, ErrorSource, Result, SubstrateError};
-use crate::generation::GeneratedCheck;
+use crate::generation::{GeneratedCheck, GenerationSignal};
 use crate::io::create_dir_all;
 use crate::layout::cell::{Cell, CellKey, Instance as LayoutInstance};
 use crate::layout::context::{LayoutCtx, LayoutData};
@@ -28,7 +32,10 @@ use crate::pdk::Pdk;
 use crate::schematic::circuit::{Instance as SchematicInstance, Reference};
 use crate::schematic::context::{ModuleKey, SchematicCtx, SchematicData};
 use crate::schematic::module::{AbstractModule, ExternalModule, Module, RawSource};
-use crate::schematic::netlist::interface::{InstanceInfo, Netlister, SubcircuitInfo};
+use crate::schematic::netlist::diff::NetlistDiff;
+use crate::schematic::netlist::interface::{
+    InstanceInfo, NetlistMetadata, Netlister, SubcircuitInfo,
+};
 use crate::schematic::netlist::preprocess::{preprocess_netlist, PreprocessedNetlist};
 use crate::schematic::netlist::NetlistPurpose;
 use crate::schematic::validation::connectivity::validate_connectivity;
@@ -36,7 +43,7 @@ use crate::schematic::validation::drivers::validate_drivers;
 use crate::schematic::validation::naming::validate_naming;
 use crate::script::map::ScriptMap;
 use crate::script::Script;
-use crate::verification::drc::{DrcInput, DrcOutput, DrcTool};
+use crate::verification::drc::{run_partitioned, DrcInput, DrcOutput, DrcTool};
 use crate::verification::lvs::{LvsInput, LvsOutput, LvsTool};
 use crate::verification::pex::{PexInput, PexOutput, PexTool};
 use crate::verification::simulation::context::{PostSimCtx, PreSimCtx};
@@ -44,6 +51,7 @@ use crate::verification::simulation::testbench::Testbench;
 use crate::verification::simulation::{SimInput, SimOpts, Simulator};
 use crate::verification::timing::context::TimingCtx;
 use crate::verification::timing::{generate_timing_report, TimingConfig};
+use crate::verification::CleanupPolicy;
 
 pub(crate) struct SubstrateData {
     schematics: SchematicData,
@@ -61,6 +69,7 @@ pub(crate) struct SubstrateData {
     script_map: ScriptMap,
     corner_db: Arc<CornerDb>,
     simulation_bashrc: Option<PathBuf>,
+    scratch_dir: Option<PathBuf>,
     timing_config: Option<Arc<TimingConfig>>,
 }
 
@@ -72,6 +81,7 @@ pub struct SubstrateConfig {
     pub lvs_tool: Option<Arc<dyn LvsTool>>,
     pub pex_tool: Option<Arc<dyn PexTool>>,
     pub simulation_bashrc: Option<PathBuf>,
+    pub scratch_dir: Option<PathBuf>,
     pub timing_config: Option<Arc<TimingConfig>>,
 }
 
@@ -84,6 +94,7 @@ pub struct SubstrateConfigBuilder {
     pub lvs_tool: Option<Arc<dyn LvsTool>>,
     pub pex_tool: Option<Arc<dyn PexTool>>,
     pub simulation_bashrc: Option<PathBuf>,
+    pub scratch_dir: Option<PathBuf>,
     pub timing_config: Option<Arc<TimingConfig>>,
 }
 
@@ -113,6 +124,8 @@ pub(crate) struct InnerWriteSchematicArgs<W> {
     top: ModuleKey,
     flatten_top: FlattenTop,
     purpose: NetlistPurpose,
+    component: &'static str,
+    params_hash: u64,
     out: W,
 }
 
@@ -144,6 +157,7 @@ impl SubstrateData {
             pex_tool: cfg.pex_tool,
             script_map: ScriptMap::new(),
             simulation_bashrc: cfg.simulation_bashrc,
+            scratch_dir: cfg.scratch_dir,
             timing_config: cfg.timing_config,
         })
     }
@@ -152,6 +166,8 @@ impl SubstrateData {
 #[derive(Clone)]
 pub struct SubstrateCtx {
     inner: Arc<RwLock<SubstrateData>>,
+    /// Wakes threads waiting on objects generated by other threads.
+    generation: Arc<GenerationSignal>,
 }
 
 impl SubstrateConfig {
@@ -218,6 +234,16 @@ impl SubstrateConfigBuilder {
         self
     }
 
+    /// Sets the base directory in which [`SubstrateCtx::sim_work_dir`]
+    /// allocates simulation working directories.
+    pub fn scratch_dir<P>(&mut self, path: P) -> &mut Self
+    where
+        P: Into<PathBuf>,
+    {
+        self.scratch_dir = Some(path.into());
+        self
+    }
+
     pub fn timing_config(&mut self, config: TimingConfig) -> &mut Self {
         self.timing_config = Some(Arc::new(config));
         self
@@ -232,26 +258,36 @@ impl SubstrateConfigBuilder {
             lvs_tool: self.lvs_tool.clone(),
             pex_tool: self.pex_tool.clone(),
             simulation_bashrc: self.simulation_bashrc.clone(),
+            scratch_dir: self.scratch_dir.clone(),
             timing_config: self.timing_config.clone(),
         }
     }
 }
 
 impl SubstrateCtx {
+    /// Acquires a read lock on the context's data.
+    ///
+    /// A generator that panics while holding the lock poisons it. The poison is ignored,
+    /// since the panicking generator's partially generated object is aborted,
+    /// so that one failed generator does not cause all others to fail.
     #[inline]
     pub(crate) fn read(&self) -> RwLockReadGuard<SubstrateData> {
-        self.inner.read().unwrap()
+        self.inner.read().unwrap_or_else(PoisonError::into_inner)
     }
 
+    /// Acquires a write lock on the context's data.
+    ///
+    /// Recovers from poisoning in the same way as [`SubstrateCtx::read`].
     #[inline]
     pub(crate) fn write(&self) -> RwLockWriteGuard<SubstrateData> {
-        self.inner.write().unwrap()
+        self.inner.write().unwrap_or_else(PoisonError::into_inner)
     }
 
     #[inline]
     pub fn from_config(cfg: SubstrateConfig) -> Result<Self> {
         Ok(Self {
             inner: Arc::new(RwLock::new(SubstrateData::from_config(cfg)?)),
+            generation: Arc::new(GenerationSignal::new()),
         })
     }
 
@@ -259,6 +295,16 @@ impl SubstrateCtx {
         self.read().pdk()
     }
 
+    /// Returns the name of the active PDK, as given by [`Pdk::name`].
+    pub fn pdk_name(&self) -> &'static str {
+        self.pdk().name()
+    }
+
+    /// Returns the process of the active PDK, as given by [`Pdk::process`].
+    pub fn pdk_process(&self) -> &'static str {
+        self.pdk().process()
+    }
+
     pub fn simulator(&self) -> Option<Arc<dyn Simulator>> {
         self.read().simulator()
     }
@@ -355,15 +401,17 @@ impl SubstrateCtx {
     where
         T: Component,
     {
-        let check = {
+        let check = wait_for_generation(&self.generation, || {
             let mut inner = self.write();
             inner.schematics.get_module::<T>(params)
-        };
+        })?;
 
         Ok(match check {
             GeneratedCheck::Exists(module) => SchematicInstance::new(Reference::Local(module)),
             GeneratedCheck::MustGenerate(id) => {
+                let guard = AbortGuard::new(self, |inner| inner.schematics.abort_module(id));
                 let module = self.generate_schematic::<T>(params, id)?;
+                guard.disarm();
                 SchematicInstance::new(Reference::Local(module))
             }
         })
@@ -385,6 +433,8 @@ impl SubstrateCtx {
             top,
             flatten_top: args.flatten_top,
             purpose: args.purpose,
+            component: std::any::type_name::<T>(),
+            params_hash: hash_params(args.params),
             out: args.out,
         };
         let mut inner = self.write();
@@ -408,6 +458,8 @@ impl SubstrateCtx {
             top,
             flatten_top: args.flatten_top,
             purpose: args.purpose,
+            component: std::any::type_name::<T>(),
+            params_hash: hash_params(args.params),
             out: args.out,
         };
         let mut inner = self.write();
@@ -415,6 +467,33 @@ impl SubstrateCtx {
         Ok(netlist)
     }
 
+    /// Compares the schematics of two components.
+    ///
+    /// Returns the changes needed to turn the netlist of `A` into the netlist of `B`.
+    /// Useful for checking that a refactored generator produces the same netlist.
+    pub fn diff_schematics<A, B>(&self, a: &A::Params, b: &B::Params) -> Result<NetlistDiff>
+    where
+        A: Component,
+        B: Component,
+    {
+        let a = self.preprocessed_schematic::<A>(a)?;
+        let b = self.preprocessed_schematic::<B>(b)?;
+        Ok(a.diff(&b))
+    }
+
+    fn preprocessed_schematic<T>(&self, params: &T::Params) -> Result<PreprocessedNetlist>
+    where
+        T: Component,
+    {
+        let inst = self.instantiate_schematic::<T>(params)?;
+        let top = inst
+            .module()
+            .local_id()
+            .ok_or(ErrorSource::NetlistExternalModule)?;
+        let inner = self.read();
+        preprocess_netlist(&inner.schematics, top)
+    }
+
     #[inline]
     pub fn write_schematic<T, W: Write>(&self, params: &T::Params, out: W) -> Result<()>
     where
@@ -479,21 +558,107 @@ impl SubstrateCtx {
     where
         T: Component,
     {
-        let check = {
+        let check = wait_for_generation(&self.generation, || {
             let mut inner = self.write();
             inner.layouts.get_generated_cell::<T>(params)
-        };
+        })?;
 
         Ok(match check {
             GeneratedCheck::Exists(cell) => LayoutInstance::new(cell),
             GeneratedCheck::MustGenerate(id) => {
+                let guard = AbortGuard::new(self, |inner| inner.layouts.abort_cell(id));
                 let cell = self.generate_layout::<T>(params, id)?;
+                guard.disarm();
                 LayoutInstance::new(cell)
             }
         })
     }
 
+    /// Generates a view of component `T` for each entry of `params_list` in parallel.
+    ///
+    /// Returns one result per entry of `params_list`, in the same order.
+    /// Successfully generated views are cached, so instantiating them afterwards
+    /// does not regenerate them.
+    ///
+    /// Work is split across up to [`std::thread::available_parallelism`] threads.
+    /// All threads share this context's generation caches, which are guarded by
+    /// the context's lock. If several threads require the same subcomponent, one
+    /// thread generates it while the others wait for it to finish. Entries that
+    /// fail to generate are removed from the cache.
+    ///
+    /// If a generator panics, the panic is reported as an error for its entry,
+    /// and the objects it was generating are removed from the cache so that
+    /// other threads do not wait on them.
+    ///
+    /// Only [`View::Schematic`] and [`View::Layout`] are supported.
+    pub fn generate_batch<T>(&self, params_list: &[T::Params], view: View) -> Vec<Result<()>>
+    where
+        T: Component,
+        T::Params: Sync,
+    {
+        let next = AtomicUsize::new(0);
+        let results = Mutex::new(
+            (0..params_list.len())
+                .map(|_| None)
+                .collect::<Vec<Option<Result<()>>>>(),
+        );
+        let threads = std::thread::available_parallelism()
+            .map(|n| n.get())
+            .unwrap_or(1)
+            .min(params_list.len());
+
+        std::thread::scope(|s| {
+            for _ in 0..threads {
+                s.spawn(|| loop {
+                    let i = next.fetch_add(1, Ordering::Relaxed);
+                    if i >= params_list.len() {
+                        break;
+                    }
+                    let params = &params_list[i];
+                    let generate = || match view {
+                        View::Schematic => self.instantiate_schematic::<T>(params).map(|_| ()),
+                        View::Layout => self.instantiate_layout::<T>(params).map(|_| ()),
+                        ref view => Err(crate::component::error::Error::ViewUnsupported(
+                            view.clone(),
+                        )
+                        .into()),
+                    };
+                    let result = std::panic::catch_unwind(AssertUnwindSafe(generate))
+                        .unwrap_or_else(|payload| {
+                            Err(ErrorSource::Internal(format!(
+                                "generator panicked: {}",
+                                panic_message(payload.as_ref())
+                            ))
+                            .into())
+                        });
+                    results.lock().unwrap()[i] = Some(result);
+                });
+            }
+        });
+
+        results
+            .into_inner()
+            .unwrap()
+            .into_iter()
+            .map(|result| result.expect("every batch entry should have been generated"))
+            .collect()
+    }
+
+    /// Writes the layout of component `T` to `path` in the default [`LayoutFormat`].
     pub fn write_layout<T>(&self, params: &T::Params, path: impl AsRef<Path>) -> Result<()>
+    where
+        T: Component,
+    {
+        self.write_layout_with_format::<T>(params, path, LayoutFormat::default())
+    }
+
+    /// Writes the layout of component `T` to `path` in the given [`LayoutFormat`].
+    pub fn write_layout_with_format<T>(
+        &self,
+        params: &T::Params,
+        path: impl AsRef<Path>,
+        format: LayoutFormat,
+    ) -> Result<()>
     where
         T: Component,
     {
@@ -501,11 +666,32 @@ impl SubstrateCtx {
 
         let inner = || -> Result<()> {
             let inst = self.instantiate_layout::<T>(params)?;
-            let top = inst.cell().clone();
+            self.write_cell_layout(inst.cell().clone(), path, format)
+        };
+
+        with_err_context(inner(), || {
+            ErrorContext::Task(arcstr::format!("writing layout to file {:?}", path))
+        })
+    }
+
+    /// Writes `cell` and all cells it instantiates to `path` in the given [`LayoutFormat`].
+    ///
+    /// Creates the parent directories of `path` if they do not exist.
+    pub fn write_cell_layout(
+        &self,
+        cell: Arc<Cell>,
+        path: impl AsRef<Path>,
+        format: LayoutFormat,
+    ) -> Result<()> {
+        let path = path.as_ref();
+
+        let inner = || -> Result<()> {
             if let Some(parent) = path.parent() {
                 create_dir_all(parent)?;
             }
-            self.to_gds_with_top(top, path)?;
+            match format {
+                LayoutFormat::Gds => self.to_gds_with_top(cell, path)?,
+            }
             Ok(())
         };
 
@@ -529,17 +715,20 @@ impl SubstrateCtx {
     where
         T: DigitalComponent,
     {
-        let check = {
+        let check = wait_for_generation(&self.generation, || {
             let mut inner = self.write();
             inner.digital.get_generated_module::<T>(params)
-        };
+        })?;
 
-        let component = self.init_component::<T>(params)?;
+        let component = self.init_component::<T>(params);
 
         Ok(match check {
-            GeneratedCheck::Exists(id) => (DigitalInstance::new(id), component.interface()),
+            GeneratedCheck::Exists(id) => (DigitalInstance::new(id), component?.interface()),
             GeneratedCheck::MustGenerate(id) => {
-                let (id, intf) = self.generate_digital::<T>(component, id)?;
+                let guard = AbortGuard::new(self, |inner| inner.digital.abort_module(id));
+                let (id, intf) =
+                    component.and_then(|component| self.generate_digital::<T>(component, id))?;
+                guard.disarm();
                 (DigitalInstance::new(id), intf)
             }
         })
@@ -552,9 +741,26 @@ impl SubstrateCtx {
     }
 
     fn _run_drc(&self, input: DrcInput) -> Result<DrcOutput> {
-        self.drc_tool()
-            .ok_or(ErrorSource::ToolNotSpecified)?
-            .run_drc(input)
+        let tool = self.drc_tool().ok_or(ErrorSource::ToolNotSpecified)?;
+        let reports = tool.report_paths(&input);
+        let preserve = (0..input.partitions)
+            .flat_map(|i| {
+                reports
+                    .iter()
+                    .map(move |path| PathBuf::from(format!("partition_{i}")).join(path))
+            })
+            .chain(reports.iter().cloned())
+            .collect::<Vec<_>>();
+        let (work_dir, waivers, cleanup) =
+            (input.work_dir.clone(), input.waivers.clone(), input.cleanup);
+
+        let output = run_partitioned(tool.as_ref(), input).map(|mut output| {
+            output.apply_waivers(&waivers);
+            output
+        });
+        let success = matches!(&output, Ok(output) if output.summary.is_ok());
+        cleanup.apply(work_dir, success, &preserve)?;
+        output
     }
 
     pub fn write_drc<T>(&self, params: &T::Params, work_dir: impl AsRef<Path>) -> Result<DrcOutput>
@@ -576,14 +782,21 @@ impl SubstrateCtx {
     {
         let work_dir = work_dir.as_ref();
         create_dir_all(work_dir)?;
-        let layout_path = PathBuf::from(&work_dir).join("layout.gds");
-        self.write_layout::<T>(params, &layout_path)?;
+        let layout_format = self
+            .drc_tool()
+            .map(|tool| tool.layout_format())
+            .unwrap_or_default();
+        let layout_path = self.write_verification_layout::<T>(params, work_dir, &layout_format)?;
         self.run_drc(DrcInput {
             cell_name: T::new(params, self)?.name(),
             work_dir: PathBuf::from(&work_dir),
             layout_path,
-            layout_format: LayoutFormat::Gds,
+            layout_format,
             opts: HashMap::new(),
+            waivers: Vec::new(),
+            rules: None,
+            partitions: 1,
+            cleanup: CleanupPolicy::default(),
         })
     }
 
@@ -594,9 +807,14 @@ impl SubstrateCtx {
     }
 
     fn _run_lvs(&self, input: LvsInput) -> Result<LvsOutput> {
-        self.lvs_tool()
-            .ok_or(ErrorSource::ToolNotSpecified)?
-            .run_lvs(input)
+        let tool = self.lvs_tool().ok_or(ErrorSource::ToolNotSpecified)?;
+        let preserve = tool.report_paths(&input);
+        let (work_dir, cleanup) = (input.work_dir.clone(), input.cleanup);
+
+        let output = tool.run_lvs(input);
+        let success = matches!(&output, Ok(output) if output.summary.is_ok());
+        cleanup.apply(work_dir, success, &preserve)?;
+        output
     }
 
     pub fn write_lvs<T>(&self, params: &T::Params, work_dir: impl AsRef<Path>) -> Result<LvsOutput>
@@ -613,13 +831,29 @@ impl SubstrateCtx {
     }
 
     fn _write_lvs<T>(&self, params: &T::Params, work_dir: impl AsRef<Path>) -> Result<LvsOutput>
+    where
+        T: Component,
+    {
+        let input = self.lvs_input::<T>(params, work_dir)?;
+        self.run_lvs(input)
+    }
+
+    /// Writes the layout and schematic of component `T` to `work_dir`
+    /// and returns the corresponding [`LvsInput`].
+    ///
+    /// The ground and supply nets are populated from the nets marked with
+    /// [`SchematicCtx::mark_ground`] and [`SchematicCtx::mark_supply`].
+    pub fn lvs_input<T>(&self, params: &T::Params, work_dir: impl AsRef<Path>) -> Result<LvsInput>
     where
         T: Component,
     {
         let work_dir = work_dir.as_ref();
         create_dir_all(work_dir)?;
-        let layout_path = PathBuf::from(&work_dir).join("layout.gds");
-        self.write_layout::<T>(params, &layout_path)?;
+        let layout_format = self
+            .lvs_tool()
+            .map(|tool| tool.layout_format())
+            .unwrap_or_default();
+        let layout_path = self.write_verification_layout::<T>(params, work_dir, &layout_format)?;
         let schematic_path = PathBuf::from(&work_dir).join("netlist.spice");
         self.write_schematic_to_file_for_purpose::<T>(
             params,
@@ -627,14 +861,18 @@ impl SubstrateCtx {
             NetlistPurpose::Lvs,
         )?;
         let cell_name = T::new(params, self)?.name();
-        self.run_lvs(LvsInput {
+        let top = self.top_module::<T>(params)?;
+        Ok(LvsInput {
             work_dir: PathBuf::from(&work_dir),
             layout_path,
             layout_cell_name: cell_name.clone(),
-            layout_format: LayoutFormat::Gds,
+            layout_format,
             source_paths: vec![schematic_path],
             source_cell_name: cell_name,
             opts: HashMap::new(),
+            ground_net: top.ground_net().map(|net| net.to_string()),
+            supply_nets: top.supply_nets().map(|(net, _)| net.to_string()).collect(),
+            cleanup: CleanupPolicy::default(),
         })
     }
 
@@ -645,9 +883,15 @@ impl SubstrateCtx {
     }
 
     fn _run_pex(&self, input: PexInput) -> Result<PexOutput> {
-        self.pex_tool()
-            .ok_or(ErrorSource::ToolNotSpecified)?
-            .run_pex(input)
+        let tool = self.pex_tool().ok_or(ErrorSource::ToolNotSpecified)?;
+        let mut preserve = tool.report_paths(&input);
+        preserve.push(input.pex_netlist_path.clone());
+        let (work_dir, cleanup) = (input.work_dir.clone(), input.cleanup);
+
+        let output = tool.run_pex(input);
+        let success = matches!(&output, Ok(output) if output.summary.is_ok());
+        cleanup.apply(work_dir, success, &preserve)?;
+        output
     }
 
     pub fn write_pex<T>(
@@ -657,6 +901,22 @@ impl SubstrateCtx {
         pex_netlist_path: impl Into<PathBuf>,
         ground_net: &str,
     ) -> Result<PexOutput>
+    where
+        T: Component,
+    {
+        self.write_pex_opt_ground::<T>(params, work_dir, pex_netlist_path, Some(ground_net))
+    }
+
+    /// Runs PEX on component `T` in `work_dir`.
+    ///
+    /// If `ground_net` is [`None`], the net marked with [`SchematicCtx::mark_ground`] is used.
+    pub fn write_pex_opt_ground<T>(
+        &self,
+        params: &T::Params,
+        work_dir: impl AsRef<Path>,
+        pex_netlist_path: impl Into<PathBuf>,
+        ground_net: Option<&str>,
+    ) -> Result<PexOutput>
     where
         T: Component,
     {
@@ -678,15 +938,58 @@ impl SubstrateCtx {
         params: &T::Params,
         work_dir: impl AsRef<Path>,
         pex_netlist_path: impl Into<PathBuf>,
-        ground_net: &str,
+        ground_net: Option<&str>,
     ) -> Result<PexOutput>
     where
         T: Component,
     {
+        let input = self._pex_input::<T>(params, work_dir, pex_netlist_path, ground_net)?;
+        self.run_pex(input)
+    }
+
+    /// Writes the layout and schematic of component `T` to `work_dir`
+    /// and returns the corresponding [`PexInput`].
+    ///
+    /// The ground net is populated from the net marked with [`SchematicCtx::mark_ground`].
+    /// Returns an error if no ground net has been marked.
+    pub fn pex_input<T>(
+        &self,
+        params: &T::Params,
+        work_dir: impl AsRef<Path>,
+        pex_netlist_path: impl Into<PathBuf>,
+    ) -> Result<PexInput>
+    where
+        T: Component,
+    {
+        self._pex_input::<T>(params, work_dir, pex_netlist_path, None)
+    }
+
+    fn _pex_input<T>(
+        &self,
+        params: &T::Params,
+        work_dir: impl AsRef<Path>,
+        pex_netlist_path: impl Into<PathBuf>,
+        ground_net: Option<&str>,
+    ) -> Result<PexInput>
+    where
+        T: Component,
+    {
+        let ground_net = match ground_net {
+            Some(ground_net) => ground_net.to_string(),
+            None => self
+                .top_module::<T>(params)?
+                .ground_net()
+                .ok_or(ErrorSource::GroundNetNotSpecified)?
+                .to_string(),
+        };
+
         let work_dir = work_dir.as_ref();
         create_dir_all(work_dir)?;
-        let layout_path = PathBuf::from(&work_dir).join("layout.gds");
-        self.write_layout::<T>(params, &layout_path)?;
+        let layout_format = self
+            .pex_tool()
+            .map(|tool| tool.layout_format())
+            .unwrap_or_default();
+        let layout_path = self.write_verification_layout::<T>(params, work_dir, &layout_format)?;
         let schematic_path = PathBuf::from(&work_dir).join("netlist.spice");
         self.write_schematic_to_file_for_purpose::<T>(
             params,
@@ -694,19 +997,48 @@ impl SubstrateCtx {
             NetlistPurpose::Pex,
         )?;
         let cell_name = T::new(params, self)?.name();
-        self.run_pex(PexInput {
+        Ok(PexInput {
             work_dir: PathBuf::from(&work_dir),
             layout_path,
             layout_cell_name: cell_name.clone(),
-            layout_format: LayoutFormat::Gds,
+            layout_format,
             source_paths: vec![schematic_path],
             source_cell_name: cell_name,
             pex_netlist_path: pex_netlist_path.into(),
             opts: HashMap::new(),
-            ground_net: ground_net.to_string(),
+            ground_net,
+            cleanup: CleanupPolicy::default(),
         })
     }
 
+    /// Writes the layout of component `T` to `work_dir` in the given format
+    /// for use by a verification tool, returning the path of the layout file.
+    fn write_verification_layout<T>(
+        &self,
+        params: &T::Params,
+        work_dir: &Path,
+        format: &LayoutFormat,
+    ) -> Result<PathBuf>
+    where
+        T: Component,
+    {
+        let layout_path = work_dir.join(format!("layout.{}", format.extension()));
+        self.write_layout_with_format::<T>(params, &layout_path, format.clone())?;
+        Ok(layout_path)
+    }
+
+    /// Returns the top-level schematic module of component `T`.
+    fn top_module<T>(&self, params: &T::Params) -> Result<Arc<Module>>
+    where
+        T: Component,
+    {
+        Ok(self
+            .instantiate_schematic::<T>(params)?
+            .module()
+            .local()
+            .ok_or(ErrorSource::NetlistExternalModule)?)
+    }
+
     pub fn write_simulation<T>(
         &self,
         params: &T::Params,
@@ -811,6 +1143,7 @@ impl SubstrateCtx {
 
         tb.setup(&mut ctx)?;
         self.pdk().pre_sim(&mut ctx)?;
+        ctx.inner().validate()?;
         let simulator = self.simulator().ok_or(ErrorSource::ToolNotSpecified)?;
 
         let output = if let VerifyTiming::Yes(ref pvt) = verify_timing {
@@ -818,11 +1151,9 @@ impl SubstrateCtx {
             let mut constraints = netlist.timing_constraint_db(pvt);
 
             for constraint in constraints.named_constraints(&netlist) {
-                let port = simulator.node_voltage_string(&constraint.port);
-                ctx.input.save.add(port);
+                ctx.input.save.add(constraint.port.clone());
                 if let Some(ref related_port) = constraint.related_port {
-                    let related_port = simulator.node_voltage_string(related_port);
-                    ctx.input.save.add(related_port);
+                    ctx.input.save.add(related_port.clone());
                 }
             }
 
@@ -855,6 +1186,44 @@ impl SubstrateCtx {
         Ok(output)
     }
 
+    /// Returns the base directory in which simulation working directories are allocated.
+    ///
+    /// This is the directory set by [`SubstrateConfigBuilder::scratch_dir`], or
+    /// a `substrate` directory in the system temporary directory if none was set.
+    pub fn scratch_dir(&self) -> PathBuf {
+        self.read()
+            .scratch_dir()
+            .unwrap_or_else(|| std::env::temp_dir().join("substrate"))
+    }
+
+    /// Returns a working directory for simulating component `T` with the given
+    /// parameters and analysis.
+    ///
+    /// The directory is a subdirectory of [`SubstrateCtx::scratch_dir`] named by
+    /// a hash of the component's [type name](std::any::type_name), its serialized
+    /// parameters, and the serialized analysis. Identical inputs always map to the
+    /// same directory, so reruns can reuse previous results, while distinct inputs
+    /// map to distinct directories. The directory is not created.
+    ///
+    /// The hash is the 64-bit FNV-1a hash of these inputs. Directory names are
+    /// stable across runs, but may change with the compiler version, since
+    /// type names are not guaranteed to be stable across compiler versions.
+    ///
+    /// The analysis may be any serializable value, such as an
+    /// [`Analysis`](crate::verification::simulation::Analysis) or a name.
+    pub fn sim_work_dir<T, A>(&self, params: &T::Params, analysis: &A) -> PathBuf
+    where
+        T: Component,
+        A: Serialize,
+    {
+        let hash = fnv1a_hash([
+            std::any::type_name::<T>().as_bytes(),
+            serialize_params(params).as_slice(),
+            serialize_params(analysis).as_slice(),
+        ]);
+        self.scratch_dir().join(format!("{hash:016x}"))
+    }
+
     pub fn simulate<T>(&self, params: &T::Params) -> Result<T::Output>
     where
         T: Testbench,
@@ -864,6 +1233,42 @@ impl SubstrateCtx {
         self.write_simulation::<T>(params, work_dir)
     }
 
+    /// Runs the testbench bundled with component `T`.
+    ///
+    /// Returns an error if `T` does not provide a testbench (see [`Component::testbench`])
+    /// or if the testbench fails.
+    pub fn write_component_simulation<T>(
+        &self,
+        params: &T::Params,
+        work_dir: impl AsRef<Path>,
+    ) -> Result<()>
+    where
+        T: Component,
+    {
+        let work_dir = work_dir.as_ref();
+        let component = self.init_component::<T>(params)?;
+        let tb = component
+            .testbench()
+            .ok_or(crate::component::error::Error::TestbenchUnsupported)?;
+        with_err_context(tb.simulate(self, work_dir), || {
+            ErrorContext::Task(arcstr::format!(
+                "running testbench of component {}",
+                component.name()
+            ))
+        })
+    }
+
+    /// Runs the testbench bundled with component `T` in a temporary directory.
+    ///
+    /// See [`SubstrateCtx::write_component_simulation`].
+    pub fn simulate_component<T>(&self, params: &T::Params) -> Result<()>
+    where
+        T: Component,
+    {
+        let work_dir = TempDir::new("subsim")?;
+        self.write_component_simulation::<T>(params, work_dir.path())
+    }
+
     pub(crate) fn generate_schematic<T>(
         &self,
         params: &T::Params,
@@ -1056,6 +1461,11 @@ impl SubstrateData {
         self.simulation_bashrc.clone()
     }
 
+    #[inline]
+    pub(crate) fn scratch_dir(&self) -> Option<PathBuf> {
+        self.scratch_dir.clone()
+    }
+
     #[inline]
     pub(crate) fn timing_config(&self) -> Option<Arc<TimingConfig>> {
         self.timing_config.clone()
@@ -1123,6 +1533,14 @@ impl SubstrateData {
 
         let netlister = self.try_netlister()?;
         netlister.emit_begin(&mut out)?;
+        netlister.emit_metadata(
+            &mut out,
+            NetlistMetadata {
+                component: args.component,
+                params_hash: args.params_hash,
+                version: env!("CARGO_PKG_VERSION"),
+            },
+        )?;
         netlister.emit_comment(&mut out, top.name())?;
         netlister.emit_comment(&mut out, "Schematic generated by Substrate")?;
 
@@ -1142,6 +1560,8 @@ impl SubstrateData {
         netlister.emit_raw_spice(&mut out, &includes.raw_spice)?;
         netlister.emit_raw_spice(&mut out, "\n")?;
 
+        // Modules are ordered such that each is defined before it is instantiated,
+        // with the top module last.
         for key in netlist.netlist_order.iter().copied() {
             // If we're emitting the top module directly at the top level,
             // emit only its contents, and rename its ground net.
@@ -1161,7 +1581,10 @@ impl SubstrateData {
             self.emit_module(key, &netlist, &mut out)?;
         }
 
-        for module in self.schematics.external_modules() {
+        let mut external_modules = self.schematics.external_modules().collect::<Vec<_>>();
+        external_modules.sort_by(|a, b| a.name().cmp(b.name()));
+
+        for module in external_modules {
             let source = module.source();
             match source {
                 RawSource::File(path) => {
@@ -1270,7 +1693,7 @@ impl SubstrateData {
 
         netlister.emit_begin_subcircuit(out, info)?;
 
-        for inst in module.instances() {
+        for inst in sorted_instances(module) {
             match inst.module() {
                 Reference::Local(_) => self.emit_local_instance(module, inst, netlist, out)?,
                 Reference::External(_) => self.emit_external_instance(module, inst, out)?,
@@ -1313,7 +1736,7 @@ impl SubstrateData {
     ) -> Result<()> {
         let netlister = self.try_netlister()?;
 
-        for inst in module.instances() {
+        for inst in sorted_instances(module) {
             match inst.module() {
                 Reference::Local(_) => self.emit_local_instance(module, inst, netlist, out)?,
                 Reference::External(_) => self.emit_external_instance(module, inst, out)?,
@@ -1329,6 +1752,112 @@ impl SubstrateData {
     }
 }
 
+/// Aborts the generation of a cached object when dropped, unless disarmed.
+///
+/// Ensures that an object is not left loading forever if its generator
+/// returns an error or panics.
+struct AbortGuard<'a, F>
+where
+    F: FnMut(&mut SubstrateData),
+{
+    ctx: &'a SubstrateCtx,
+    abort: F,
+    armed: bool,
+}
+
+impl<'a, F> AbortGuard<'a, F>
+where
+    F: FnMut(&mut SubstrateData),
+{
+    fn new(ctx: &'a SubstrateCtx, abort: F) -> Self {
+        Self {
+            ctx,
+            abort,
+            armed: true,
+        }
+    }
+
+    /// Keeps the object, since it was generated successfully.
+    fn disarm(mut self) {
+        self.armed = false;
+    }
+}
+
+impl<F> Drop for AbortGuard<'_, F>
+where
+    F: FnMut(&mut SubstrateData),
+{
+    fn drop(&mut self) {
+        if self.armed {
+            // The lock is poisoned if the generator panicked while holding it,
+            // which `SubstrateCtx::write` recovers from.
+            let mut inner = self.ctx.write();
+            (self.abort)(&mut inner);
+        }
+        // Either way, the object is no longer loading.
+        self.ctx.generation.notify();
+    }
+}
+
+/// Computes the 64-bit FNV-1a hash of the given byte strings.
+///
+/// Each part is prefixed with its length, so that different splits
+/// of the same bytes produce different hashes.
+pub(crate) fn fnv1a_hash<'a>(parts: impl IntoIterator<Item = &'a [u8]>) -> u64 {
+    const OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+    const PRIME: u64 = 0x100000001b3;
+
+    let mut hash = OFFSET_BASIS;
+    for part in parts {
+        for &byte in (part.len() as u64).to_le_bytes().iter().chain(part) {
+            hash ^= byte as u64;
+            hash = hash.wrapping_mul(PRIME);
+        }
+    }
+    hash
+}
+
+/// Returns the message of a panic payload, if it has one.
+fn panic_message(payload: &(dyn Any + Send)) -> &str {
+    if let Some(msg) = payload.downcast_ref::<&str>() {
+        msg
+    } else if let Some(msg) = payload.downcast_ref::<String>() {
+        msg
+    } else {
+        "unknown panic"
+    }
+}
+
+/// Calls `check` until it returns a value, blocking on `signal` in between.
+///
+/// Generation caches return the ID of the generating thread as an error while an object
+/// is being generated by another live thread; `check` should release any locks it acquires
+/// before returning. Objects abandoned by threads that have exited are handed back to the
+/// caller to regenerate, so this does not wait on threads that will never finish.
+///
+/// Returns an error if the generating thread is itself waiting on the current thread.
+fn wait_for_generation<T>(
+    signal: &GenerationSignal,
+    mut check: impl FnMut() -> std::result::Result<T, ThreadId>,
+) -> Result<T> {
+    loop {
+        let epoch = signal.epoch();
+        match check() {
+            Ok(value) => return Ok(value),
+            Err(loader) => signal.wait(epoch, loader)?,
+        }
+    }
+}
+
+/// Returns the instances of a module sorted by name.
+///
+/// The sort is stable, so instances with the same name remain in insertion order.
+fn sorted_instances(module: &Module) -> Vec<&SchematicInstance> {
+    let mut instances = module.instances().collect::<Vec<_>>();
+    instances.sort_by(|a, b| a.name().cmp(b.name()));
+    instances
+}
+
 impl FlattenTop {
     #[inline]
     #[allow(unused)]
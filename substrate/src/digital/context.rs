@@ -1,5 +1,6 @@
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::thread::ThreadId;
 
 use arcstr::ArcStr;
 
@@ -117,15 +118,23 @@ impl DigitalData {
     }
 
     /// Returns the generated module if it already exists or generates a new ID.
+    ///
+    /// Returns the ID of the generating thread as an error if the module
+    /// is being generated by another thread.
     #[allow(dead_code)]
     pub(crate) fn get_generated_module<T>(
         &mut self,
         params: &T::Params,
-    ) -> GeneratedCheck<Arc<DigitalModule>, DigitalModuleKey>
+    ) -> Result<GeneratedCheck<Arc<DigitalModule>, DigitalModuleKey>, ThreadId>
     where
         T: DigitalComponent,
     {
-        self.modules.get(ParamKey::from_params::<T>(params))
+        self.modules.try_get(ParamKey::from_params::<T>(params))
+    }
+
+    /// Forgets about a module that failed to generate.
+    pub(crate) fn abort_module(&mut self, id: DigitalModuleKey) {
+        self.modules.abort(id);
     }
 
     /// Adds a module to the map based on its [`DigitalModuleKey`].
@@ -15,6 +15,8 @@ use crate::pdk::stdcell::error::StdCellError;
 use crate::schematic::circuit::PortError as SchematicPortError;
 use crate::schematic::netlist::interface::NetlistError;
 use crate::verification::simulation::bits::BitConvError;
+use crate::verification::simulation::error::SimulatorError;
+use crate::verification::simulation::AnalysisType;
 use crate::verification::timing::TimingReport;
 
 pub type Result<T> = std::result::Result<T, SubstrateError>;
@@ -201,12 +203,21 @@ pub enum ErrorSource {
     #[error("no timing config specified")]
     TimingConfigNotSpecified,
 
+    #[error("no ground net specified")]
+    GroundNetNotSpecified,
+
     #[error("invalid pdk")]
     InvalidPdk,
 
     #[error("invalid arguments: {0}")]
     InvalidArgs(String),
 
+    #[error("cyclic dependency between components generated on different threads")]
+    GenerationCycle,
+
+    #[error("invalid measurement `{name}`: {reason}")]
+    InvalidMeasurement { name: String, reason: String },
+
     #[error("already exists: {0}")]
     AlreadyExists(ArcStr),
 
@@ -240,6 +251,25 @@ pub enum ErrorSource {
     #[error("error converting signal to logic level: {0}")]
     BitConv(#[from] BitConvError),
 
+    #[error("no analysis at index {index}; the simulation has {len} analyses")]
+    AnalysisIndexOutOfBounds { index: usize, len: usize },
+
+    #[error("expected analysis {index} to be of type {expected:?}, but found {found:?}")]
+    AnalysisTypeMismatch {
+        index: usize,
+        expected: AnalysisType,
+        found: AnalysisType,
+    },
+
+    #[error("no {0:?} analysis found")]
+    AnalysisNotFound(AnalysisType),
+
+    #[error("simulation failed: {0}")]
+    Simulator(#[from] SimulatorError),
+
+    #[error("testbench failed: {0}")]
+    TestbenchFailed(String),
+
     #[error("timing constraints not satisfied; see report for more details")]
     TimingFailed(TimingReport),
 
@@ -4,7 +4,9 @@ use std::any::TypeId;
 use std::collections::hash_map::Entry;
 use std::collections::HashMap;
 use std::hash::Hash;
-use std::sync::Arc;
+use std::sync::{Arc, Condvar, Mutex, PoisonError, Weak};
+use std::thread::{self, ThreadId};
+use std::time::Duration;
 
 use slotmap::{Key, SlotMap};
 
@@ -46,8 +48,51 @@ pub(crate) enum ObjectStatus<V> {
     /// The item of type `V` exists.
     Exists(Arc<V>),
     /// The item is currently loading (i.e. it has been assigned an ID but is still pending a
-    /// value).
-    Loading,
+    /// value) on the given thread.
+    Loading(Loader),
+}
+
+impl<V> ObjectStatus<V> {
+    /// Marks an object as being loaded by the current thread.
+    #[inline]
+    fn loading() -> Self {
+        Self::Loading(Loader::current())
+    }
+}
+
+thread_local! {
+    /// A token that is dropped when the current thread exits.
+    static THREAD_ALIVE: Arc<()> = Arc::new(());
+}
+
+/// The thread loading an object.
+#[derive(Debug)]
+pub(crate) struct Loader {
+    thread: ThreadId,
+    /// A weak reference to the thread's [`THREAD_ALIVE`] token.
+    alive: Weak<()>,
+}
+
+impl Loader {
+    /// Returns the [`Loader`] representing the current thread.
+    fn current() -> Self {
+        Self {
+            thread: thread::current().id(),
+            alive: THREAD_ALIVE.with(Arc::downgrade),
+        }
+    }
+
+    /// Returns `true` if the loading thread is the current thread.
+    #[inline]
+    fn is_current(&self) -> bool {
+        self.thread == thread::current().id()
+    }
+
+    /// Returns `true` if the loading thread has not yet exited.
+    #[inline]
+    fn is_alive(&self) -> bool {
+        self.alive.strong_count() > 0
+    }
 }
 
 impl<K, S, V> GenerationMap<K, S, V>
@@ -73,7 +118,7 @@ where
         match self.target_map.entry(key) {
             Entry::Occupied(o) => GeneratedCheck::Exists(*o.get()),
             Entry::Vacant(v) => {
-                let mkey = self.objects.insert(ObjectStatus::Loading);
+                let mkey = self.objects.insert(ObjectStatus::loading());
                 v.insert(mkey);
                 GeneratedCheck::MustGenerate(mkey)
             }
@@ -87,7 +132,7 @@ where
     /// See unit tests for examples.
     pub(crate) fn get_by_id(&self, id: S) -> SubResult<&Arc<V>> {
         match self.objects[id] {
-            ObjectStatus::Loading => Err(ErrorSource::Internal(
+            ObjectStatus::Loading(_) => Err(ErrorSource::Internal(
                 "attempted to view object before it has been loaded".to_string(),
             )
             .into()),
@@ -115,6 +160,39 @@ where
         }
     }
 
+    /// Gets a object generated with the given parameters.
+    ///
+    /// Behaves like [`GenerationMap::get`], except that it returns the ID of the generating
+    /// thread as an error rather than panicking if the object is currently being generated
+    /// by another thread. In that case, callers should release any locks and wait on a
+    /// [`GenerationSignal`] before trying again.
+    ///
+    /// If the thread generating the object has exited without finishing it,
+    /// the object is aborted and the caller is asked to generate it instead.
+    pub(crate) fn try_get(&mut self, key: K) -> Result<GeneratedCheck<Arc<V>, S>, ThreadId> {
+        if let Some(&id) = self.target_map.get(&key) {
+            if let ObjectStatus::Loading(ref loader) = self.objects[id] {
+                if !loader.is_alive() {
+                    self.abort(id);
+                } else if !loader.is_current() {
+                    return Err(loader.thread);
+                }
+            }
+        }
+        Ok(self.get(key))
+    }
+
+    /// Forgets about an object that failed to generate.
+    ///
+    /// Subsequent requests for the same object will be asked to generate it again,
+    /// rather than waiting on an object that will never be loaded.
+    pub(crate) fn abort(&mut self, id: S) {
+        if let Some(ObjectStatus::Loading(_)) = self.objects.get(id) {
+            self.objects.remove(id);
+            self.target_map.retain(|_, v| *v != id);
+        }
+    }
+
     /// Generates a new identifier and marks the corresponding object with
     /// [`ObjectStatus::Loading`].
     ///
@@ -125,7 +203,7 @@ where
     ///
     /// See unit tests for examples.
     pub(crate) fn gen_id(&mut self) -> S {
-        self.objects.insert(ObjectStatus::Loading)
+        self.objects.insert(ObjectStatus::loading())
     }
 
     /// Sets the value for an object with ID `id` after it has been loaded.
@@ -181,11 +259,87 @@ where
     pub(crate) fn values(&self) -> impl Iterator<Item = &Arc<V>> {
         self.objects.values().filter_map(|v| match v {
             ObjectStatus::Exists(v) => Some(v),
-            ObjectStatus::Loading => None,
+            ObjectStatus::Loading(_) => None,
         })
     }
 }
 
+/// The longest time that [`GenerationSignal::wait`] blocks before returning,
+/// so that objects abandoned by exited threads are eventually noticed.
+const WAIT_TIMEOUT: Duration = Duration::from_millis(100);
+
+/// Wakes threads waiting on objects that are being generated by other threads.
+///
+/// Also tracks which thread each waiting thread is waiting on,
+/// so that cycles of threads waiting on one another are reported as errors.
+#[derive(Debug, Default)]
+pub(crate) struct GenerationSignal {
+    state: Mutex<SignalState>,
+    cond: Condvar,
+}
+
+#[derive(Debug, Default)]
+struct SignalState {
+    /// Incremented each time an object finishes loading or is aborted.
+    epoch: u64,
+    /// Maps each waiting thread to the thread generating the object it is waiting on.
+    waiting: HashMap<ThreadId, ThreadId>,
+}
+
+impl GenerationSignal {
+    /// Creates a new [`GenerationSignal`].
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    fn state(&self) -> std::sync::MutexGuard<'_, SignalState> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Returns the current epoch, to be passed to [`GenerationSignal::wait`].
+    ///
+    /// Must be called before checking whether the desired object is loaded,
+    /// so that notifications sent after the check are not missed.
+    pub(crate) fn epoch(&self) -> u64 {
+        self.state().epoch
+    }
+
+    /// Wakes all waiting threads after an object finishes loading or is aborted.
+    pub(crate) fn notify(&self) {
+        self.state().epoch += 1;
+        self.cond.notify_all();
+    }
+
+    /// Blocks the current thread while it waits on an object being generated by `loader`.
+    ///
+    /// Returns once [`GenerationSignal::notify`] has been called since `epoch` was observed,
+    /// or after a short timeout, after which the caller should check the object again.
+    ///
+    /// Returns an error if `loader` is itself waiting, directly or through other threads,
+    /// on the current thread, since neither thread would ever finish.
+    pub(crate) fn wait(&self, epoch: u64, loader: ThreadId) -> SubResult<()> {
+        let current = thread::current().id();
+        let mut state = self.state();
+
+        let mut next = Some(loader);
+        while let Some(thread) = next {
+            if thread == current {
+                return Err(ErrorSource::GenerationCycle.into());
+            }
+            next = state.waiting.get(&thread).copied();
+        }
+
+        state.waiting.insert(current, loader);
+        let (mut state, _) = self
+            .cond
+            .wait_timeout_while(state, WAIT_TIMEOUT, |state| state.epoch == epoch)
+            .unwrap_or_else(PoisonError::into_inner);
+        state.waiting.remove(&current);
+
+        Ok(())
+    }
+}
+
 /// Key for uniquely identifying generated [`Component`]s.
 #[derive(Clone, Eq, PartialEq, Hash, Debug)]
 pub(crate) struct ParamKey {
@@ -290,6 +444,94 @@ mod tests {
         assert_ne!(id, new_id);
     }
 
+    #[test]
+    fn test_generation_map_try_get() {
+        let mut gen_map = GenerationMap::new();
+
+        let id: TestKey = match gen_map.try_get("key1".to_string()) {
+            Ok(GeneratedCheck::MustGenerate(id)) => id,
+            _ => panic!("Corresponding object should not exist already"),
+        };
+
+        // Another thread must wait for the object to be generated by this thread.
+        let current = thread::current().id();
+        std::thread::scope(|s| {
+            s.spawn(|| assert_eq!(gen_map.try_get("key1".to_string()).err(), Some(current)));
+        });
+
+        gen_map.set(id, "name", "value".to_string());
+
+        std::thread::scope(|s| {
+            s.spawn(|| match gen_map.try_get("key1".to_string()) {
+                Ok(GeneratedCheck::Exists(v)) => assert_eq!(v, Arc::from("value".to_string())),
+                _ => panic!("Corresponding object should exist already"),
+            });
+        });
+    }
+
+    #[test]
+    fn test_generation_map_try_get_exited_thread() {
+        let mut gen_map: GenerationMap<String, _, String> = GenerationMap::new();
+
+        // A thread that exits without finishing its object, such as after a panic.
+        let id: TestKey = std::thread::scope(|s| {
+            s.spawn(|| match gen_map.try_get("key1".to_string()) {
+                Ok(GeneratedCheck::MustGenerate(id)) => id,
+                _ => panic!("Corresponding object should not exist already"),
+            })
+            .join()
+            .unwrap()
+        });
+
+        match gen_map.try_get("key1".to_string()) {
+            Ok(GeneratedCheck::MustGenerate(new_id)) => assert_ne!(id, new_id),
+            _ => panic!("Object abandoned by an exited thread should be regenerated"),
+        }
+    }
+
+    #[test]
+    fn test_generation_signal_wait() {
+        let signal = GenerationSignal::new();
+        let current = thread::current().id();
+        let other = {
+            let handle = thread::spawn(|| {});
+            let id = handle.thread().id();
+            handle.join().unwrap();
+            id
+        };
+
+        // Waiting returns once another thread notifies the signal.
+        let epoch = signal.epoch();
+        std::thread::scope(|s| {
+            s.spawn(|| signal.notify());
+            signal.wait(epoch, other).unwrap();
+        });
+        assert!(signal.state().waiting.is_empty());
+
+        // Waiting on a thread that is waiting on the current thread is an error.
+        signal.state().waiting.insert(other, current);
+        let err = signal.wait(signal.epoch(), other).unwrap_err();
+        assert!(matches!(err.source(), ErrorSource::GenerationCycle));
+    }
+
+    #[test]
+    fn test_generation_map_abort() {
+        let mut gen_map: GenerationMap<String, _, String> = GenerationMap::new();
+
+        let id: TestKey = match gen_map.get("key1".to_string()) {
+            GeneratedCheck::Exists(_) => panic!("Corresponding object should not exist already"),
+            GeneratedCheck::MustGenerate(id) => id,
+        };
+
+        gen_map.abort(id);
+
+        let new_id = match gen_map.get("key1".to_string()) {
+            GeneratedCheck::Exists(_) => panic!("Aborted object should not exist"),
+            GeneratedCheck::MustGenerate(id) => id,
+        };
+        assert_ne!(id, new_id);
+    }
+
     #[test]
     fn test_generation_map_values() {
         let mut gen_map: GenerationMap<String, _, _> = GenerationMap::new();
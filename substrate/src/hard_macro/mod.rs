@@ -1,30 +1,45 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
 
 use serde::{Deserialize, Serialize};
+use subspice::parser::PinDirection;
 
 use crate::deps::arcstr::ArcStr;
-use crate::error::Result;
-use crate::fmt::signal::BusFmt;
+use crate::error::{ErrorSource, Result};
+use crate::fmt::signal::{parse_bus, BusFmt, ParsedBus};
 use crate::schematic::circuit::Direction;
 
 #[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
 pub struct Config {
     pub ports: HashMap<ArcStr, Port>,
+    /// The order in which ports are declared on the imported module.
+    ///
+    /// If specified, must list each port in [`Config::ports`] exactly once.
+    /// If empty, ports are ordered by their first appearance in the SPICE subcircuit.
+    #[serde(default)]
+    pub port_order: Vec<ArcStr>,
     #[serde(default)]
     pub bus_format: BusFmt,
     #[serde(default)]
     pub spice_subckt_name: Option<ArcStr>,
     #[serde(default)]
     pub spice_path: Option<PathBuf>,
+    /// Whether to infer the directions of ports that do not specify one
+    /// from the SPICE subcircuit. See [`infer_directions`].
+    #[serde(default)]
+    pub infer_directions: bool,
 }
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Ord, PartialOrd, Serialize, Deserialize)]
 pub struct Port {
     #[serde(default = "default_port_width")]
     pub width: usize,
+    /// The direction of the port.
+    ///
+    /// Defaults to the inferred direction if [`Config::infer_directions`] is set,
+    /// or [`Direction::default`] otherwise.
     #[serde(default)]
-    pub direction: Direction,
+    pub direction: Option<Direction>,
 }
 
 const fn default_port_width() -> usize {
@@ -33,10 +48,51 @@ const fn default_port_width() -> usize {
 
 impl Config {
     pub fn from_toml(input: &str) -> Result<Self> {
-        let value = toml::from_str(input)?;
+        let value: Self = toml::from_str(input)?;
+        value.validate()?;
         Ok(value)
     }
 
+    /// Checks that [`Config::port_order`], if specified, lists each declared port exactly once.
+    pub fn validate(&self) -> Result<()> {
+        if self.port_order.is_empty() {
+            return Ok(());
+        }
+
+        let mut seen = HashSet::new();
+        for name in self.port_order.iter() {
+            if !self.ports.contains_key(name) {
+                return Err(ErrorSource::InvalidArgs(format!(
+                    "port `{name}` in port order is not a declared port"
+                ))
+                .into());
+            }
+            if !seen.insert(name) {
+                return Err(ErrorSource::InvalidArgs(format!(
+                    "port `{name}` appears more than once in port order"
+                ))
+                .into());
+            }
+        }
+
+        let mut missing = self
+            .ports
+            .keys()
+            .filter(|name| !seen.contains(name))
+            .map(|name| name.as_str())
+            .collect::<Vec<_>>();
+        if !missing.is_empty() {
+            missing.sort();
+            return Err(ErrorSource::InvalidArgs(format!(
+                "port order is missing declared ports: {}",
+                missing.join(", ")
+            ))
+            .into());
+        }
+
+        Ok(())
+    }
+
     pub fn from_toml_file(path: impl AsRef<Path>) -> Result<Self> {
         let path = path.as_ref();
         let input = std::fs::read_to_string(path)?;
@@ -55,3 +111,39 @@ impl Config {
         println!("{:?}", self.spice_path);
     }
 }
+
+/// Infers the directions of the ports of the subcircuit named `subckt`
+/// in the SPICE file at `path`.
+///
+/// Directions are read from `.PININFO` comments in the subcircuit body
+/// (eg. `*.PININFO a:I y:O vdd:P`); power and ground pins are treated as [`Direction::InOut`].
+/// The bits of a bus are combined using [`Direction::combine`].
+///
+/// The subcircuit's devices are not analyzed, so ports that are not annotated
+/// are omitted from the returned map, and callers should fall back to a default.
+pub fn infer_directions(
+    path: impl AsRef<Path>,
+    subckt: &str,
+    bus_format: BusFmt,
+) -> Result<HashMap<ArcStr, Direction>> {
+    let spice = crate::io::read_to_string(path)?;
+    let parsed = subspice::parse(&spice)?;
+
+    let mut directions: HashMap<ArcStr, Direction> = HashMap::new();
+    for (pin, direction) in parsed.pin_directions(subckt) {
+        let name = match parse_bus(pin, bus_format) {
+            Ok(ParsedBus { name, .. }) => name,
+            Err(_) => pin,
+        };
+        let direction = match direction {
+            PinDirection::Input => Direction::Input,
+            PinDirection::Output => Direction::Output,
+            PinDirection::InOut => Direction::InOut,
+        };
+        directions
+            .entry(name.into())
+            .and_modify(|d| *d = d.combine(direction))
+            .or_insert(direction);
+    }
+    Ok(directions)
+}
@@ -1,7 +1,7 @@
 //! Types related to the creation and instantiation of [`Cell`]s.
 
 use std::collections::hash_map::Entry;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fmt;
 use std::hash::Hash;
 use std::sync::Arc;
@@ -266,6 +266,67 @@ where
     }
 }
 
+/// Returns a [`PortMapFn`] that renames ports according to `map`.
+///
+/// Ports whose names are not keys of `map` are exposed unchanged.
+/// Bus indices are preserved.
+pub fn rename_map<M>(map: HashMap<ArcStr, ArcStr>) -> impl PortMapFn<M> {
+    move |port: CellPort, _: M| match map.get(&port.name()) {
+        Some(name) => Some(port.named(name.clone())),
+        None => Some(port),
+    }
+}
+
+/// Returns a [`PortMapFn`] that exposes only the ports named in `names`.
+///
+/// All bus indices of the named ports are exposed.
+/// Use [`compose`] to combine this with a renaming [`PortMapFn`].
+pub fn only<M>(names: &[&str]) -> impl PortMapFn<M> {
+    let names = names
+        .iter()
+        .map(|&name| ArcStr::from(name))
+        .collect::<HashSet<_>>();
+    move |port: CellPort, _: M| names.contains(&port.name()).then_some(port)
+}
+
+/// Returns a [`PortMapFn`] that exposes all ports except those named in `names`.
+///
+/// Use [`compose`] to combine this with a renaming [`PortMapFn`].
+pub fn except<M>(names: &[&str]) -> impl PortMapFn<M> {
+    let names = names
+        .iter()
+        .map(|&name| ArcStr::from(name))
+        .collect::<HashSet<_>>();
+    move |port: CellPort, _: M| (!names.contains(&port.name())).then_some(port)
+}
+
+/// Returns a [`PortMapFn`] that applies `first`, then applies `second`
+/// to the ports that `first` exposes.
+///
+/// For example, `compose(only(&["clk"]), prefix_with_instance_name())` exposes
+/// only the `clk` port of each instance, prefixed with the instance name.
+pub fn compose<M: Clone>(
+    mut first: impl PortMapFn<M>,
+    mut second: impl PortMapFn<M>,
+) -> impl PortMapFn<M> {
+    move |port: CellPort, metadata: M| {
+        let port = first.map(port, metadata.clone())?;
+        second.map(port, metadata)
+    }
+}
+
+/// Returns a [`PortMapFn`] that prefixes the name of each port with the name of
+/// the [`Instance`] it belongs to, separated by an underscore.
+///
+/// For example, port `data[3]` of instance `a` is exposed as `a_data[3]`.
+/// Instances must have distinct names (see [`Instance::set_name`]) to avoid conflicts.
+pub fn prefix_with_instance_name() -> impl PortMapFn<Instance> {
+    |port: CellPort, inst: Instance| {
+        let name = arcstr::format!("{}_{}", inst.name(), port.name());
+        Some(port.named(name))
+    }
+}
+
 #[derive(Debug, Default, Clone)]
 pub struct PortMap {
     ports: HashMap<ArcStr, BusPort>,
@@ -982,6 +1043,23 @@ impl LayerBoundBox for Cell {
     }
 }
 
+impl Cell {
+    /// Returns the bounding box of all geometry in the cell and its instances,
+    /// ignoring geometry on any of the given layers.
+    pub fn bbox_excluding(&self, layers: &[LayerKey]) -> Bbox {
+        let mut bbox = Bbox::empty();
+        for elem in &self.elems {
+            if !layers.contains(&elem.layer.layer()) {
+                bbox = elem.inner.union(bbox);
+            }
+        }
+        for inst in &self.insts {
+            bbox = inst.bbox_excluding(layers).union(bbox);
+        }
+        bbox
+    }
+}
+
 impl CellPort {
     /// Create a new [`CellPort`] with the given `id`
     pub fn new(id: impl Into<PortId>) -> Self {
@@ -1112,7 +1190,7 @@ impl CellPort {
                 while j < rects.len() {
                     let rect1 = rects[i];
                     let rect2 = rects[j];
-                    for dir in [Dir::Horiz, Dir::Vert] {
+                    for dir in Dir::all() {
                         if rect1.span(dir) == rect2.span(dir)
                             && rect1.span(!dir).intersects(&rect2.span(!dir))
                         {
@@ -1394,6 +1472,12 @@ impl Instance {
         &self.name
     }
 
+    /// Sets the name of the instance.
+    #[inline]
+    pub fn set_name(&mut self, name: impl Into<ArcStr>) {
+        self.name = name.into();
+    }
+
     /// Returns a pointer to the instance's reference cell.
     #[inline]
     pub fn cell(&self) -> &Arc<Cell> {
@@ -1530,6 +1614,20 @@ impl BoundBox for Instance {
     }
 }
 
+impl Instance {
+    /// Returns the bounding box of the instance, ignoring geometry on any of the given layers.
+    ///
+    /// See [`Cell::bbox_excluding`].
+    pub fn bbox_excluding(&self, layers: &[LayerKey]) -> Bbox {
+        let bbox = self.cell.bbox_excluding(layers);
+        if bbox.is_empty() {
+            return bbox;
+        }
+
+        bbox.into_rect().transform(self.transformation()).bbox()
+    }
+}
+
 impl LayerBoundBox for Instance {
     fn layer_bbox(&self, layer: LayerKey) -> Bbox {
         let bbox = self.cell.layer_bbox(layer);
@@ -1,6 +1,7 @@
 //! Context methods for instantiating component layouts.
 
 use std::sync::Arc;
+use std::thread::ThreadId;
 
 use subgeom::bbox::{Bbox, BoundBox};
 use subgeom::trim::Trim;
@@ -69,15 +70,23 @@ impl LayoutData {
     }
 
     /// Returns the generated cell if it already exists or generates a new ID.
+    ///
+    /// Returns the ID of the generating thread as an error if the cell
+    /// is being generated by another thread.
     #[allow(dead_code)]
     pub(crate) fn get_generated_cell<T>(
         &mut self,
         params: &T::Params,
-    ) -> GeneratedCheck<Arc<Cell>, CellKey>
+    ) -> Result<GeneratedCheck<Arc<Cell>, CellKey>, ThreadId>
     where
         T: Component,
     {
-        self.cells.get(ParamKey::from_params::<T>(params))
+        self.cells.try_get(ParamKey::from_params::<T>(params))
+    }
+
+    /// Forgets about a cell that failed to generate.
+    pub(crate) fn abort_cell(&mut self, id: CellKey) {
+        self.cells.abort(id);
     }
 
     /// Adds a cell to the map based on its [`CellKey`].
@@ -12,7 +12,9 @@ use subgeom::transform::TranslateOwned;
 use subgeom::{Dims, Dir, ExpandMode, Rect};
 
 use super::ViaExpansion;
+use crate::error::Result;
 use crate::layout::cell::Element;
+use crate::layout::error::LayoutError;
 use crate::layout::group::elements::ElementGroup;
 use crate::layout::layers::{LayerBoundBox, LayerKey, LayerSpec};
 use crate::layout::placement::align::AlignRect;
@@ -152,6 +154,20 @@ pub struct Pair<T> {
     top: T,
 }
 
+impl<T> Pair<T> {
+    /// The value associated with the bottom metal.
+    #[inline]
+    pub fn bot(&self) -> &T {
+        &self.bot
+    }
+
+    /// The value associated with the top metal.
+    #[inline]
+    pub fn top(&self) -> &T {
+        &self.top
+    }
+}
+
 impl<T> From<MetalInfo<T>> for Pair<T>
 where
     T: Copy,
@@ -240,6 +256,18 @@ impl ViaArrayDims {
         builder.build()
     }
 
+    /// Returns the minimum dimensions of the bottom and top layer geometry
+    /// required to fit a single via without expansion.
+    ///
+    /// Assumes the extensions are not transposed.
+    pub fn min_metal_dims(&self) -> Pair<Dims> {
+        let via = self.array_dims(1, 1);
+        Pair {
+            bot: via + self.extension[MetalZ::Bot] * 2,
+            top: via + self.extension[MetalZ::Top] * 2,
+        }
+    }
+
     /// Calculates the size of the via layer bounding box.
     ///
     /// Does not consider extension on the top/bottom metal layers.
@@ -368,10 +396,72 @@ impl MaxViaArray {
     }
 
     /// Draws the via array, returning an [`ElementGroup`].
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the expansion mode is [`ViaExpansion::None`]
+    /// and no via fits within the given geometry.
+    /// Use [`MaxViaArray::try_draw`] to get an error instead.
     pub fn draw(&self, bot: Rect, top: Rect) -> ElementGroup {
         self._draw(MetalInfo::new(bot, top))
     }
 
+    /// Draws the via array, returning an [`ElementGroup`].
+    ///
+    /// If the expansion mode is [`ViaExpansion::None`] and no via fits within
+    /// the given geometry, returns an error reporting the minimum
+    /// bottom and top layer dimensions required to fit a single via.
+    pub fn try_draw(&self, bot: Rect, top: Rect) -> Result<ElementGroup> {
+        let metals = MetalInfo::new(bot, top);
+        if self.expansion == ViaExpansion::None && !self.fits(metals) {
+            let min = self.dims.min_metal_dims();
+            let (min_bot, min_top) = (min.bot(), min.top());
+            return Err(LayoutError::msg(format!(
+                "via does not fit within the given geometry (bot {}x{}, top {}x{}) \
+                without expansion; the bottom layer must be at least {}x{} \
+                and the top layer at least {}x{}, and they must overlap by at least {}x{}",
+                bot.width(),
+                bot.height(),
+                top.width(),
+                top.height(),
+                min_bot.w(),
+                min_bot.h(),
+                min_top.w(),
+                min_top.h(),
+                self.dims.via_size.w(),
+                self.dims.via_size.h(),
+            ))
+            .into());
+        }
+        Ok(self._draw(metals))
+    }
+
+    /// Returns `true` if at least one via fits within the given
+    /// geometry without expansion, considering all permissible
+    /// extension orientations.
+    fn fits(&self, metals: MetalInfo<Rect>) -> bool {
+        for tt in [false, true] {
+            for tb in [false, true] {
+                let mut dims = self.dims;
+                if (dims.fixed[MetalZ::Top] && tt) || (dims.fixed[MetalZ::Bot] && tb) {
+                    continue;
+                }
+                if tt {
+                    dims = dims.transpose_extension(MetalZ::Top);
+                }
+                if tb {
+                    dims = dims.transpose_extension(MetalZ::Bot);
+                }
+
+                let (nx, ny) = dims.max_ns(metals);
+                if nx >= 1 && ny >= 1 {
+                    return true;
+                }
+            }
+        }
+        false
+    }
+
     /// A helper function for [`MaxViaArray::draw`].
     fn _draw(&self, metals: MetalInfo<Rect>) -> ElementGroup {
         let mut max = 0;
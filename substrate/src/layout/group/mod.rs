@@ -2,13 +2,13 @@
 //!
 //! For cases when you want a collection of objects,
 //! but you don't want to create a separate [`Component`](crate::component::Component).
-use subgeom::bbox::{Bbox, BoundBox};
+use subgeom::bbox::{Bbox, BboxBuilder, BoundBox};
 use subgeom::orientation::Orientation;
 use subgeom::transform::{Transform, Transformation, Translate};
-use subgeom::{Point, Rect, Shape};
+use subgeom::{Dims, Point, Rect, Shape};
 
 use super::cell::{
-    BusPort, CellPort, Instance, PortConflictStrategy, PortError, PortId, PortMap, PortMapFn,
+    BusPort, Cell, CellPort, Instance, PortConflictStrategy, PortError, PortId, PortMap, PortMapFn,
     TextElement, TransformedPort,
 };
 use super::layers::{LayerBoundBox, LayerKey, LayerPurpose, UserLayer};
@@ -182,6 +182,33 @@ impl Group {
         Ok(())
     }
 
+    /// Adds an `nx` by `ny` array of copies of `inst` to this group.
+    ///
+    /// The copy in column `i` and row `j` is translated by `(i * pitch.w(), j * pitch.h())`
+    /// relative to `inst`, and is named `{name}_{i}_{j}`, where `{name}` is the name of `inst`.
+    /// Each port `{port}` of the copy is exposed as `{port}_{i}_{j}`, preserving bus indices.
+    pub fn add_array(
+        &mut self,
+        inst: Instance,
+        pitch: Dims,
+        nx: usize,
+        ny: usize,
+    ) -> Result<(), PortError> {
+        for j in 0..ny {
+            for i in 0..nx {
+                let mut copy = inst.clone();
+                copy.set_name(arcstr::format!("{}_{}_{}", inst.name(), i, j));
+                copy.translate(Point::new(i as i64 * pitch.w(), j as i64 * pitch.h()));
+                for port in copy.ports() {
+                    let name = arcstr::format!("{}_{}_{}", port.name(), i, j);
+                    self.add_port(port.named(name))?;
+                }
+                self.add_instance(copy);
+            }
+        }
+        Ok(())
+    }
+
     #[inline]
     pub fn port_map(&self) -> &PortMap {
         &self.ports
@@ -309,12 +336,31 @@ impl Group {
 
 impl BoundBox for Group {
     fn bbox(&self) -> Bbox {
+        let mut bbox = BboxBuilder::new();
+        for elem in self.elements() {
+            bbox.add(&elem.inner);
+        }
+        for inst in self.instances() {
+            bbox.add(inst);
+        }
+        bbox.build()
+    }
+}
+
+impl Group {
+    /// Returns the bounding box of the group, ignoring geometry on any of the given layers.
+    ///
+    /// For example, this can be used to find the extent of a group's drawn geometry
+    /// while ignoring its outline layer.
+    pub fn bbox_excluding(&self, layers: &[LayerKey]) -> Bbox {
         let mut bbox = Bbox::empty();
         for elem in self.elements() {
-            bbox = elem.inner.union(bbox);
+            if !layers.contains(&elem.layer.layer()) {
+                bbox = elem.inner.union(bbox);
+            }
         }
         for inst in self.instances() {
-            bbox = inst.bbox().union(bbox);
+            bbox = inst.bbox_excluding(layers).union(bbox);
         }
         bbox
     }
@@ -390,6 +436,23 @@ impl From<Instance> for Group {
     }
 }
 
+impl From<&Cell> for Group {
+    /// Copies the elements, instances, annotations, and ports of `value` into a new [`Group`].
+    ///
+    /// Blockages are not copied, since groups cannot contain them.
+    fn from(value: &Cell) -> Self {
+        let mut group = Self {
+            elems: value.elems().cloned().collect(),
+            insts: value.insts().cloned().collect(),
+            annotations: value.annotations().cloned().collect(),
+            ..Default::default()
+        };
+        // Should be able to unwrap since the cell's ports are already unique.
+        group.add_ports(value.ports().cloned()).unwrap();
+        group
+    }
+}
+
 impl From<Element> for Group {
     fn from(value: Element) -> Self {
         Self {
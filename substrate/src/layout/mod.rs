@@ -24,6 +24,15 @@ pub enum LayoutFormat {
     Gds,
 }
 
+impl LayoutFormat {
+    /// Returns the conventional file extension for layouts in this format, without a leading dot.
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Gds => "gds",
+        }
+    }
+}
+
 /// A trait implemented by objects that can be drawn
 /// inside a layout cell's context.
 #[enum_dispatch]
@@ -1,7 +1,6 @@
 use std::collections::HashMap;
 
 use itertools::Itertools;
-use subgeom::bbox::BoundBox;
 use subgeom::{Dir, Rect, Sign};
 
 use self::abs::{GreedyAbstractRouter, Net};
@@ -190,8 +189,8 @@ impl GreedyRouter {
         net: Net,
     ) -> crate::error::Result<()> {
         // src and dst geometry must be contained within the routing area.
-        assert!(self.area.bbox().intersection(src.bbox()).into_rect() == src);
-        assert!(self.area.bbox().intersection(dst.bbox()).into_rect() == dst);
+        assert_eq!(self.area.intersection_inclusive(src), Some(src));
+        assert_eq!(self.area.intersection_inclusive(dst), Some(dst));
         assert!(self.key_to_index.contains_key(&src_layer));
         assert!(self.key_to_index.contains_key(&dst_layer));
 
@@ -1,6 +1,5 @@
 use std::collections::HashMap;
 
-use subgeom::bbox::BoundBox;
 use subgeom::Rect;
 
 use super::GreedyRouter;
@@ -117,13 +116,13 @@ impl RoutedStraps {
                     if let Some(t) = self.targets.get_mut(target_layer) {
                         for t in t.iter_mut() {
                             if index(t.net) == segment.track_id % 2 {
-                                let intersection = t.rect.intersection(segment.rect.bbox());
-                                if !intersection.is_empty()
-                                    && (intersection.into_rect().length(track_info.dir)
+                                let intersection = t.rect.intersection_inclusive(segment.rect);
+                                if intersection.is_some_and(|intersection| {
+                                    intersection.length(track_info.dir)
                                         == t.rect.length(track_info.dir)
-                                        && intersection.into_rect().length(!track_info.dir)
-                                            == track_info.tracks.line)
-                                {
+                                        && intersection.length(!track_info.dir)
+                                            == track_info.tracks.line
+                                }) {
                                     let viap = ViaParams::builder()
                                         .geometry(t.rect, segment.rect)
                                         .layers(*bot, *top)
@@ -150,26 +149,27 @@ impl RoutedStraps {
             let mut via: Option<Instance> = None;
             for (i, t) in top_segments.iter().copied().enumerate() {
                 for (j, b) in bot_segments.iter().copied().enumerate() {
-                    let intersection = t.rect.intersection(b.rect.bbox());
-                    if t.track_id % 2 == b.track_id % 2 && !intersection.is_empty() {
-                        if let Some(ref via) = via {
-                            let mut via = via.clone();
-                            via.place_center(
-                                intersection.center().snap_to_grid(ctx.pdk().layout_grid()),
-                            );
-                            ctx.draw(via)?;
-                        } else {
-                            let viap = ViaParams::builder()
-                                .geometry(b.rect, t.rect)
-                                .layers(bot, top)
-                                .build();
-                            let inner = ctx.instantiate::<Via>(&viap)?;
-                            via = Some(inner.clone());
-                            ctx.draw(inner)?;
-                        }
-                        segment_map.get_mut(&top).unwrap()[i].hit = true;
-                        segment_map.get_mut(&bot).unwrap()[j].hit = true;
+                    let intersection = match t.rect.intersection_inclusive(b.rect) {
+                        Some(intersection) if t.track_id % 2 == b.track_id % 2 => intersection,
+                        _ => continue,
+                    };
+                    if let Some(ref via) = via {
+                        let mut via = via.clone();
+                        via.place_center(
+                            intersection.center().snap_to_grid(ctx.pdk().layout_grid()),
+                        );
+                        ctx.draw(via)?;
+                    } else {
+                        let viap = ViaParams::builder()
+                            .geometry(b.rect, t.rect)
+                            .layers(bot, top)
+                            .build();
+                        let inner = ctx.instantiate::<Via>(&viap)?;
+                        via = Some(inner.clone());
+                        ctx.draw(inner)?;
                     }
+                    segment_map.get_mut(&top).unwrap()[i].hit = true;
+                    segment_map.get_mut(&bot).unwrap()[j].hit = true;
                 }
             }
         }
@@ -63,7 +63,10 @@ use subgeom::{Dir, DirParseError, Rect, Span};
 use thiserror::Error;
 
 use super::cell::Element;
+use super::context::LayoutCtx;
+use super::elements::via::{Via, ViaParams};
 use super::group::elements::ElementGroup;
+use super::group::Group;
 use super::layers::selector::Selector;
 use super::layers::{LayerKey, LayerSpec};
 use crate::data::SubstrateCtx;
@@ -219,6 +222,35 @@ impl<N: Clone> LayerStraps<N> {
             .take_while(move |s| s.span.start() < x)
     }
 
+    /// Iterate over all straps on net `net` that intersect the interval `[0, until]`.
+    ///
+    /// See [`LayerStraps::straps_until`].
+    pub fn straps_for_net<'a>(
+        &'a self,
+        net: &'a N,
+        until: i64,
+    ) -> impl Iterator<Item = Strap<N>> + 'a
+    where
+        N: PartialEq,
+    {
+        self.straps_until(until).filter(move |s| &s.net == net)
+    }
+
+    /// Iterate over all straps that overlap `bounds`, clipped to lie entirely within `bounds`.
+    ///
+    /// Straps that extend past either end of `bounds` are narrowed,
+    /// and straps that only touch `bounds` at a single point are omitted.
+    pub fn straps_within(&self, bounds: Span) -> impl Iterator<Item = Strap<N>> + '_ {
+        (0..)
+            .map(|i| self.strap(i))
+            .take_while(move |s| s.span.start() < bounds.stop())
+            .filter_map(move |s| {
+                let start = std::cmp::max(s.span.start(), bounds.start());
+                let stop = std::cmp::min(s.span.stop(), bounds.stop());
+                (start < stop).then(|| Strap::new(s.net, Span::new(start, stop)))
+            })
+    }
+
     /// Draws all power straps intersecting the interval `[0, x]`, with the given span.
     ///
     /// The span is in the direction parallel to the power straps.
@@ -234,6 +266,23 @@ impl<N: Clone> LayerStraps<N> {
         }
         group
     }
+
+    /// Draws all power straps overlapping `bounds`, clipped to `bounds`, with the given span.
+    ///
+    /// The span is in the direction parallel to the power straps;
+    /// `bounds` is in the perpendicular direction. See [`LayerStraps::straps_within`].
+    pub fn draw_within(&self, bounds: Span, span: Span) -> ElementGroup {
+        let mut group = ElementGroup::new();
+        for strap in self.straps_within(bounds) {
+            let rect = Rect::span_builder()
+                .with(self.dir, span)
+                .with(!self.dir, strap.span)
+                .build();
+            let spec = LayerSpec::drawing(self.layer);
+            group.add(Element::new(spec, rect));
+        }
+        group
+    }
 }
 
 impl<N> LayerStraps<N> {
@@ -315,6 +364,7 @@ where
         ctx: &SubstrateCtx,
     ) -> Result<Self> {
         let straps = straps.get_macro(macro_name)?;
+        validate_hammer_layers(straps, ctx)?;
 
         let n = straps.len();
         // Must specify 1 or 2 layers.
@@ -389,6 +439,88 @@ where
     }
 }
 
+impl<N: Clone + PartialEq> StrapConfig<N> {
+    /// Draws vias connecting the straps on the top layer to the straps on the layer above it,
+    /// at every point within `area` where two straps on the same net cross.
+    ///
+    /// Straps are clipped to `area` before looking for crossings.
+    /// Returns an empty group if there is no layer above the top layer.
+    pub fn connect_layers(&self, ctx: &mut LayoutCtx, area: Rect) -> Result<Group> {
+        let mut group = Group::new();
+        let above = match self.above_top {
+            Some(ref above) => above,
+            None => return Ok(group),
+        };
+        let top = &self.top;
+
+        if top.dir == above.dir {
+            return Err(SubstrateError::new(PowerStrapError::ParallelStrapLayers(
+                top.index,
+                above.index,
+            )));
+        }
+
+        let top_straps = top.straps_within(area.span(!top.dir)).collect::<Vec<_>>();
+        let above_straps = above
+            .straps_within(area.span(!above.dir))
+            .collect::<Vec<_>>();
+
+        for t in top_straps.iter() {
+            let bot_rect = Rect::span_builder()
+                .with(top.dir, area.span(top.dir))
+                .with(!top.dir, t.span)
+                .build();
+            for a in above_straps.iter().filter(|a| a.net == t.net) {
+                let top_rect = Rect::span_builder()
+                    .with(above.dir, area.span(above.dir))
+                    .with(!above.dir, a.span)
+                    .build();
+                let viap = ViaParams::builder()
+                    .geometry(bot_rect, top_rect)
+                    .layers(top.layer, above.layer)
+                    .build();
+                group.add_instance(ctx.instantiate::<Via>(&viap)?);
+            }
+        }
+
+        Ok(group)
+    }
+}
+
+/// Checks that every layer referenced by the given Hammer strap configuration
+/// of a single macro exists in the PDK and is a metal layer.
+///
+/// All invalid layers are reported in a single [`PowerStrapError::InvalidHammerLayers`] error.
+fn validate_hammer_layers(straps: &[hammer::StrapInfo], ctx: &SubstrateCtx) -> Result<()> {
+    let layers = ctx.layers();
+    let mut missing = Vec::new();
+    let mut not_metal = Vec::new();
+
+    for info in straps {
+        let name = info.layer;
+        if missing.iter().chain(not_metal.iter()).any(|l| l == name) {
+            continue;
+        }
+        match layers.get(Selector::Name(name)) {
+            Ok(layer) => {
+                if layers.info(layer)?.metal_idx.is_none() {
+                    not_metal.push(name.to_string());
+                }
+            }
+            Err(_) => missing.push(name.to_string()),
+        }
+    }
+
+    if missing.is_empty() && not_metal.is_empty() {
+        Ok(())
+    } else {
+        Err(SubstrateError::new(PowerStrapError::InvalidHammerLayers {
+            missing,
+            not_metal,
+        }))
+    }
+}
+
 #[derive(Error, Debug)]
 #[non_exhaustive]
 pub enum PowerStrapError {
@@ -404,9 +536,22 @@ pub enum PowerStrapError {
     #[error("layer {0} is not marked as a metal layer in the Substrate PDK")]
     NotMetalLayer(String),
 
+    #[error(
+        "invalid layers in Hammer power strap configuration (not found in the Substrate PDK: {missing:?}; not metal layers: {not_metal:?})"
+    )]
+    InvalidHammerLayers {
+        missing: Vec<String>,
+        not_metal: Vec<String>,
+    },
+
     #[error("error parsing direction: {0}")]
     InvalidDirection(#[from] DirParseError),
 
     #[error("found the same metal index `{0}` multiple times in power strap configuration")]
     DuplicateMetalLayers(usize),
+
+    #[error(
+        "cannot connect straps on metal layers `{0}` and `{1}`, which run in the same direction"
+    )]
+    ParallelStrapLayers(usize, usize),
 }
@@ -5,6 +5,8 @@ use crate::deps::arcstr::ArcStr;
 use crate::log::Log;
 use crate::validation::{Empty, ValidatorOutput};
 
+pub mod spacing;
+
 /// Validates a layout cell.
 pub fn validate_cell(cell: &Cell) -> LayoutValidatorOutput {
     LayoutValidator { cell }.validate()
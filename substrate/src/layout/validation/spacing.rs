@@ -0,0 +1,39 @@
+//! Spacing checks between layout rectangles.
+
+use subgeom::{Dir, Rect};
+
+/// Returns the spacing between two rectangles.
+///
+/// The spacing is the larger of the gaps between the rectangles' horizontal
+/// and vertical spans, as computed by [`Span::min_distance`](subgeom::Span::min_distance).
+/// For rectangles that are offset diagonally, this is a lower bound on the
+/// Euclidean distance between their corners.
+///
+/// Rectangles that touch or overlap have a spacing of 0.
+pub fn spacing(a: Rect, b: Rect) -> i64 {
+    Dir::all()
+        .map(|dir| a.span(dir).min_distance(b.span(dir)))
+        .max()
+        .unwrap()
+}
+
+/// Returns the minimum [`spacing`] between any two of the given rectangles,
+/// along with the indices of a pair of rectangles with that spacing.
+///
+/// The returned indices are in increasing order. If several pairs have the minimum spacing,
+/// the pair that comes first in lexicographic order is returned.
+/// Returns [`None`] if there are fewer than two rectangles.
+///
+/// This is intended for sanity-checking generated geometry before running DRC.
+pub fn min_spacing(rects: &[Rect]) -> Option<(i64, (usize, usize))> {
+    let mut min: Option<(i64, (usize, usize))> = None;
+    for (i, a) in rects.iter().enumerate() {
+        for (j, b) in rects.iter().enumerate().skip(i + 1) {
+            let spacing = spacing(*a, *b);
+            if min.map(|(s, _)| spacing < s).unwrap_or(true) {
+                min = Some((spacing, (i, j)));
+            }
+        }
+    }
+    min
+}
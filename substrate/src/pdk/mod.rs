@@ -2,6 +2,8 @@ use std::collections::HashMap;
 use std::fmt::Display;
 use std::path::PathBuf;
 
+use serde::{Deserialize, Serialize};
+
 use self::corner::CornerDb;
 use self::mos::spec::MosSpec;
 use self::mos::{LayoutMosParams, MosParams};
@@ -44,7 +46,7 @@ pub enum SupplyId {
     Named(String),
 }
 
-#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Serialize, Deserialize)]
 pub enum DeviceClass {
     Mos,
     Res,
@@ -78,7 +80,7 @@ impl Units {
     }
 }
 
-pub trait Pdk {
+pub trait Pdk: Send + Sync {
     fn name(&self) -> &'static str;
 
     fn process(&self) -> &'static str;
@@ -10,6 +10,16 @@ pub enum MosError {
     BadParams(String),
     #[error("no devices to draw")]
     NoDevices,
+    #[error("invalid source/drain metal skip index {index} for device {device} with {nf} fingers")]
+    InvalidSkipIndex {
+        device: usize,
+        index: usize,
+        nf: u64,
+    },
+    #[error(
+        "mismatched source/drain metal skip list (expected {expected} entries, found {found})"
+    )]
+    MismatchedSkipLength { expected: usize, found: usize },
 }
 
 pub type MosResult<T> = std::result::Result<T, MosError>;
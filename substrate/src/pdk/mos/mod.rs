@@ -1,4 +1,5 @@
 use std::fmt::{Display, Write};
+use std::str::FromStr;
 use std::sync::Arc;
 
 use db::MosDb;
@@ -25,6 +26,9 @@ pub struct MosParams {
 pub struct LayoutMosParams {
     pub devices: Vec<MosParams>,
     /// A vector of finger indices to skip metallization on for each device.
+    ///
+    /// Must contain one entry per device. A device with `nf` fingers
+    /// has `nf + 1` source/drain regions, indexed from `0` to `nf` inclusive.
     pub skip_sd_metal: Vec<Vec<usize>>,
     pub deep_nwell: bool,
     pub contact_strategy: GateContactStrategy,
@@ -67,26 +71,94 @@ impl Display for MosParams {
     }
 }
 
+impl FromStr for MosParams {
+    type Err = MosError;
+
+    /// Parses the canonical `w{w}_l{l}_m{m}_nf{nf}_id{id}` form produced by
+    /// the [`Display`] implementation of [`MosParams`].
+    ///
+    /// The device ID is parsed as a raw [`MosId`] value.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let bad = || MosError::BadParams(format!("cannot parse MOS params from `{s}`"));
+
+        let mut parts = s.split('_');
+        let w = parse_field(parts.next(), "w").ok_or_else(bad)?;
+        let l = parse_field(parts.next(), "l").ok_or_else(bad)?;
+        let m = parse_field(parts.next(), "m").ok_or_else(bad)?;
+        let nf = parse_field(parts.next(), "nf").ok_or_else(bad)?;
+        let id = MosId::new(parse_field(parts.next(), "id").ok_or_else(bad)?);
+
+        if parts.next().is_some() {
+            return Err(bad());
+        }
+
+        Ok(Self { w, l, m, nf, id })
+    }
+}
+
+/// Parses a `{prefix}{value}` field of a canonical [`MosParams`] string.
+fn parse_field<T: FromStr>(part: Option<&str>, prefix: &str) -> Option<T> {
+    part?.strip_prefix(prefix)?.parse().ok()
+}
+
 impl LayoutMosParams {
+    /// Validates the parameters, returning the first problem found.
+    ///
+    /// See [`LayoutMosParams::validate_all`] to collect every problem at once.
     pub fn validate(&self) -> Result<(), MosError> {
+        self.validate_all().map_err(|mut errors| errors.remove(0))
+    }
+
+    /// Validates the parameters, returning every problem found.
+    ///
+    /// If validation fails, the returned vector is guaranteed to be non-empty.
+    pub fn validate_all(&self) -> Result<(), Vec<MosError>> {
         if self.devices.is_empty() {
-            return Err(MosError::NoDevices);
+            return Err(vec![MosError::NoDevices]);
         }
 
+        let mut errors = Vec::new();
+
         let start = &self.devices[0];
         if start.nf as i64 <= 0 {
-            return Err(MosError::InvalidNumFingers(start.nf));
+            errors.push(MosError::InvalidNumFingers(start.nf));
         }
 
+        let mut mismatched_lengths = false;
+        let mut mismatched_fingers = false;
         for device in self.devices.iter().skip(1) {
-            if device.l != start.l {
-                return Err(MosError::MismatchedLengths);
-            } else if device.nf != start.nf {
-                return Err(MosError::MismatchedFingers);
+            if device.l != start.l && !mismatched_lengths {
+                mismatched_lengths = true;
+                errors.push(MosError::MismatchedLengths);
+            }
+            if device.nf != start.nf && !mismatched_fingers {
+                mismatched_fingers = true;
+                errors.push(MosError::MismatchedFingers);
+            }
+        }
+
+        if self.skip_sd_metal.len() != self.devices.len() {
+            errors.push(MosError::MismatchedSkipLength {
+                expected: self.devices.len(),
+                found: self.skip_sd_metal.len(),
+            });
+        }
+
+        for (device, (params, skips)) in self.devices.iter().zip(&self.skip_sd_metal).enumerate() {
+            for &index in skips.iter().filter(|&&i| i as u64 > params.nf) {
+                errors.push(MosError::InvalidSkipIndex {
+                    device,
+                    index,
+                    nf: params.nf,
+                });
             }
         }
 
-        Ok(())
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
     }
 
     pub fn name(&self, mos_db: &Arc<MosDb>) -> String {
@@ -1,5 +1,7 @@
 //! Standard cell and standard cell library error handling.
 
+use std::path::PathBuf;
+
 use thiserror::Error;
 
 use super::{StdCellKey, StdCellLibKey};
@@ -21,4 +23,16 @@ pub enum StdCellError {
 
     #[error("no standard cell named `{cell}` was found in library `{lib}`")]
     CellNameNotFound { cell: String, lib: String },
+
+    #[error("error parsing Liberty file: {0}")]
+    LibertyParse(String),
+
+    #[error("{owner}: {view} source {path:?} is not readable: {reason}")]
+    SourceUnreadable {
+        /// A description of the library or cell that references the source.
+        owner: String,
+        view: String,
+        path: PathBuf,
+        reason: String,
+    },
 }
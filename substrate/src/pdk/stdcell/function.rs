@@ -0,0 +1,254 @@
+//! Recognizing standard cell [`Function`]s from boolean expressions.
+
+use super::Function;
+
+/// The maximum number of distinct inputs in an expression that can be recognized.
+const MAX_INPUTS: usize = 6;
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+enum Expr {
+    Var(usize),
+    Const(bool),
+    Not(Box<Expr>),
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+    Xor(Box<Expr>, Box<Expr>),
+}
+
+impl Expr {
+    fn eval(&self, inputs: usize) -> bool {
+        match self {
+            Expr::Var(i) => inputs & (1 << i) != 0,
+            Expr::Const(c) => *c,
+            Expr::Not(e) => !e.eval(inputs),
+            Expr::And(a, b) => a.eval(inputs) && b.eval(inputs),
+            Expr::Or(a, b) => a.eval(inputs) || b.eval(inputs),
+            Expr::Xor(a, b) => a.eval(inputs) ^ b.eval(inputs),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+enum Token {
+    Ident(String),
+    Const(bool),
+    Not,
+    Prime,
+    And,
+    Or,
+    Xor,
+    Open,
+    Close,
+}
+
+fn tokenize(input: &str) -> Option<Vec<Token>> {
+    let mut tokens = Vec::new();
+    let mut chars = input.chars().peekable();
+    while let Some(c) = chars.next() {
+        let token = match c {
+            c if c.is_whitespace() => continue,
+            '!' | '~' => Token::Not,
+            '\'' => Token::Prime,
+            '&' | '*' => Token::And,
+            '|' | '+' => Token::Or,
+            '^' => Token::Xor,
+            '(' => Token::Open,
+            ')' => Token::Close,
+            '0' => Token::Const(false),
+            '1' => Token::Const(true),
+            c if c.is_alphabetic() || c == '_' => {
+                let mut name = c.to_string();
+                while let Some(&c) = chars.peek() {
+                    if !(c.is_alphanumeric() || c == '_' || c == '[' || c == ']') {
+                        break;
+                    }
+                    name.push(c);
+                    chars.next();
+                }
+                Token::Ident(name)
+            }
+            _ => return None,
+        };
+        tokens.push(token);
+    }
+    Some(tokens)
+}
+
+/// A recursive descent parser for Liberty-style boolean expressions.
+///
+/// From lowest to highest precedence, the operators are
+/// OR (`|`, `+`), AND (`&`, `*`, or juxtaposition), XOR (`^`),
+/// and NOT (prefix `!` or `~`, or postfix `'`).
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+    vars: Vec<String>,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn eat(&mut self, token: &Token) -> bool {
+        if self.peek() == Some(token) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn or(&mut self) -> Option<Expr> {
+        let mut expr = self.and()?;
+        while self.eat(&Token::Or) {
+            expr = Expr::Or(Box::new(expr), Box::new(self.and()?));
+        }
+        Some(expr)
+    }
+
+    fn and(&mut self) -> Option<Expr> {
+        let mut expr = self.xor()?;
+        loop {
+            let explicit = self.eat(&Token::And);
+            let implicit = matches!(
+                self.peek(),
+                Some(Token::Ident(_) | Token::Const(_) | Token::Not | Token::Open)
+            );
+            if !(explicit || implicit) {
+                return Some(expr);
+            }
+            expr = Expr::And(Box::new(expr), Box::new(self.xor()?));
+        }
+    }
+
+    fn xor(&mut self) -> Option<Expr> {
+        let mut expr = self.unary()?;
+        while self.eat(&Token::Xor) {
+            expr = Expr::Xor(Box::new(expr), Box::new(self.unary()?));
+        }
+        Some(expr)
+    }
+
+    fn unary(&mut self) -> Option<Expr> {
+        if self.eat(&Token::Not) {
+            return Some(Expr::Not(Box::new(self.unary()?)));
+        }
+        let mut expr = self.primary()?;
+        while self.eat(&Token::Prime) {
+            expr = Expr::Not(Box::new(expr));
+        }
+        Some(expr)
+    }
+
+    fn primary(&mut self) -> Option<Expr> {
+        let token = self.peek()?.clone();
+        self.pos += 1;
+        match token {
+            Token::Ident(name) => {
+                let idx = match self.vars.iter().position(|v| *v == name) {
+                    Some(idx) => idx,
+                    None => {
+                        self.vars.push(name);
+                        self.vars.len() - 1
+                    }
+                };
+                Some(Expr::Var(idx))
+            }
+            Token::Const(c) => Some(Expr::Const(c)),
+            Token::Open => {
+                let expr = self.or()?;
+                self.eat(&Token::Close).then_some(expr)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Parses `input`, returning the expression and its number of distinct inputs.
+fn parse(input: &str) -> Option<(Expr, usize)> {
+    let mut parser = Parser {
+        tokens: tokenize(input)?,
+        pos: 0,
+        vars: Vec::new(),
+    };
+    let expr = parser.or()?;
+    if parser.pos != parser.tokens.len() {
+        return None;
+    }
+    Some((expr, parser.vars.len()))
+}
+
+/// Returns whether `table` is a multiplexer with `select_bits` select inputs,
+/// for some assignment of the expression's inputs to select and data roles.
+fn is_mux(table: &[bool], n: usize, select_bits: usize) -> bool {
+    n == select_bits + (1 << select_bits)
+        && assign_roles(table, select_bits, &mut Vec::with_capacity(n), n)
+}
+
+/// Tries every ordering of the remaining inputs after those already in `roles`.
+///
+/// The first `select_bits` inputs in `roles` are the select inputs, from least
+/// to most significant; the rest are the data inputs, in select order.
+fn assign_roles(table: &[bool], select_bits: usize, roles: &mut Vec<usize>, n: usize) -> bool {
+    if roles.len() == n {
+        return table.iter().enumerate().all(|(m, &value)| {
+            let bit = |role: usize| (m >> roles[role]) & 1;
+            let sel = (0..select_bits).fold(0, |acc, i| acc | (bit(i) << i));
+            value == (bit(select_bits + sel) == 1)
+        });
+    }
+    (0..n).any(|input| {
+        if roles.contains(&input) {
+            return false;
+        }
+        roles.push(input);
+        let found = assign_roles(table, select_bits, roles, n);
+        roles.pop();
+        found
+    })
+}
+
+impl Function {
+    /// Recognizes the logic function computed by a boolean expression,
+    /// such as a Liberty pin's `function` attribute.
+    ///
+    /// The expression's truth table is compared against each known function,
+    /// so any expression equivalent to a known function is recognized,
+    /// regardless of how it is written or what its inputs are named.
+    /// For example, `"!(A&B)"` and `"A' + B'"` are both [`Function::Nand2`].
+    ///
+    /// Returns [`Function::Other`] containing the original expression
+    /// if the expression cannot be parsed or does not match a known function.
+    /// [`Function::Mux3`] and [`Function::Tap`] are never returned.
+    pub fn from_expr(expr: &str) -> Function {
+        Self::recognize(expr).unwrap_or_else(|| Function::Other(expr.to_string()))
+    }
+
+    fn recognize(expr: &str) -> Option<Function> {
+        let (expr, n) = parse(expr)?;
+        if n == 0 || n > MAX_INPUTS {
+            return None;
+        }
+
+        let table = (0..1usize << n).map(|m| expr.eval(m)).collect::<Vec<_>>();
+        let is = |f: &dyn Fn(usize) -> bool| table.iter().enumerate().all(|(m, &v)| v == f(m));
+        let ones = |m: usize| m.count_ones() as usize;
+
+        use Function::*;
+        let function = match n {
+            1 if is(&|m| m == 1) => Buf,
+            1 if is(&|m| m == 0) => Inv,
+            2 | 3 if is(&|m| ones(m) == n) => [And2, And3][n - 2].clone(),
+            2 | 3 if is(&|m| ones(m) != n) => [Nand2, Nand3][n - 2].clone(),
+            2 | 3 if is(&|m| ones(m) > 0) => [Or2, Or3][n - 2].clone(),
+            2 | 3 if is(&|m| ones(m) == 0) => [Nor2, Nor3][n - 2].clone(),
+            2..=4 if is(&|m| ones(m) % 2 == 1) => [Xor2, Xor3, Xor4][n - 2].clone(),
+            2..=4 if is(&|m| ones(m) % 2 == 0) => [Xnor2, Xnor3, Xnor4][n - 2].clone(),
+            3 if is_mux(&table, n, 1) => Mux2,
+            6 if is_mux(&table, n, 2) => Mux4,
+            _ => return None,
+        };
+        Some(function)
+    }
+}
@@ -0,0 +1,296 @@
+//! A minimal parser for Liberty (`.lib`) standard cell library files.
+//!
+//! Only the group and attribute structure of the file is parsed;
+//! attribute values are kept as unparsed strings.
+
+use std::path::Path;
+
+use super::error::StdCellError;
+use super::{Function, StdCellData, StdCellLibData, StdCellPin};
+use crate::schematic::circuit::Direction;
+
+/// A Liberty group, such as `cell (INV) { ... }`.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub(crate) struct Group {
+    pub(crate) kind: String,
+    pub(crate) args: Vec<String>,
+    /// Simple (`key : value;`) and complex (`key (a, b);`) attributes, in order.
+    ///
+    /// The arguments of complex attributes are joined with commas.
+    pub(crate) attrs: Vec<(String, String)>,
+    pub(crate) groups: Vec<Group>,
+}
+
+impl Group {
+    /// Returns the value of the first attribute named `key`.
+    pub(crate) fn attr(&self, key: &str) -> Option<&str> {
+        self.attrs
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// Returns an iterator over the subgroups of the given kind.
+    pub(crate) fn groups(&self, kind: &'static str) -> impl Iterator<Item = &Group> {
+        self.groups.iter().filter(move |g| g.kind == kind)
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+enum Token {
+    Word(String),
+    Str(String),
+    Punct(char),
+}
+
+fn is_punct(c: char) -> bool {
+    matches!(c, '(' | ')' | '{' | '}' | ':' | ';' | ',')
+}
+
+fn tokenize(input: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = input.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() => {}
+            // Line continuations.
+            '\\' => {}
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+                let mut prev = None;
+                loop {
+                    match chars.next() {
+                        Some('/') if prev == Some('*') => break,
+                        Some(c) => prev = Some(c),
+                        None => return Err("unterminated comment".to_string()),
+                    }
+                }
+            }
+            '/' if chars.peek() == Some(&'/') => {
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        break;
+                    }
+                }
+            }
+            '"' => {
+                let mut s = String::new();
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => match chars.next() {
+                            Some('\n') | Some('\r') => {}
+                            Some(c) => s.push(c),
+                            None => return Err("unterminated string".to_string()),
+                        },
+                        Some(c) => s.push(c),
+                        None => return Err("unterminated string".to_string()),
+                    }
+                }
+                tokens.push(Token::Str(s));
+            }
+            c if is_punct(c) => tokens.push(Token::Punct(c)),
+            c => {
+                let mut s = c.to_string();
+                while let Some(&c) = chars.peek() {
+                    if c.is_whitespace() || is_punct(c) || c == '"' {
+                        break;
+                    }
+                    s.push(c);
+                    chars.next();
+                }
+                tokens.push(Token::Word(s));
+            }
+        }
+    }
+
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn bump(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn eat(&mut self, punct: char) -> bool {
+        if self.peek() == Some(&Token::Punct(punct)) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn value(&mut self) -> Result<String, String> {
+        match self.bump() {
+            Some(Token::Word(s)) | Some(Token::Str(s)) => Ok(s),
+            other => Err(format!("expected a value, found {other:?}")),
+        }
+    }
+
+    /// Parses statements into `group` until a closing brace or the end of input.
+    fn statements(&mut self, group: &mut Group) -> Result<(), String> {
+        loop {
+            let kind = match self.peek() {
+                None | Some(Token::Punct('}')) => return Ok(()),
+                Some(Token::Punct(';')) => {
+                    self.pos += 1;
+                    continue;
+                }
+                Some(_) => self.value()?,
+            };
+
+            if self.eat(':') {
+                let value = self.value()?;
+                self.eat(';');
+                group.attrs.push((kind, value));
+            } else if self.eat('(') {
+                let mut args = Vec::new();
+                while !self.eat(')') {
+                    if !self.eat(',') {
+                        args.push(self.value()?);
+                    }
+                }
+                if self.eat('{') {
+                    let mut child = Group {
+                        kind,
+                        args,
+                        ..Default::default()
+                    };
+                    self.statements(&mut child)?;
+                    if !self.eat('}') {
+                        return Err(format!("unterminated group `{}`", child.kind));
+                    }
+                    group.groups.push(child);
+                } else {
+                    self.eat(';');
+                    group.attrs.push((kind, args.join(",")));
+                }
+            } else {
+                return Err(format!(
+                    "expected `:` or `(` after `{kind}`, found {:?}",
+                    self.peek()
+                ));
+            }
+        }
+    }
+}
+
+/// Parses the given Liberty source into a root group containing all top-level groups.
+pub(crate) fn parse(input: &str) -> Result<Group, String> {
+    let mut parser = Parser {
+        tokens: tokenize(input)?,
+        pos: 0,
+    };
+    let mut root = Group::default();
+    parser.statements(&mut root)?;
+    if parser.peek().is_some() {
+        return Err("unexpected `}`".to_string());
+    }
+    Ok(root)
+}
+
+/// Infers a cell's drive strength from a trailing number in its name,
+/// such as `inv_2` or `INVX4`.
+fn strength(name: &str) -> usize {
+    let digits = name
+        .chars()
+        .rev()
+        .take_while(|c| c.is_ascii_digit())
+        .collect::<String>();
+    if digits.is_empty() {
+        return 1;
+    }
+    let prefix = &name[..name.len() - digits.len()];
+    if !(prefix.ends_with('_') || prefix.ends_with('X') || prefix.ends_with('x')) {
+        return 1;
+    }
+    digits
+        .chars()
+        .rev()
+        .collect::<String>()
+        .parse()
+        .unwrap_or(1)
+}
+
+fn direction(value: &str) -> Direction {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "input" => Direction::Input,
+        "output" => Direction::Output,
+        _ => Direction::InOut,
+    }
+}
+
+impl StdCellLibData {
+    /// Loads a standard cell library from the Liberty file at `path`.
+    ///
+    /// See [`StdCellLibData::from_liberty_str`].
+    pub fn from_liberty(path: impl AsRef<Path>) -> crate::error::Result<Self> {
+        let input = crate::io::read_to_string(path)?;
+        Self::from_liberty_str(&input)
+    }
+
+    /// Loads a standard cell library from the given Liberty source.
+    ///
+    /// Each `cell` group becomes a standard cell, with its area and pins.
+    /// The cell's [`Function`] is taken from the `function` attribute of its
+    /// first output pin that has one, using [`Function::from_expr`].
+    /// Drive strengths are inferred from trailing numbers in cell names, defaulting to 1.
+    ///
+    /// No layout or schematic sources are set.
+    pub fn from_liberty_str(input: &str) -> crate::error::Result<Self> {
+        let root = parse(input).map_err(StdCellError::LibertyParse)?;
+        let library = root
+            .groups("library")
+            .next()
+            .ok_or_else(|| StdCellError::LibertyParse("no library group found".to_string()))?;
+        let name = library.args.first().cloned().unwrap_or_default();
+        let mut lib = StdCellLibData::new(name);
+
+        for cell in library.groups("cell") {
+            let name = cell.args.first().ok_or_else(|| {
+                StdCellError::LibertyParse("cell group without a name".to_string())
+            })?;
+
+            let mut pins = Vec::new();
+            let mut func = None;
+            for pin in cell.groups("pin") {
+                let dir = pin.attr("direction").map(direction).unwrap_or_default();
+                for name in pin.args.iter() {
+                    pins.push(StdCellPin {
+                        name: name.into(),
+                        direction: dir,
+                    });
+                }
+                if dir == Direction::Output && func.is_none() {
+                    func = pin.attr("function").map(Function::from_expr);
+                }
+            }
+
+            let mut builder = StdCellData::builder();
+            builder
+                .name(name.as_str())
+                .function(func.unwrap_or_else(|| Function::Other(String::new())))
+                .strength(strength(name))
+                .pins(pins);
+            if let Some(area) = cell.attr("area").and_then(|a| a.parse().ok()) {
+                builder.area(area);
+            }
+            lib.add_cell(builder.build().unwrap());
+        }
+
+        Ok(lib)
+    }
+}
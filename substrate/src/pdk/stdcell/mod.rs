@@ -1,4 +1,5 @@
-use std::path::PathBuf;
+use std::fs::File;
+use std::path::{Path, PathBuf};
 
 use arcstr::ArcStr;
 use derive_builder::Builder;
@@ -7,8 +8,11 @@ use slotmap::{new_key_type, SlotMap};
 
 use self::error::StdCellError;
 use crate::component::{Component, View};
+use crate::schematic::circuit::Direction;
 
 pub mod error;
+mod function;
+mod liberty;
 
 new_key_type! {
     /// A unique identifier for [standard cells](StdCellData).
@@ -50,6 +54,19 @@ pub struct StdCellData {
     function: Function,
     #[builder(default = "1")]
     strength: usize,
+
+    /// The area of the cell, in the units of its source library.
+    #[builder(default, setter(strip_option))]
+    area: Option<f64>,
+    #[builder(default)]
+    pins: Vec<StdCellPin>,
+}
+
+/// A signal pin of a standard cell.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct StdCellPin {
+    pub name: ArcStr,
+    pub direction: Direction,
 }
 
 #[derive(Debug, Clone, Builder)]
@@ -89,6 +106,28 @@ impl<'a> StdCellRef<'a> {
         self.inner
     }
 
+    /// Instantiates the layout of this standard cell.
+    ///
+    /// Equivalent to instantiating a [`StdCell`] with this cell's [`StdCellId`].
+    #[inline]
+    pub fn instantiate_layout(
+        &self,
+        ctx: &mut crate::layout::context::LayoutCtx,
+    ) -> crate::error::Result<crate::layout::cell::Instance> {
+        ctx.instantiate::<StdCell>(&self.id())
+    }
+
+    /// Instantiates the schematic of this standard cell.
+    ///
+    /// Equivalent to instantiating a [`StdCell`] with this cell's [`StdCellId`].
+    #[inline]
+    pub fn instantiate_schematic(
+        &self,
+        ctx: &mut crate::schematic::context::SchematicCtx,
+    ) -> crate::error::Result<crate::schematic::circuit::Instance> {
+        ctx.instantiate::<StdCell>(&self.id())
+    }
+
     #[inline]
     fn new(library_id: StdCellLibKey, inner: &'a StdCellEntry) -> Self {
         Self { library_id, inner }
@@ -131,6 +170,26 @@ fn view_unsupported(view: View) -> crate::error::SubstrateError {
     crate::component::error::Error::ViewUnsupported(view).into()
 }
 
+/// Checks that the file at `path` exists and can be opened for reading.
+fn check_source(
+    path: &Path,
+    view: View,
+    owner: impl FnOnce() -> String,
+) -> crate::error::Result<()> {
+    let reason = match File::open(path).and_then(|f| f.metadata()) {
+        Ok(metadata) if metadata.is_file() => return Ok(()),
+        Ok(_) => "not a file".to_string(),
+        Err(e) => e.to_string(),
+    };
+    Err(StdCellError::SourceUnreadable {
+        owner: owner(),
+        view: view.to_string(),
+        path: path.to_path_buf(),
+        reason,
+    }
+    .into())
+}
+
 impl StdCellLibData {
     pub fn new(name: impl Into<ArcStr>) -> Self {
         Self {
@@ -160,6 +219,26 @@ impl StdCellLibData {
         self.schematic_source = Some(source.into());
     }
 
+    /// Checks that every source file referenced by this library
+    /// and by each of its cells exists and is readable.
+    ///
+    /// Returns an error describing the first unreadable source found.
+    pub fn validate(&self) -> crate::error::Result<()> {
+        for view in [View::Schematic, View::Layout] {
+            if let Some(path) = self.source(view.clone()) {
+                check_source(path, view.clone(), || format!("library `{}`", self.name))?;
+            }
+            for cell in self.cells() {
+                if let Some(path) = cell.source(view.clone()) {
+                    check_source(path, view.clone(), || {
+                        format!("cell `{}` in library `{}`", cell.name(), self.name)
+                    })?;
+                }
+            }
+        }
+        Ok(())
+    }
+
     pub fn try_cell_named(&self, name: &str) -> crate::error::Result<&StdCellEntry> {
         self.cells().find(|c| c.name() == name).ok_or_else(|| {
             StdCellError::CellNameNotFound {
@@ -286,6 +365,16 @@ impl StdCellData {
         self.strength
     }
 
+    #[inline]
+    pub fn area(&self) -> Option<f64> {
+        self.area
+    }
+
+    #[inline]
+    pub fn pins(&self) -> &[StdCellPin] {
+        &self.pins
+    }
+
     #[inline]
     pub fn builder() -> StdCellDataBuilder {
         StdCellDataBuilder::default()
@@ -318,6 +407,16 @@ impl StdCellEntry {
         self.data.view_name(view)
     }
 
+    #[inline]
+    pub fn area(&self) -> Option<f64> {
+        self.data.area()
+    }
+
+    #[inline]
+    pub fn pins(&self) -> &[StdCellPin] {
+        self.data.pins()
+    }
+
     #[inline]
     pub fn function(&self) -> &Function {
         self.data.function()
@@ -344,6 +443,16 @@ impl StdCellDb {
         }
     }
 
+    /// Checks the source files of every library in the database.
+    ///
+    /// See [`StdCellLibData::validate`].
+    pub fn validate(&self) -> crate::error::Result<()> {
+        for lib in self.libraries.values() {
+            lib.data.validate()?;
+        }
+        Ok(())
+    }
+
     #[inline]
     pub fn add_lib(&mut self, data: StdCellLibData) -> StdCellLibKey {
         self.libraries
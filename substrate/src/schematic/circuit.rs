@@ -1,6 +1,7 @@
 //! Circuit primitives for schematic generation.
 
 use std::collections::HashMap;
+use std::ops::Range;
 use std::sync::Arc;
 
 use serde::{Deserialize, Serialize};
@@ -9,7 +10,7 @@ use thiserror::Error;
 
 use super::context::{ModuleKey, SchematicCtx};
 use super::module::Module;
-use super::signal::{Signal, SignalKey};
+use super::signal::{Signal, SignalKey, SliceOne};
 use crate::deps::arcstr::ArcStr;
 
 /// A signal exposed by a [`Module`](super::module::Module).
@@ -74,6 +75,36 @@ pub struct Instance {
     params: HashMap<ArcStr, Value>,
     /// A map of connections to the instance's ports.
     connections: HashMap<ArcStr, Signal>,
+    /// Bitwise connections to ports that have only been partially connected.
+    ///
+    /// Moved to `connections` once every bit is connected.
+    partial_connections: HashMap<ArcStr, Vec<Option<SliceOne>>>,
+}
+
+/// A range of bits of a bus port.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PortSlice {
+    /// The name of the port.
+    pub port: ArcStr,
+    /// The range of bits within the port.
+    pub range: Range<usize>,
+}
+
+impl PortSlice {
+    /// Creates a new [`PortSlice`] referring to bits `range` of `port`.
+    #[inline]
+    pub fn new(port: impl Into<ArcStr>, range: Range<usize>) -> Self {
+        Self {
+            port: port.into(),
+            range,
+        }
+    }
+
+    /// Returns the number of bits in the slice.
+    #[inline]
+    pub fn width(&self) -> usize {
+        self.range.len()
+    }
 }
 
 impl Instance {
@@ -84,13 +115,71 @@ impl Instance {
             module,
             params: HashMap::new(),
             connections: HashMap::new(),
+            partial_connections: HashMap::new(),
         }
     }
 
     /// Connects a port of the instance to the provided signal.
     #[inline]
     pub fn connect(&mut self, port: impl Into<ArcStr>, signal: impl Into<Signal>) {
-        self.connections.insert(port.into(), signal.into());
+        let port = port.into();
+        self.partial_connections.remove(&port);
+        self.connections.insert(port, signal.into());
+    }
+
+    /// Connects a range of bits of a bus port to the provided signal.
+    ///
+    /// The widths of the slice and the signal must match. Bits of the port outside
+    /// the slice keep their existing connections. Once every bit of the port is connected,
+    /// the port appears in [`Instance::connections`] as if connected with [`Instance::connect`].
+    ///
+    /// The port width is taken from the instance's module. Ports of external modules
+    /// can only be sliced after being fully connected.
+    pub fn connect_slice(
+        &mut self,
+        slice: PortSlice,
+        signal: impl Into<Signal>,
+    ) -> Result<(), PortError> {
+        let signal = signal.into();
+        if signal.width() != slice.width() {
+            return Err(PortError::WidthMismatch {
+                port: slice.port,
+                expected: slice.width(),
+                found: signal.width(),
+            });
+        }
+
+        let mut bits: Vec<Option<SliceOne>> =
+            if let Some(existing) = self.connections.get(&slice.port) {
+                existing.bits().map(Some).collect()
+            } else if let Some(bits) = self.partial_connections.get(&slice.port) {
+                bits.clone()
+            } else {
+                let module = self.module.local_ref().ok_or(PortError::ExternalModule)?;
+                vec![None; module.port(&slice.port)?.width]
+            };
+
+        if slice.range.start > slice.range.end || slice.range.end > bits.len() {
+            return Err(PortError::SliceOutOfBounds {
+                port: slice.port,
+                range: slice.range,
+                width: bits.len(),
+            });
+        }
+
+        for (bit, new) in bits[slice.range].iter_mut().zip(signal.bits()) {
+            *bit = Some(new);
+        }
+
+        if bits.iter().all(Option::is_some) {
+            self.partial_connections.remove(&slice.port);
+            self.connections
+                .insert(slice.port, Signal::from_bits(bits.into_iter().flatten()));
+        } else {
+            self.connections.remove(&slice.port);
+            self.partial_connections.insert(slice.port, bits);
+        }
+        Ok(())
     }
 
     /// Connects port-signal tuples provided as an iterator.
@@ -158,6 +247,18 @@ impl Instance {
         self
     }
 
+    /// A consuming method to connect a range of bits of a bus port.
+    ///
+    /// See [`Instance::connect_slice`] for more information.
+    pub fn with_connection_slice(
+        mut self,
+        slice: PortSlice,
+        signal: impl Into<Signal>,
+    ) -> Result<Self, PortError> {
+        self.connect_slice(slice, signal)?;
+        Ok(self)
+    }
+
     /// Adds this instance to the given schematic context.
     ///
     /// Equivalent to calling `ctx.add_instance(instance)`.
@@ -194,6 +295,22 @@ pub enum PortError {
     /// Cannot list the ports of an external module.
     #[error("cannot list the ports of an external module")]
     ExternalModule,
+
+    /// A port slice was connected to a signal of a different width.
+    #[error("cannot connect a slice of port {port} with width {expected} to a signal with width {found}")]
+    WidthMismatch {
+        port: ArcStr,
+        expected: usize,
+        found: usize,
+    },
+
+    /// A port slice extends beyond the bounds of the port.
+    #[error("slice {range:?} is out of bounds for port {port} with width {width}")]
+    SliceOutOfBounds {
+        port: ArcStr,
+        range: Range<usize>,
+        width: usize,
+    },
 }
 
 /// An enumeration of reference types.
@@ -274,3 +391,65 @@ pub enum Direction {
     #[default]
     InOut,
 }
+
+impl Direction {
+    /// Returns the opposite direction.
+    ///
+    /// Swaps [`Direction::Input`] and [`Direction::Output`].
+    /// [`Direction::InOut`] is left unchanged.
+    ///
+    /// Useful for viewing a port from the inside of its module,
+    /// where an input port drives the nets it is connected to.
+    #[inline]
+    pub fn flip(self) -> Self {
+        match self {
+            Direction::Input => Direction::Output,
+            Direction::Output => Direction::Input,
+            Direction::InOut => Direction::InOut,
+        }
+    }
+
+    /// Combines two directions, e.g. when merging the nets they are attached to.
+    ///
+    /// Returns the common direction if both directions are the same,
+    /// and [`Direction::InOut`] otherwise.
+    #[inline]
+    pub fn combine(self, other: Self) -> Self {
+        if self == other {
+            self
+        } else {
+            Direction::InOut
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_direction_flip() {
+        assert_eq!(Direction::Input.flip(), Direction::Output);
+        assert_eq!(Direction::Output.flip(), Direction::Input);
+        assert_eq!(Direction::InOut.flip(), Direction::InOut);
+    }
+
+    #[test]
+    fn test_direction_combine() {
+        use Direction::*;
+        let table = [
+            (Input, Input, Input),
+            (Input, Output, InOut),
+            (Input, InOut, InOut),
+            (Output, Input, InOut),
+            (Output, Output, Output),
+            (Output, InOut, InOut),
+            (InOut, Input, InOut),
+            (InOut, Output, InOut),
+            (InOut, InOut, InOut),
+        ];
+        for (a, b, expected) in table {
+            assert_eq!(a.combine(b), expected, "{a:?} combined with {b:?}");
+        }
+    }
+}
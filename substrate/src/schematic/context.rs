@@ -5,6 +5,7 @@ use std::collections::HashMap;
 use std::hash::Hash;
 use std::path::Path;
 use std::sync::Arc;
+use std::thread::ThreadId;
 
 use itertools::Itertools;
 use slotmap::new_key_type;
@@ -18,7 +19,7 @@ use crate::deps::arcstr::ArcStr;
 use crate::error::{with_err_context, ErrorContext, ErrorSource, Result as SubResult};
 use crate::fmt::signal::{parse_bus, ParsedBus};
 use crate::generation::{GeneratedCheck, GenerationMap, ParamKey};
-use crate::hard_macro::Config as HardMacroConfig;
+use crate::hard_macro::{infer_directions, Config as HardMacroConfig};
 use crate::index::IndexOwned;
 use crate::pdk::mos::db::MosDb;
 use crate::pdk::Pdk;
@@ -62,14 +63,23 @@ impl SchematicData {
         self.modules.get_id(ParamKey::from_params::<T>(params))
     }
 
+    /// Returns the generated module if it already exists or generates a new ID.
+    ///
+    /// Returns the ID of the generating thread as an error if the module
+    /// is being generated by another thread.
     pub(crate) fn get_module<T>(
         &mut self,
         params: &T::Params,
-    ) -> GeneratedCheck<Arc<Module>, ModuleKey>
+    ) -> Result<GeneratedCheck<Arc<Module>, ModuleKey>, ThreadId>
     where
         T: Component,
     {
-        self.modules.get(ParamKey::from_params::<T>(params))
+        self.modules.try_get(ParamKey::from_params::<T>(params))
+    }
+
+    /// Forgets about a module that failed to generate.
+    pub(crate) fn abort_module(&mut self, id: ModuleKey) {
+        self.modules.abort(id);
     }
 
     pub(crate) fn set_module(&mut self, module: Module) -> Arc<Module> {
@@ -197,6 +207,42 @@ impl SchematicCtx {
         self.module.set_raw_spice(spice)
     }
 
+    /// Marks `net` as the ground net of this module.
+    ///
+    /// The marked net is used to populate the inputs of verification tools
+    /// (e.g. [`PexInput::ground_net`](crate::verification::pex::PexInput::ground_net)),
+    /// so it does not need to be restated for each tool.
+    /// Marking another net replaces the previously marked ground net.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `net` is not a single-bit signal.
+    pub fn mark_ground(&mut self, net: Slice) {
+        self.assert_single_bit(net);
+        self.module.set_ground_net(net.signal());
+    }
+
+    /// Marks `net` as a supply net of this module with the given voltage, in volts.
+    ///
+    /// Like [`SchematicCtx::mark_ground`], marked supply nets are used to populate
+    /// the inputs of verification tools.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `net` is not a single-bit signal.
+    pub fn mark_supply(&mut self, net: Slice, voltage: f64) {
+        self.assert_single_bit(net);
+        self.module.add_supply_net(net.signal(), voltage);
+    }
+
+    fn assert_single_bit(&self, net: Slice) {
+        assert_eq!(
+            self.module.signal_width(net.signal()),
+            Some(1),
+            "only single-bit signals can be marked as ground or supply nets"
+        );
+    }
+
     /// Bubbles up the port with the given name.
     ///
     /// The instance must still be added to the schematic context.
@@ -295,6 +341,7 @@ impl SchematicCtx {
     }
 
     pub fn import_hard_macro_config(&mut self, config: HardMacroConfig) -> SubResult<()> {
+        config.validate()?;
         let subckt = config.spice_subckt_name.ok_or_else(|| {
             ErrorSource::InvalidArgs(
                 "subcircuit name must be specified when importing hard macro".to_string(),
@@ -306,6 +353,12 @@ impl SchematicCtx {
             )
         })?;
 
+        let inferred = if config.infer_directions {
+            infer_directions(&path, &subckt, config.bus_format)?
+        } else {
+            HashMap::new()
+        };
+
         // Rename this module to avoid conflicting with the external module.
         self.module.set_name(arcstr::format!("{}_wrapper", subckt));
 
@@ -316,19 +369,50 @@ impl SchematicCtx {
             connected: Vec<bool>,
         }
 
-        let mut pub_ports = config
-            .ports
-            .into_iter()
-            .map(|(name, info)| {
-                (
-                    name.clone(),
-                    PortStatus {
-                        slice: self.bus_port(name, info.width, info.direction),
-                        connected: vec![false; info.width],
-                    },
-                )
-            })
-            .collect::<HashMap<_, _>>();
+        // Declare ports in the configured order, falling back to the order in which
+        // they first appear in the subcircuit so that the result is deterministic.
+        let order = if config.port_order.is_empty() {
+            let mut order = Vec::new();
+            for port in ext.ports.iter() {
+                let raw_name = ext.signals()[port.signal].name();
+                let name = match parse_bus(raw_name, config.bus_format) {
+                    Ok(ParsedBus { name, .. }) => name,
+                    Err(_) => raw_name.as_str(),
+                };
+                if let Some((name, _)) = config.ports.get_key_value(name) {
+                    if !order.contains(name) {
+                        order.push(name.clone());
+                    }
+                }
+            }
+            let mut rest = config
+                .ports
+                .keys()
+                .filter(|name| !order.contains(*name))
+                .cloned()
+                .collect::<Vec<_>>();
+            rest.sort();
+            order.extend(rest);
+            order
+        } else {
+            config.port_order
+        };
+
+        let mut pub_ports = HashMap::new();
+        for name in order {
+            let info = config.ports[&name];
+            let direction = info
+                .direction
+                .or_else(|| inferred.get(&name).copied())
+                .unwrap_or_default();
+            pub_ports.insert(
+                name.clone(),
+                PortStatus {
+                    slice: self.bus_port(name, info.width, direction),
+                    connected: vec![false; info.width],
+                },
+            );
+        }
 
         let mut conns = Vec::new();
 
@@ -2,29 +2,47 @@
 
 use crate::component::Component;
 use crate::deps::arcstr::ArcStr;
+use crate::error::ErrorSource;
 use crate::schematic::circuit::Direction;
 use crate::schematic::context::SchematicCtx;
 use crate::units::SiValue;
 
 /// A primitive capacitor parametrized by capacitance.
+///
+/// Negative capacitances are rejected. A zero capacitance is netlisted
+/// with a value of `0`, leaving its terminals unconnected.
 pub struct Capacitor(SiValue);
 
 impl Component for Capacitor {
     type Params = SiValue;
 
     fn new(params: &Self::Params, _ctx: &crate::data::SubstrateCtx) -> crate::error::Result<Self> {
+        if params.is_negative() {
+            return Err(ErrorSource::InvalidArgs(format!(
+                "capacitance must not be negative, got {params}"
+            ))
+            .into());
+        }
         Ok(Self(*params))
     }
 
     fn name(&self) -> ArcStr {
-        arcstr::format!("capacitor_{}", self.0)
+        if self.0.is_zero() {
+            arcstr::literal!("capacitor_0")
+        } else {
+            arcstr::format!("capacitor_{}", self.0)
+        }
     }
 
     fn schematic(&self, ctx: &mut SchematicCtx) -> crate::error::Result<()> {
         let _p = ctx.port("p", Direction::InOut);
         let _n = ctx.port("n", Direction::InOut);
 
-        ctx.set_spice(format!("C1 p n {}", self.0));
+        if self.0.is_zero() {
+            ctx.set_spice("C1 p n 0");
+        } else {
+            ctx.set_spice(format!("C1 p n {}", self.0));
+        }
         Ok(())
     }
 }
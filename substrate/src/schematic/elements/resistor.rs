@@ -2,21 +2,35 @@
 
 use crate::component::Component;
 use crate::deps::arcstr::ArcStr;
+use crate::error::ErrorSource;
 use crate::schematic::circuit::Direction;
 use crate::units::SiValue;
 
 /// A primitive resistor parametrized by resistance.
+///
+/// Negative resistances are rejected. A zero resistance is netlisted as a short,
+/// implemented as a zero-volt voltage source.
 pub struct Resistor(SiValue);
 
 impl Component for Resistor {
     type Params = SiValue;
 
     fn new(params: &Self::Params, _ctx: &crate::data::SubstrateCtx) -> crate::error::Result<Self> {
+        if params.is_negative() {
+            return Err(ErrorSource::InvalidArgs(format!(
+                "resistance must not be negative, got {params}"
+            ))
+            .into());
+        }
         Ok(Self(*params))
     }
 
     fn name(&self) -> ArcStr {
-        arcstr::format!("resistor_{}", self.0)
+        if self.0.is_zero() {
+            arcstr::literal!("resistor_short")
+        } else {
+            arcstr::format!("resistor_{}", self.0)
+        }
     }
 
     fn schematic(
@@ -26,7 +40,11 @@ impl Component for Resistor {
         let _p = ctx.port("p", Direction::InOut);
         let _n = ctx.port("n", Direction::InOut);
 
-        ctx.set_spice(format!("R1 p n {}", self.0));
+        if self.0.is_zero() {
+            ctx.set_spice("V1 p n 0");
+        } else {
+            ctx.set_spice(format!("R1 p n {}", self.0));
+        }
         Ok(())
     }
 }
@@ -21,6 +21,10 @@ pub struct Module {
     signals: SlotMap<SignalKey, SignalInfo>,
     raw_spice: Option<ArcStr>,
     timing: TimingView,
+    /// The net marked as ground.
+    ground_net: Option<SignalKey>,
+    /// The nets marked as supplies, along with their voltages.
+    supply_nets: Vec<(SignalKey, f64)>,
 }
 
 impl Module {
@@ -34,6 +38,8 @@ impl Module {
             signals: SlotMap::with_key(),
             raw_spice: None,
             timing: Default::default(),
+            ground_net: None,
+            supply_nets: Vec::new(),
         }
     }
 
@@ -130,6 +136,29 @@ impl Module {
         &mut self.timing
     }
 
+    #[inline]
+    pub(crate) fn set_ground_net(&mut self, signal: SignalKey) {
+        self.ground_net = Some(signal);
+    }
+
+    #[inline]
+    pub(crate) fn add_supply_net(&mut self, signal: SignalKey, voltage: f64) {
+        self.supply_nets.retain(|(key, _)| *key != signal);
+        self.supply_nets.push((signal, voltage));
+    }
+
+    /// Returns the name of the net marked as ground, if any.
+    pub fn ground_net(&self) -> Option<&ArcStr> {
+        self.ground_net.map(|key| self.signals[key].name())
+    }
+
+    /// Returns the names and voltages of the nets marked as supplies.
+    pub fn supply_nets(&self) -> impl Iterator<Item = (&ArcStr, f64)> {
+        self.supply_nets
+            .iter()
+            .map(|&(key, voltage)| (self.signals[key].name(), voltage))
+    }
+
     #[inline]
     pub fn id(&self) -> ModuleKey {
         self.id
@@ -0,0 +1,283 @@
+//! Structural comparison of netlists.
+
+use std::collections::HashMap;
+use std::fmt::Display;
+
+use slotmap::SlotMap;
+
+use super::preprocess::PreprocessedNetlist;
+use crate::deps::arcstr::ArcStr;
+use crate::schematic::circuit::{Instance, Reference};
+use crate::schematic::module::Module;
+use crate::schematic::signal::{Signal, SignalInfo, SignalKey};
+
+/// The differences between two netlists.
+///
+/// Modules are matched by name, except for the top modules,
+/// which are always compared with each other.
+/// Instances are matched by name within each module.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct NetlistDiff {
+    /// Modules present only in the new netlist.
+    pub added_modules: Vec<ArcStr>,
+    /// Modules present only in the old netlist.
+    pub removed_modules: Vec<ArcStr>,
+    /// Changes to modules present in both netlists.
+    pub modules: Vec<ModuleDiff>,
+}
+
+/// The differences between two versions of a module.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ModuleDiff {
+    /// The name of the module in the old netlist.
+    pub name: ArcStr,
+    /// The instance-level changes to the module.
+    pub changes: Vec<InstanceChange>,
+}
+
+/// A change to an instance within a module.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum InstanceChange {
+    /// An instance present only in the new module.
+    Added { instance: ArcStr, module: ArcStr },
+    /// An instance present only in the old module.
+    Removed { instance: ArcStr, module: ArcStr },
+    /// An instance whose reference module changed.
+    ModuleChanged {
+        instance: ArcStr,
+        old: ArcStr,
+        new: ArcStr,
+    },
+    /// An instance port whose connection changed.
+    ///
+    /// Connections are given as space-separated lists of signal bits.
+    /// A connection of `None` indicates that the port was unconnected.
+    ConnectionChanged {
+        instance: ArcStr,
+        port: ArcStr,
+        old: Option<String>,
+        new: Option<String>,
+    },
+}
+
+impl NetlistDiff {
+    /// Returns `true` if the two netlists are structurally identical.
+    pub fn is_empty(&self) -> bool {
+        self.added_modules.is_empty() && self.removed_modules.is_empty() && self.modules.is_empty()
+    }
+}
+
+impl Display for NetlistDiff {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for name in self.added_modules.iter() {
+            writeln!(f, "+ module {name}")?;
+        }
+        for name in self.removed_modules.iter() {
+            writeln!(f, "- module {name}")?;
+        }
+        for module in self.modules.iter() {
+            write!(f, "{module}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for ModuleDiff {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "module {}:", self.name)?;
+        for change in self.changes.iter() {
+            writeln!(f, "  {change}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for InstanceChange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let conn = |c: &Option<String>| c.clone().unwrap_or_else(|| "<unconnected>".to_string());
+        match self {
+            Self::Added { instance, module } => write!(f, "+ instance {instance} ({module})"),
+            Self::Removed { instance, module } => write!(f, "- instance {instance} ({module})"),
+            Self::ModuleChanged { instance, old, new } => {
+                write!(f, "~ instance {instance}: module {old} -> {new}")
+            }
+            Self::ConnectionChanged {
+                instance,
+                port,
+                old,
+                new,
+            } => write!(
+                f,
+                "~ instance {instance}, port {port}: {} -> {}",
+                conn(old),
+                conn(new)
+            ),
+        }
+    }
+}
+
+impl PreprocessedNetlist {
+    /// Computes the differences from `self` to `other`.
+    pub(crate) fn diff(&self, other: &PreprocessedNetlist) -> NetlistDiff {
+        let old_modules = self.modules_by_name();
+        let new_modules = other.modules_by_name();
+        let new_top = &other.modules[other.top];
+
+        let mut diff = NetlistDiff::default();
+
+        for module in other.ordered_modules() {
+            let matched =
+                matches!(old_modules.get(module.name()), Some(old) if old.id() != self.top);
+            if module.id() != other.top && !matched {
+                diff.added_modules.push(module.name().clone());
+            }
+        }
+
+        for old in self.ordered_modules() {
+            let new = if old.id() == self.top {
+                new_top
+            } else {
+                match new_modules.get(old.name()) {
+                    Some(&new) if new.id() != other.top => new,
+                    _ => {
+                        diff.removed_modules.push(old.name().clone());
+                        continue;
+                    }
+                }
+            };
+
+            let changes = diff_modules(self, old, other, new);
+            if !changes.is_empty() {
+                diff.modules.push(ModuleDiff {
+                    name: old.name().clone(),
+                    changes,
+                });
+            }
+        }
+
+        diff
+    }
+
+    fn ordered_modules(&self) -> impl Iterator<Item = &Module> {
+        self.netlist_order.iter().map(|&key| &self.modules[key])
+    }
+
+    fn modules_by_name(&self) -> HashMap<&ArcStr, &Module> {
+        self.ordered_modules()
+            .map(|module| (module.name(), module))
+            .collect()
+    }
+
+    fn reference_name(&self, reference: &Reference) -> ArcStr {
+        match reference {
+            Reference::Local(module) => self.modules[module.id].name().clone(),
+            Reference::External(name) => name.clone(),
+        }
+    }
+}
+
+fn diff_modules(
+    old_netlist: &PreprocessedNetlist,
+    old: &Module,
+    new_netlist: &PreprocessedNetlist,
+    new: &Module,
+) -> Vec<InstanceChange> {
+    let new_instances = new
+        .instances()
+        .map(|inst| (inst.name(), inst))
+        .collect::<HashMap<_, _>>();
+    let old_instances = old
+        .instances()
+        .map(|inst| (inst.name(), inst))
+        .collect::<HashMap<_, _>>();
+
+    let mut changes = Vec::new();
+
+    for old_inst in old.instances() {
+        let old_module = old_netlist.reference_name(&old_inst.module());
+        let new_inst = match new_instances.get(old_inst.name()) {
+            Some(&inst) => inst,
+            None => {
+                changes.push(InstanceChange::Removed {
+                    instance: old_inst.name().clone(),
+                    module: old_module,
+                });
+                continue;
+            }
+        };
+
+        let new_module = new_netlist.reference_name(&new_inst.module());
+        if old_module != new_module {
+            changes.push(InstanceChange::ModuleChanged {
+                instance: old_inst.name().clone(),
+                old: old_module,
+                new: new_module,
+            });
+        }
+
+        changes.extend(diff_connections(old, old_inst, new, new_inst));
+    }
+
+    for new_inst in new.instances() {
+        if !old_instances.contains_key(new_inst.name()) {
+            changes.push(InstanceChange::Added {
+                instance: new_inst.name().clone(),
+                module: new_netlist.reference_name(&new_inst.module()),
+            });
+        }
+    }
+
+    changes
+}
+
+fn diff_connections(
+    old: &Module,
+    old_inst: &Instance,
+    new: &Module,
+    new_inst: &Instance,
+) -> Vec<InstanceChange> {
+    let mut ports = old_inst
+        .connections()
+        .keys()
+        .chain(new_inst.connections().keys())
+        .collect::<Vec<_>>();
+    ports.sort();
+    ports.dedup();
+
+    ports
+        .into_iter()
+        .filter_map(|port| {
+            let old_conn = old_inst
+                .connections()
+                .get(port)
+                .map(|signal| signal_bits(signal, old.signals()));
+            let new_conn = new_inst
+                .connections()
+                .get(port)
+                .map(|signal| signal_bits(signal, new.signals()));
+            (old_conn != new_conn).then(|| InstanceChange::ConnectionChanged {
+                instance: old_inst.name().clone(),
+                port: port.clone(),
+                old: old_conn,
+                new: new_conn,
+            })
+        })
+        .collect()
+}
+
+/// Formats the bits of `signal` as a space-separated list, using the same
+/// naming convention as the SPICE netlister.
+fn signal_bits(signal: &Signal, signals: &SlotMap<SignalKey, SignalInfo>) -> String {
+    let mut bits = Vec::new();
+    for part in signal.parts() {
+        let info = &signals[part.signal()];
+        if info.width() == 1 {
+            bits.push(info.name().to_string());
+        } else {
+            for i in part.range() {
+                bits.push(format!("{}[{}]", info.name(), i));
+            }
+        }
+    }
+    bits.join(" ")
+}
@@ -4,18 +4,80 @@ use std::path::Path;
 
 use crate::fmt::signal::format_signal;
 use crate::schematic::netlist::interface::{
-    InstanceInfo, NetlistOpts, Netlister, Result, SubcircuitInfo,
+    InstanceInfo, NetlistMetadata, NetlistOpts, Netlister, Result, SubcircuitInfo,
 };
 
 /// A SPICE netlister.
 #[derive(Clone, Debug, Default)]
-pub struct SpiceNetlister;
+pub struct SpiceNetlister {
+    max_line_width: Option<usize>,
+    metadata: bool,
+}
 
 impl SpiceNetlister {
     /// Creates a new [`SpiceNetlister`].
+    ///
+    /// By default, each port of a subcircuit or instance
+    /// is written on its own `+` continuation line.
     #[inline]
     pub fn new() -> Self {
-        Self
+        Self::default()
+    }
+
+    /// Packs tokens onto as few lines as possible,
+    /// wrapping lines longer than `width` characters with `+` continuations.
+    ///
+    /// Tokens that are longer than `width` on their own are placed on a line by themselves.
+    #[inline]
+    pub fn with_max_line_width(mut self, width: usize) -> Self {
+        self.max_line_width = Some(width);
+        self
+    }
+
+    /// Emits header comments describing the component, parameters,
+    /// and Substrate version that produced each netlist.
+    #[inline]
+    pub fn with_metadata(mut self) -> Self {
+        self.metadata = true;
+        self
+    }
+
+    /// Returns the maximum line width, if one was configured.
+    #[inline]
+    pub fn max_line_width(&self) -> Option<usize> {
+        self.max_line_width
+    }
+
+    /// Writes `first` followed by `tokens`, breaking lines with `+` continuations.
+    fn emit_wrapped(
+        &self,
+        out: &mut dyn std::io::Write,
+        first: &str,
+        tokens: impl IntoIterator<Item = String>,
+    ) -> Result<()> {
+        let max = match self.max_line_width {
+            Some(max) => max,
+            None => {
+                writeln!(out, "{first}")?;
+                for token in tokens {
+                    writeln!(out, "+ {token}")?;
+                }
+                return Ok(());
+            }
+        };
+
+        let mut line = first.to_string();
+        for token in tokens {
+            if line.len() + 1 + token.len() > max {
+                writeln!(out, "{line}")?;
+                line = format!("+ {token}");
+            } else {
+                line.push(' ');
+                line.push_str(&token);
+            }
+        }
+        writeln!(out, "{line}")?;
+        Ok(())
     }
 }
 
@@ -35,22 +97,32 @@ impl Netlister for SpiceNetlister {
         Ok(())
     }
 
+    fn emit_metadata(&self, out: &mut dyn std::io::Write, metadata: NetlistMetadata) -> Result<()> {
+        if self.metadata {
+            self.emit_comment(out, &format!("Component: {}", metadata.component))?;
+            self.emit_comment(
+                out,
+                &format!("Parameter hash: {:016x}", metadata.params_hash),
+            )?;
+            self.emit_comment(out, &format!("Substrate version: {}", metadata.version))?;
+        }
+        Ok(())
+    }
+
     fn emit_begin_subcircuit(
         &self,
         out: &mut dyn std::io::Write,
         info: SubcircuitInfo,
     ) -> Result<()> {
-        writeln!(out, "\n.subckt {}", info.name)?;
-        for &port in info.ports {
-            let sig = &info.signals[port.signal];
-            for i in 0..sig.width() {
-                writeln!(
-                    out,
-                    "+ {}",
-                    format_signal(sig.name(), i, sig.width(), self.opts().bus_format)
-                )?;
-            }
-        }
+        let bus_format = self.opts().bus_format;
+        let signals = info.signals;
+        let tokens = info.ports.iter().flat_map(|port| {
+            let sig = &signals[port.signal];
+            (0..sig.width())
+                .map(move |i| format_signal(sig.name(), i, sig.width(), bus_format).to_string())
+        });
+        writeln!(out)?;
+        self.emit_wrapped(out, &format!(".subckt {}", info.name), tokens)?;
         // Write a newline
         writeln!(out)?;
         Ok(())
@@ -67,21 +139,21 @@ impl Netlister for SpiceNetlister {
     }
 
     fn emit_instance(&self, out: &mut dyn std::io::Write, instance: InstanceInfo) -> Result<()> {
-        writeln!(out, "X{}", instance.name)?;
+        let mut tokens = Vec::new();
         for &signal in instance.ports {
             for part in signal.parts() {
                 let info = &instance.signals[part.signal()];
                 if info.width() == 1 {
-                    writeln!(out, "+ {}", info.name())?;
+                    tokens.push(info.name().to_string());
                 } else {
                     for i in part.range() {
-                        writeln!(out, "+ {}[{}]", info.name(), i)?;
+                        tokens.push(format!("{}[{}]", info.name(), i));
                     }
                 }
             }
         }
-        writeln!(out, "+ {}", instance.subcircuit_name)?;
-        Ok(())
+        tokens.push(instance.subcircuit_name.to_string());
+        self.emit_wrapped(out, &format!("X{}", instance.name), tokens)
     }
 
     fn emit_include(&self, out: &mut dyn std::io::Write, include: &Path) -> Result<()> {
@@ -56,7 +56,7 @@ impl Display for NetlistFormat {
 }
 
 /// A trait representing the expected functionality of a netlister.
-pub trait Netlister {
+pub trait Netlister: Send + Sync {
     /// Returns [`NetlistOpts`] describing the output of the netlister.
     fn opts(&self) -> NetlistOpts {
         NetlistOpts::default()
@@ -92,6 +92,14 @@ pub trait Netlister {
         Ok(())
     }
 
+    /// Emits provenance information about the netlist to the provided output stream.
+    ///
+    /// Called after [`Netlister::emit_begin`]. Does nothing by default.
+    #[allow(unused_variables)]
+    fn emit_metadata(&self, out: &mut dyn Write, metadata: NetlistMetadata) -> Result<()> {
+        Ok(())
+    }
+
     /// Emits an epilogue to the provided output stream.
     #[allow(unused_variables)]
     fn emit_end(&self, out: &mut dyn Write) -> Result<()> {
@@ -99,6 +107,16 @@ pub trait Netlister {
     }
 }
 
+/// Provenance information describing how a netlist was generated.
+pub struct NetlistMetadata<'a> {
+    /// The name of the component that produced the top-level subcircuit.
+    pub component: &'a str,
+    /// A hash of the parameters of the top-level component.
+    pub params_hash: u64,
+    /// The version of Substrate that generated the netlist.
+    pub version: &'a str,
+}
+
 /// A description of a schematic instance.
 pub struct InstanceInfo<'a> {
     /// The instance name.
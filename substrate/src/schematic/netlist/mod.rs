@@ -8,6 +8,7 @@ use serde::{Deserialize, Serialize};
 use crate::deps::arcstr::ArcStr;
 use crate::pdk::corner::CornerEntry;
 
+pub mod diff;
 pub mod impls;
 pub mod interface;
 pub(crate) mod preprocess;
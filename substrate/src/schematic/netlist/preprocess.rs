@@ -1,6 +1,6 @@
 //! Preprocessing operations for netlist exporting.
 
-use std::collections::{HashSet, VecDeque};
+use std::collections::HashSet;
 
 use slotmap::SecondaryMap;
 
@@ -16,7 +16,6 @@ use crate::schematic::signal::SignalPathBuf;
 struct NetlistPreprocessor<'a> {
     data: &'a SchematicData,
     netlist_order: Vec<ModuleKey>,
-    queue: VecDeque<ModuleKey>,
     top: ModuleKey,
 
     modules: SecondaryMap<ModuleKey, Module>,
@@ -24,7 +23,10 @@ struct NetlistPreprocessor<'a> {
     mod_names: HashSet<ArcStr>,
 }
 
-/// The preprocessed netlist with deduplicated names and top-down ordering.
+/// The preprocessed netlist with deduplicated names and bottom-up ordering.
+///
+/// Every module in [`PreprocessedNetlist::netlist_order`] appears after all of the modules
+/// it instantiates, so the top module is always last.
 #[derive(Debug, Clone)]
 pub(crate) struct PreprocessedNetlist {
     pub(crate) modules: SecondaryMap<ModuleKey, Module>,
@@ -48,7 +50,6 @@ impl<'a> NetlistPreprocessor<'a> {
         Self {
             data,
             netlist_order: Vec::new(),
-            queue: VecDeque::new(),
             top,
             modules: SecondaryMap::new(),
             visited: SecondaryMap::new(),
@@ -58,7 +59,12 @@ impl<'a> NetlistPreprocessor<'a> {
 
     /// Preprocesses the netlist.
     pub fn preprocess(mut self) -> Result<PreprocessedNetlist> {
-        self.bfs()?;
+        // Reserve the top module's name so that it is never renamed,
+        // even though it is visited last.
+        let top = self.data.get_by_id(self.top)?;
+        self.mod_names.insert(top.name().clone());
+
+        self.visit(self.top)?;
         Ok(PreprocessedNetlist {
             modules: self.modules,
             netlist_order: self.netlist_order,
@@ -66,36 +72,36 @@ impl<'a> NetlistPreprocessor<'a> {
         })
     }
 
-    /// Makes a first pass rewriting of the module list.
+    /// Makes a first pass rewriting of the module list,
+    /// starting from the module with the given ID.
     ///
     /// Does the following:
     /// 1. Makes a list of all modules that are actually used by the top module
-    /// or its submodules.
+    /// or its submodules, ordered such that each module follows its submodules.
     /// 2. Rewrites duplicate module names.
     /// 3. Rewrites duplicate instance names within a module.
-    fn bfs(&mut self) -> Result<()> {
-        self.queue.push_back(self.top);
-        self.visited.insert(self.top, true);
-
-        while !self.queue.is_empty() {
-            // Cannot panic since we check that the queue is not empty
-            let id = self.queue.pop_front().unwrap();
-            let module = self.data.get_by_id(id)?;
-
-            for inst in module.instances() {
-                if let Reference::Local(module) = inst.module() {
-                    let id = module.id;
-                    if !self.visited.contains_key(id) {
-                        self.visited.insert(id, true);
-                        self.queue.push_back(id);
-                    }
+    ///
+    /// Submodules are visited in order of instance name,
+    /// so that the resulting order does not depend on instance insertion order.
+    fn visit(&mut self, id: ModuleKey) -> Result<()> {
+        self.visited.insert(id, true);
+        let module = self.data.get_by_id(id)?;
+
+        let mut instances = module.instances().collect::<Vec<_>>();
+        instances.sort_by(|a, b| a.name().cmp(b.name()));
+
+        for inst in instances {
+            if let Reference::Local(child) = inst.module() {
+                if !self.visited.contains_key(child.id) {
+                    self.visit(child.id)?;
                 }
             }
-            let module = self.fix_module_names(module);
-            self.modules.insert(id, module);
-            self.netlist_order.push(id);
         }
 
+        let module = self.fix_module_names(module);
+        self.modules.insert(id, module);
+        self.netlist_order.push(id);
+
         Ok(())
     }
 
@@ -109,8 +115,11 @@ impl<'a> NetlistPreprocessor<'a> {
 
     /// Renames a module if its current name is already taken,
     /// then adds the updated name to the list of used names ([`Self::mod_names`]).
+    ///
+    /// The top module is never renamed, since its name is reserved before any
+    /// other module is visited.
     fn save_and_rename_module(&mut self, module: &mut Module) {
-        if self.mod_names.contains(module.name()) {
+        if module.id() != self.top && self.mod_names.contains(module.name()) {
             let mut i = 1;
             let name = loop {
                 let name = arcstr::format!("{}_{}", module.name(), i);
@@ -349,6 +349,31 @@ impl Signal {
     pub fn width(&self) -> usize {
         self.parts.iter().map(Slice::width).sum()
     }
+
+    /// Returns an iterator over the individual bits of this signal.
+    pub(crate) fn bits(&self) -> impl Iterator<Item = SliceOne> + '_ {
+        self.parts.iter().flat_map(|part| {
+            part.range()
+                .into_iter()
+                .map(|idx| SliceOne::new(part.signal(), idx))
+        })
+    }
+
+    /// Creates a signal from its individual bits.
+    ///
+    /// Consecutive bits of the same underlying signal are merged into a single [`Slice`].
+    pub(crate) fn from_bits(bits: impl IntoIterator<Item = SliceOne>) -> Self {
+        let mut parts: Vec<Slice> = Vec::new();
+        for bit in bits {
+            match parts.last_mut() {
+                Some(last) if last.signal == bit.signal && last.range.end == bit.idx => {
+                    last.range.end += 1;
+                }
+                _ => parts.push(bit.into()),
+            }
+        }
+        Self { parts }
+    }
 }
 
 impl SignalPathBuf {
@@ -252,6 +252,18 @@ impl NetState {
         Self::default()
     }
 
+    /// Records a connection to a port with the given direction.
+    ///
+    /// The direction is taken from the perspective of the net,
+    /// so an [`Direction::Output`] port drives the net.
+    fn add(&mut self, direction: Direction) {
+        match direction {
+            Direction::Input => self.taps += 1,
+            Direction::Output => self.drivers += 1,
+            Direction::InOut => self.inouts += 1,
+        };
+    }
+
     /// Returns the number of connections to the net.
     fn degree(&self) -> usize {
         self.drivers + self.taps + self.inouts
@@ -316,11 +328,8 @@ impl<'a> DriverValidator<'a> {
 
         for port in module.ports() {
             for net in net_states[port.signal].iter_mut() {
-                match port.direction {
-                    Direction::Input => net.drivers += 1,
-                    Direction::Output => net.taps += 1,
-                    Direction::InOut => net.inouts += 1,
-                };
+                // Ports of the module under validation are seen from the inside.
+                net.add(port.direction.flip());
             }
         }
 
@@ -371,12 +380,7 @@ impl<'a> DriverValidator<'a> {
             });
             for part in connected_to.parts() {
                 for idx in part.range() {
-                    let state = &mut net_states[part.signal()][idx];
-                    match port.direction {
-                        Direction::Input => state.taps += 1,
-                        Direction::Output => state.drivers += 1,
-                        Direction::InOut => state.inouts += 1,
-                    };
+                    net_states[part.signal()][idx].add(port.direction);
                 }
             }
         }
@@ -25,6 +25,16 @@ pub enum SearchSide {
     /// Only accept elements for which the predicate returned [`SearchRange::Equal`].
     #[default]
     Equal,
+    /// Return whichever of the elements bracketing the target is nearest to it.
+    ///
+    /// If the target lies outside the searched range, the element at the nearest
+    /// end of the range is returned.
+    ///
+    /// [`search`] has no notion of distance, so it returns the [`SearchSide::Before`]
+    /// element if one exists, and the [`SearchSide::After`] element otherwise.
+    /// Use [`search_nearest`] to choose between the bracketing elements using a
+    /// distance metric.
+    Nearest,
 }
 
 impl From<std::cmp::Ordering> for SearchRange {
@@ -63,7 +73,7 @@ where
     }
     if predicate(&lst[lo]) == SearchRange::Down {
         return match side {
-            SearchSide::After => Some((lo, &lst[lo])),
+            SearchSide::After | SearchSide::Nearest => Some((lo, &lst[lo])),
             _ => None,
         };
     }
@@ -91,7 +101,7 @@ where
         return Some((lo, &lst[lo]));
     }
     match side {
-        SearchSide::Before => Some((lo, &lst[lo])),
+        SearchSide::Before | SearchSide::Nearest => Some((lo, &lst[lo])),
         SearchSide::After => {
             if hi < initial_hi {
                 Some((hi, &lst[hi]))
@@ -103,6 +113,52 @@ where
     }
 }
 
+/// Searches for the element nearest to a target.
+///
+/// The `predicate` locates the target as in [`search`], and `metric` returns the
+/// distance between an element and the target. If the two elements bracketing the
+/// target are equally distant, the element before the target is returned.
+#[inline]
+pub fn search_nearest<T, P, M>(lst: &[T], predicate: P, metric: M) -> Option<(usize, &T)>
+where
+    P: FnMut(&T) -> SearchRange,
+    M: FnMut(&T) -> f64,
+{
+    search_nearest_in_range(lst, predicate, metric, 0, lst.len())
+}
+
+/// Searches for the element nearest to a target within the indices `lo..hi`.
+///
+/// See [`search_nearest`] for details.
+pub fn search_nearest_in_range<V, P, M>(
+    lst: &V,
+    mut predicate: P,
+    mut metric: M,
+    lo: usize,
+    hi: usize,
+) -> Option<(usize, &V::Output)>
+where
+    V: Index<usize> + ?Sized,
+    P: FnMut(&V::Output) -> SearchRange,
+    M: FnMut(&V::Output) -> f64,
+{
+    let (idx, before) = match search_in_range(lst, &mut predicate, SearchSide::Before, lo, hi) {
+        Some(result) => result,
+        None => return search_in_range(lst, &mut predicate, SearchSide::After, lo, hi),
+    };
+
+    if idx + 1 >= hi || predicate(before) == SearchRange::Equal {
+        return Some((idx, before));
+    }
+
+    let after = &lst[idx + 1];
+    if metric(after) < metric(before) {
+        Some((idx + 1, after))
+    } else {
+        Some((idx, before))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -137,4 +193,57 @@ mod tests {
         let result = search(&v, |e| e.cmp(&10).into(), SearchSide::Equal);
         assert!(result.is_none());
     }
+
+    #[test]
+    fn test_search_nearest() {
+        let v = vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 12, 14, 15];
+        let nearest = |target: i32| {
+            search_nearest(&v, |e| e.cmp(&target).into(), |e| (e - target).abs() as f64)
+                .map(|(idx, val)| (idx, *val))
+        };
+
+        assert_eq!(nearest(11), Some((9, 12)));
+        assert_eq!(nearest(10), Some((8, 9)));
+        // Ties are broken in favor of the element before the target.
+        let v = vec![10, 20];
+        let (idx, _) =
+            search_nearest(&v, |e| e.cmp(&15).into(), |e| (e - 15).abs() as f64).unwrap();
+        assert_eq!(idx, 0);
+    }
+
+    #[test]
+    fn test_search_nearest_extremes() {
+        let v = vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 12, 14, 15];
+        let metric = |target: i32| move |e: &i32| (e - target).abs() as f64;
+
+        // Exact matches.
+        for (idx, target) in v.iter().copied().enumerate() {
+            let result = search_nearest(&v, |e| e.cmp(&target).into(), metric(target));
+            assert_eq!(result, Some((idx, &target)));
+        }
+
+        // Below the first element, the nearest element is the one after the target.
+        let after = search(&v, |e| e.cmp(&-5).into(), SearchSide::After);
+        assert_eq!(after, Some((0, &1)));
+        assert_eq!(search_nearest(&v, |e| e.cmp(&-5).into(), metric(-5)), after);
+        assert_eq!(
+            search(&v, |e| e.cmp(&-5).into(), SearchSide::Nearest),
+            after
+        );
+
+        // Above the last element, the nearest element is the one before the target.
+        let before = search(&v, |e| e.cmp(&20).into(), SearchSide::Before);
+        assert_eq!(before, Some((11, &15)));
+        assert_eq!(
+            search_nearest(&v, |e| e.cmp(&20).into(), metric(20)),
+            before
+        );
+        assert_eq!(
+            search(&v, |e| e.cmp(&20).into(), SearchSide::Nearest),
+            before
+        );
+
+        let empty: Vec<i32> = Vec::new();
+        assert!(search_nearest(&empty, |e| e.cmp(&0).into(), metric(0)).is_none());
+    }
 }
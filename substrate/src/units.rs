@@ -27,6 +27,18 @@ impl SiValue {
         self.prefix
     }
 
+    /// Returns `true` if this value is zero, regardless of its prefix.
+    #[inline]
+    pub fn is_zero(&self) -> bool {
+        self.value == 0
+    }
+
+    /// Returns `true` if this value is strictly less than zero.
+    #[inline]
+    pub fn is_negative(&self) -> bool {
+        self.value < 0
+    }
+
     /// Creates a new [`SiValue`] by rounding to the given precision.
     ///
     /// For example, if [`SiPrefix::Micro`] is given, and `value` is given in Volts,
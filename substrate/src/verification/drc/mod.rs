@@ -1,6 +1,7 @@
 //! DRC plugin API.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::iter::Peekable;
 use std::path::PathBuf;
 
 use serde::{Deserialize, Serialize};
@@ -8,6 +9,10 @@ use serde::{Deserialize, Serialize};
 use crate::deps::arcstr::ArcStr;
 use crate::error::Result;
 use crate::layout::LayoutFormat;
+use crate::verification::CleanupPolicy;
+
+#[cfg(test)]
+mod tests;
 
 /// Inputs passed to a [`DrcTool`].
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
@@ -22,6 +27,26 @@ pub struct DrcInput {
     pub layout_format: LayoutFormat,
     /// Unstructured options.
     pub opts: HashMap<ArcStr, ArcStr>,
+    /// Names of DRC rules whose violations should be ignored.
+    #[serde(default)]
+    pub waivers: Vec<String>,
+    /// The names of the rule checks to run.
+    ///
+    /// If [`None`], every rule check in the rule deck is run.
+    #[serde(default)]
+    pub rules: Option<Vec<ArcStr>>,
+    /// The number of groups to partition the rule deck into.
+    ///
+    /// If greater than 1, [`run_partitioned`] runs each group of rule checks concurrently
+    /// in its own subdirectory of `work_dir` and merges the results.
+    /// Values of 0 and 1 run the whole deck at once.
+    #[serde(default)]
+    pub partitions: usize,
+    /// When to remove intermediate files from `work_dir` after the run.
+    ///
+    /// Reports listed by [`DrcTool::report_paths`] are kept.
+    #[serde(default)]
+    pub cleanup: CleanupPolicy,
 }
 
 /// An enumeration describing the high-level result of a DRC run.
@@ -45,11 +70,28 @@ impl DrcSummary {
     }
 }
 
+/// The severity of a [`DrcError`].
+#[derive(
+    Copy, Clone, Debug, Default, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize,
+)]
+pub enum DrcSeverity {
+    /// An informational message that does not affect the result of the DRC run.
+    Info,
+    /// A warning that does not cause the DRC run to fail.
+    Warning,
+    /// A violation that causes the DRC run to fail.
+    #[default]
+    Error,
+}
+
 /// A DRC error.
 #[derive(Clone, Debug, Default, Eq, PartialEq, Hash, Serialize, Deserialize)]
 pub struct DrcError {
     /// The name of the error.
     pub name: ArcStr,
+    /// The severity of the error.
+    #[serde(default)]
+    pub severity: DrcSeverity,
     /// An optional description of the error.
     pub desc: Option<ArcStr>,
     /// The Cartesian coordinates of the error.
@@ -65,8 +107,319 @@ pub struct DrcOutput {
     pub errors: Vec<DrcError>,
 }
 
+impl DrcOutput {
+    /// Merges the outputs of several DRC runs, such as runs on disjoint
+    /// groups of rules from the same rule deck.
+    ///
+    /// The merged run passes only if every run passed: the merged summary is
+    /// the worst of the individual summaries. Errors already reported by an
+    /// earlier run are skipped, but repeated errors within a single run are kept.
+    /// An empty set of outputs is treated as a pass.
+    pub fn merge(outputs: impl IntoIterator<Item = DrcOutput>) -> Self {
+        let mut summary = DrcSummary::Pass;
+        let mut errors = Vec::new();
+        let mut seen = HashSet::new();
+
+        for output in outputs {
+            summary = match (summary, output.summary) {
+                (DrcSummary::Fail, _) | (_, DrcSummary::Fail) => DrcSummary::Fail,
+                (DrcSummary::Warn, _) | (_, DrcSummary::Warn) => DrcSummary::Warn,
+                (DrcSummary::Pass, DrcSummary::Pass) => DrcSummary::Pass,
+            };
+            let start = errors.len();
+            errors.extend(
+                output
+                    .errors
+                    .into_iter()
+                    .filter(|error| !seen.contains(error)),
+            );
+            seen.extend(errors[start..].iter().cloned());
+        }
+
+        Self { summary, errors }
+    }
+
+    /// Removes errors whose names match one of the given waivers.
+    ///
+    /// If any errors are waived, the summary is recomputed from the severities
+    /// of the remaining errors: [`DrcSummary::Fail`] if any remaining error has
+    /// severity [`DrcSeverity::Error`], [`DrcSummary::Warn`] if any has severity
+    /// [`DrcSeverity::Warning`], and [`DrcSummary::Pass`] otherwise.
+    pub fn apply_waivers(&mut self, waivers: &[impl AsRef<str>]) {
+        let len = self.errors.len();
+        self.errors
+            .retain(|e| !waivers.iter().any(|w| w.as_ref() == e.name.as_str()));
+
+        if self.errors.len() == len {
+            return;
+        }
+
+        self.summary = match self.errors.iter().map(|e| e.severity).max() {
+            Some(DrcSeverity::Error) => DrcSummary::Fail,
+            Some(DrcSeverity::Warning) => DrcSummary::Warn,
+            Some(DrcSeverity::Info) | None => DrcSummary::Pass,
+        };
+    }
+
+    /// Parses the violations recorded in a Calibre ASCII results database.
+    ///
+    /// Each violation marker becomes its own [`DrcError`], named after its rule check.
+    /// Errors are located at the center of their marker's bounding box, in database units.
+    /// The rule check's text, excluding the rule file path, becomes the error's description.
+    /// The run fails if there are any violations.
+    ///
+    /// Returns [`None`] if the results database is malformed.
+    pub fn parse_calibre_results(results: &str) -> Option<Self> {
+        let mut lines = results.lines().peekable();
+        // The header contains the top cell name and database precision.
+        lines.find(|line| !line.trim().is_empty())?;
+
+        let mut errors = Vec::new();
+        loop {
+            let name = match lines.find(|line| !line.trim().is_empty()) {
+                Some(name) => name.trim(),
+                None => break,
+            };
+            let counts = lines
+                .next()?
+                .split_whitespace()
+                .take(3)
+                .map(|count| count.parse().ok())
+                .collect::<Option<Vec<usize>>>()?;
+            let (num_results, num_text_lines) = match counts.as_slice() {
+                [current, _, text] => (*current, *text),
+                _ => return None,
+            };
+
+            let mut desc = Vec::new();
+            for _ in 0..num_text_lines {
+                let line = lines.next()?.trim();
+                if !line.is_empty() && !line.starts_with("Rule File Pathname:") {
+                    desc.push(line);
+                }
+            }
+            let desc: Option<ArcStr> = (!desc.is_empty()).then(|| desc.join("\n").into());
+
+            for _ in 0..num_results {
+                errors.push(DrcError {
+                    name: name.into(),
+                    severity: DrcSeverity::Error,
+                    desc: desc.clone(),
+                    location: Some(parse_calibre_marker(&mut lines)?),
+                });
+            }
+        }
+
+        let summary = if errors.is_empty() {
+            DrcSummary::Pass
+        } else {
+            DrcSummary::Fail
+        };
+        Some(Self { summary, errors })
+    }
+}
+
+/// Parses a polygon (`p`) or edge (`e`) violation marker,
+/// returning the center of its bounding box in top-level coordinates.
+fn parse_calibre_marker<'a>(
+    lines: &mut Peekable<impl Iterator<Item = &'a str>>,
+) -> Option<(i64, i64)> {
+    let mut header = lines.next()?.split_whitespace();
+    let kind = header.next()?;
+    let num_lines: usize = header.nth(1)?.parse().ok()?;
+
+    // Hierarchical results name the cell containing the marker.
+    let transform = if lines.peek()?.starts_with("CN ") {
+        parse_calibre_cell_transform(lines.next()?)?
+    } else {
+        None
+    };
+
+    let mut points = Vec::new();
+    for _ in 0..num_lines {
+        let coords = lines
+            .next()?
+            .split_whitespace()
+            .map(|coord| coord.parse().ok())
+            .collect::<Option<Vec<i64>>>()?;
+        match (kind, coords.as_slice()) {
+            ("p", [x, y]) => points.push((*x, *y)),
+            ("e", [x0, y0, x1, y1]) => points.extend([(*x0, *y0), (*x1, *y1)]),
+            _ => return None,
+        }
+    }
+
+    if let Some([a, b, c, d, tx, ty]) = transform {
+        for (x, y) in points.iter_mut() {
+            let (xf, yf) = (*x as f64, *y as f64);
+            *x = (a * xf + b * yf + tx).round() as i64;
+            *y = (c * xf + d * yf + ty).round() as i64;
+        }
+    }
+
+    let (xmin, xmax) = (
+        points.iter().map(|p| p.0).min()?,
+        points.iter().map(|p| p.0).max()?,
+    );
+    let (ymin, ymax) = (
+        points.iter().map(|p| p.1).min()?,
+        points.iter().map(|p| p.1).max()?,
+    );
+    Some(((xmin + xmax) / 2, (ymin + ymax) / 2))
+}
+
+/// Parses a `CN <cell> [c] a b c d tx ty` line.
+///
+/// If the `c` flag is present, the marker's coordinates are in the cell's coordinate space,
+/// and the returned transformation `[a, b, c, d, tx, ty]` maps a cell point `(x, y)`
+/// to the top-level point `(a x + b y + tx, c x + d y + ty)`.
+/// Otherwise, the coordinates are already top-level and [`None`] is returned.
+fn parse_calibre_cell_transform(line: &str) -> Option<Option<[f64; 6]>> {
+    let mut fields = line.split_whitespace().skip(2).peekable();
+    if fields.peek() != Some(&"c") {
+        return Some(None);
+    }
+    fields.next();
+    let values = fields
+        .map(|value| value.parse().ok())
+        .collect::<Option<Vec<f64>>>()?;
+    Some(Some(values.try_into().ok()?))
+}
+
+/// Returns the names of the rule checks in an SVRF rule deck, such as a Calibre rule file,
+/// in the order they appear.
+///
+/// Rule checks are top-level blocks of the form `NAME { ... }`. Comments and quoted strings
+/// are ignored, as are statements outside of rule checks, such as layer definitions.
+pub fn svrf_rule_checks(deck: &str) -> Vec<ArcStr> {
+    let mut checks = Vec::new();
+    let mut depth = 0usize;
+    let mut statement = String::new();
+
+    for line in deck.lines() {
+        let line = line.split("//").next().unwrap_or_default();
+        let mut in_string = false;
+        for c in line.chars() {
+            match c {
+                '"' => in_string = !in_string,
+                _ if in_string => {}
+                '{' => {
+                    if depth == 0 {
+                        if let Some(name) = statement.split_whitespace().last() {
+                            checks.push(name.into());
+                        }
+                    }
+                    depth += 1;
+                }
+                '}' => depth = depth.saturating_sub(1),
+                _ if depth == 0 => statement.push(c),
+                _ => {}
+            }
+        }
+        statement.clear();
+    }
+
+    checks
+}
+
+/// Splits `rules` into at most `n` contiguous groups of nearly equal size.
+///
+/// Earlier groups receive any remaining rules. No group is empty.
+pub fn partition_rules(rules: &[ArcStr], n: usize) -> Vec<Vec<ArcStr>> {
+    let n = n.clamp(1, rules.len().max(1));
+    let mut rest = rules;
+    (0..n)
+        .map(|i| {
+            let len = rules.len() / n + usize::from(i < rules.len() % n);
+            let (group, tail) = rest.split_at(len);
+            rest = tail;
+            group.to_vec()
+        })
+        .filter(|group| !group.is_empty())
+        .collect()
+}
+
+/// Runs DRC on `input` using `tool`, partitioning the rule deck as requested
+/// by [`DrcInput::partitions`].
+///
+/// The rule checks in [`DrcInput::rules`], or all rule checks reported by
+/// [`DrcTool::rule_checks`] if no rules are given, are split into groups with
+/// [`partition_rules`]. Each group is run concurrently in the subdirectory
+/// `partition_{i}` of the input's working directory, and the results are combined
+/// with [`DrcOutput::merge`]. The run passes only if every partition passes.
+///
+/// If the input requests at most one partition, or the tool cannot list its rule checks,
+/// the whole deck is run at once.
+pub fn run_partitioned(tool: &dyn DrcTool, input: DrcInput) -> Result<DrcOutput> {
+    if input.partitions <= 1 {
+        return tool.run_drc(input);
+    }
+    let rules = match input.rules.clone() {
+        Some(rules) => rules,
+        None => match tool.rule_checks(&input)? {
+            Some(rules) => rules,
+            None => return tool.run_drc(input),
+        },
+    };
+    let groups = partition_rules(&rules, input.partitions);
+    if groups.len() <= 1 {
+        return tool.run_drc(input);
+    }
+
+    let outputs = std::thread::scope(|s| {
+        let handles = groups
+            .into_iter()
+            .enumerate()
+            .map(|(i, group)| {
+                let partition = DrcInput {
+                    work_dir: input.work_dir.join(format!("partition_{i}")),
+                    rules: Some(group),
+                    partitions: 1,
+                    ..input.clone()
+                };
+                s.spawn(move || tool.run_drc(partition))
+            })
+            .collect::<Vec<_>>();
+        handles
+            .into_iter()
+            .map(|handle| handle.join().expect("DRC partition thread panicked"))
+            .collect::<Result<Vec<_>>>()
+    })?;
+
+    Ok(DrcOutput::merge(outputs))
+}
+
 /// The trait that DRC plugins must implement.
-pub trait DrcTool {
+pub trait DrcTool: Send + Sync {
     /// Runs the DRC tool on the provided input files.
+    ///
+    /// If [`DrcInput::rules`] is given, only those rule checks should be run.
     fn run_drc(&self, input: DrcInput) -> Result<DrcOutput>;
+
+    /// Returns the names of the rule checks in the rule deck used for `input`, in deck order.
+    ///
+    /// Used by [`run_partitioned`] to split the deck into groups.
+    /// Tools for SVRF rule decks can use [`svrf_rule_checks`].
+    ///
+    /// Defaults to [`None`], in which case the deck is never partitioned.
+    fn rule_checks(&self, _input: &DrcInput) -> Result<Option<Vec<ArcStr>>> {
+        Ok(None)
+    }
+
+    /// Returns the paths of the reports written for `input`, relative to its working directory.
+    ///
+    /// These files are kept when intermediate files are removed according to
+    /// [`DrcInput::cleanup`], including in the working directory of each partition.
+    ///
+    /// Defaults to an empty list.
+    fn report_paths(&self, _input: &DrcInput) -> Vec<PathBuf> {
+        Vec::new()
+    }
+
+    /// Returns the layout format this tool expects its input layout to be written in.
+    ///
+    /// Defaults to [`LayoutFormat::Gds`].
+    fn layout_format(&self) -> LayoutFormat {
+        LayoutFormat::Gds
+    }
 }
@@ -0,0 +1,311 @@
+use std::sync::Mutex;
+
+use super::*;
+
+const CALIBRE_RESULTS: &str = r#"
+TOP 1000
+M1.S.1
+2 2 3 Oct 14 10:00:00 2026
+Rule File Pathname: /pdk/calibre/drc.rul
+M1.S.1 { @ Minimum metal 1 spacing is 0.14um
+  EXT M1 < 0.14 ABUT < 90 SINGULAR REGION }
+p 1 4
+0 0
+100 0
+100 50
+0 50
+e 2 1
+CN inv c 1 0 0 1 0 0
+200 300 400 300
+M2.W.1
+0 0 2 Oct 14 10:00:00 2026
+Rule File Pathname: /pdk/calibre/drc.rul
+M2.W.1 { @ Minimum metal 2 width is 0.14um }
+"#;
+
+fn error(name: &str, severity: DrcSeverity) -> DrcError {
+    DrcError {
+        name: name.into(),
+        severity,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn waiving_only_failing_rule_passes() {
+    let mut output = DrcOutput {
+        summary: DrcSummary::Fail,
+        errors: vec![error("m1.density", DrcSeverity::Error)],
+    };
+    output.apply_waivers(&["m1.density"]);
+    assert_eq!(output.summary, DrcSummary::Pass);
+    assert!(output.errors.is_empty());
+}
+
+#[test]
+fn waivers_downgrade_summary() {
+    let errors = vec![
+        error("m1.density", DrcSeverity::Error),
+        error("m2.density", DrcSeverity::Warning),
+        error("antenna", DrcSeverity::Info),
+    ];
+    let mut output = DrcOutput {
+        summary: DrcSummary::Fail,
+        errors: errors.clone(),
+    };
+    output.apply_waivers(&["m1.density".to_string()]);
+    assert_eq!(output.summary, DrcSummary::Warn);
+    assert_eq!(output.errors.len(), 2);
+
+    let mut output = DrcOutput {
+        summary: DrcSummary::Fail,
+        errors,
+    };
+    output.apply_waivers(&["m2.density"]);
+    assert_eq!(output.summary, DrcSummary::Fail);
+    assert_eq!(output.errors.len(), 2);
+}
+
+#[test]
+fn unmatched_waivers_keep_summary() {
+    let mut output = DrcOutput {
+        summary: DrcSummary::Fail,
+        errors: Vec::new(),
+    };
+    output.apply_waivers(&["m1.density"]);
+    assert_eq!(output.summary, DrcSummary::Fail);
+}
+
+#[test]
+fn merge_rule_groups() {
+    let density = error("m1.density", DrcSeverity::Error);
+    let spacing = DrcError {
+        location: Some((100, 200)),
+        ..error("m1.spacing", DrcSeverity::Error)
+    };
+    let groups = vec![
+        DrcOutput {
+            summary: DrcSummary::Pass,
+            errors: Vec::new(),
+        },
+        DrcOutput {
+            summary: DrcSummary::Warn,
+            errors: vec![error("antenna", DrcSeverity::Warning)],
+        },
+        // The same rule may legitimately be violated twice within a group.
+        DrcOutput {
+            summary: DrcSummary::Fail,
+            errors: vec![density.clone(), spacing.clone(), density.clone()],
+        },
+        // A rule shared between groups reports the same violation twice.
+        DrcOutput {
+            summary: DrcSummary::Fail,
+            errors: vec![density.clone()],
+        },
+    ];
+
+    let merged = DrcOutput::merge(groups.clone());
+    assert_eq!(merged.summary, DrcSummary::Fail);
+    assert_eq!(
+        merged.errors,
+        vec![
+            error("antenna", DrcSeverity::Warning),
+            density.clone(),
+            spacing,
+            density
+        ]
+    );
+
+    let merged = DrcOutput::merge(groups.into_iter().take(2));
+    assert_eq!(merged.summary, DrcSummary::Warn);
+    assert_eq!(DrcOutput::merge([]).summary, DrcSummary::Pass);
+}
+
+#[test]
+fn parse_calibre_results() {
+    let output = DrcOutput::parse_calibre_results(CALIBRE_RESULTS).unwrap();
+    assert_eq!(output.summary, DrcSummary::Fail);
+
+    let desc: ArcStr =
+        "M1.S.1 { @ Minimum metal 1 spacing is 0.14um\nEXT M1 < 0.14 ABUT < 90 SINGULAR REGION }"
+            .into();
+    assert_eq!(
+        output.errors,
+        vec![
+            DrcError {
+                desc: Some(desc.clone()),
+                location: Some((50, 25)),
+                ..error("M1.S.1", DrcSeverity::Error)
+            },
+            DrcError {
+                desc: Some(desc),
+                location: Some((300, 300)),
+                ..error("M1.S.1", DrcSeverity::Error)
+            },
+        ]
+    );
+}
+
+#[test]
+fn parse_hierarchical_calibre_results() {
+    // The first marker is in a cell rotated by 90 degrees and translated by (1000, 2000).
+    // The second marker's coordinates are already top-level.
+    let results = r#"
+TOP 1000
+M1.S.1
+2 2 1
+M1.S.1 { @ Minimum metal 1 spacing is 0.14um }
+p 1 4
+CN inv c 0 -1 1 0 1000 2000
+0 0
+100 0
+100 50
+0 50
+p 2 4
+CN inv 0 -1 1 0 1000 2000
+0 0
+100 0
+100 50
+0 50
+"#;
+    let output = DrcOutput::parse_calibre_results(results).unwrap();
+    assert_eq!(
+        output
+            .errors
+            .iter()
+            .map(|error| error.location)
+            .collect::<Vec<_>>(),
+        vec![Some((975, 2050)), Some((50, 25))]
+    );
+}
+
+#[test]
+fn parse_clean_calibre_results() {
+    let output = DrcOutput::parse_calibre_results("TOP 1000\n").unwrap();
+    assert_eq!(output.summary, DrcSummary::Pass);
+    assert!(output.errors.is_empty());
+
+    assert_eq!(
+        DrcOutput::parse_calibre_results("TOP 1000\nM1.S.1\n1 1 0\np 1 2\n0 0\n"),
+        None
+    );
+}
+
+const MOCK_DECK: &str = r#"
+// Layer definitions are shared by every rule check.
+LAYER M1 68
+LAYER M2 69
+INCLUDE "common.svrf"
+
+M1.W.1 { @ Minimum metal 1 width is 0.14um
+  INT M1 < 0.14 ABUT < 90 SINGULAR REGION
+}
+M1.S.1 { @ Minimum metal 1 spacing is 0.14um
+  EXT M1 < 0.14 ABUT < 90 SINGULAR REGION }
+M2.W.1 { INT M2 < 0.14 } // Minimum metal 2 width.
+M2.S.1 {
+  @ Minimum metal 2 spacing { nested braces } are ignored
+  EXT M2 < 0.14
+}
+M2.A.1 { AREA M2 < 0.0676 }
+"#;
+
+/// A DRC tool that runs [`MOCK_DECK`], reporting a violation of every metal 1 rule.
+///
+/// Every run also reports the same deck-level warning, as real tools do
+/// for checks that are not part of any rule group.
+#[derive(Default)]
+struct MockDrcTool {
+    work_dirs: Mutex<Vec<PathBuf>>,
+}
+
+impl DrcTool for MockDrcTool {
+    fn run_drc(&self, input: DrcInput) -> Result<DrcOutput> {
+        self.work_dirs.lock().unwrap().push(input.work_dir.clone());
+        let rules = input.rules.unwrap_or_else(|| svrf_rule_checks(MOCK_DECK));
+        let mut errors = vec![error("deck.precision", DrcSeverity::Warning)];
+        errors.extend(
+            rules
+                .iter()
+                .filter(|rule| rule.starts_with("M1"))
+                .map(|rule| error(rule, DrcSeverity::Error)),
+        );
+        let summary = if errors.iter().any(|e| e.severity == DrcSeverity::Error) {
+            DrcSummary::Fail
+        } else {
+            DrcSummary::Warn
+        };
+        Ok(DrcOutput { summary, errors })
+    }
+
+    fn rule_checks(&self, _input: &DrcInput) -> Result<Option<Vec<ArcStr>>> {
+        Ok(Some(svrf_rule_checks(MOCK_DECK)))
+    }
+}
+
+fn mock_input(partitions: usize) -> DrcInput {
+    DrcInput {
+        cell_name: "top".into(),
+        work_dir: PathBuf::from("drc"),
+        layout_path: PathBuf::from("drc/layout.gds"),
+        layout_format: LayoutFormat::Gds,
+        opts: HashMap::new(),
+        waivers: Vec::new(),
+        rules: None,
+        partitions,
+        cleanup: CleanupPolicy::Never,
+    }
+}
+
+#[test]
+fn parse_svrf_rule_checks() {
+    assert_eq!(
+        svrf_rule_checks(MOCK_DECK),
+        vec!["M1.W.1", "M1.S.1", "M2.W.1", "M2.S.1", "M2.A.1"]
+    );
+}
+
+#[test]
+fn partition_rule_groups() {
+    let rules = svrf_rule_checks(MOCK_DECK);
+    let groups = partition_rules(&rules, 3);
+    assert_eq!(
+        groups,
+        vec![
+            vec!["M1.W.1", "M1.S.1"],
+            vec!["M2.W.1", "M2.S.1"],
+            vec!["M2.A.1"]
+        ]
+    );
+    assert_eq!(partition_rules(&rules, 8).len(), 5);
+    assert_eq!(partition_rules(&rules, 0), vec![rules]);
+}
+
+#[test]
+fn run_partitioned_mock_deck() {
+    let tool = MockDrcTool::default();
+    let serial = run_partitioned(&tool, mock_input(1)).unwrap();
+    assert_eq!(*tool.work_dirs.lock().unwrap(), vec![PathBuf::from("drc")]);
+
+    let tool = MockDrcTool::default();
+    let partitioned = run_partitioned(&tool, mock_input(3)).unwrap();
+    let mut work_dirs = tool.work_dirs.lock().unwrap().clone();
+    work_dirs.sort();
+    assert_eq!(
+        work_dirs,
+        ["partition_0", "partition_1", "partition_2"].map(|dir| PathBuf::from("drc").join(dir))
+    );
+
+    // Only the first partition contains failing rules, but the merged run still fails,
+    // and the warning reported by every partition is only counted once.
+    assert_eq!(partitioned, serial);
+    assert_eq!(partitioned.summary, DrcSummary::Fail);
+    assert_eq!(
+        partitioned.errors,
+        vec![
+            error("deck.precision", DrcSeverity::Warning),
+            error("M1.W.1", DrcSeverity::Error),
+            error("M1.S.1", DrcSeverity::Error),
+        ]
+    );
+}
@@ -8,6 +8,11 @@ use serde::{Deserialize, Serialize};
 use crate::deps::arcstr::ArcStr;
 use crate::error::Result;
 use crate::layout::LayoutFormat;
+use crate::pdk::DeviceClass;
+use crate::verification::CleanupPolicy;
+
+#[cfg(test)]
+mod tests;
 
 /// Inputs passed to a [`LvsTool`].
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
@@ -26,6 +31,17 @@ pub struct LvsInput {
     pub source_cell_name: ArcStr,
     /// Unstructured options.
     pub opts: HashMap<ArcStr, ArcStr>,
+    /// The name of the ground net, if known.
+    #[serde(default)]
+    pub ground_net: Option<String>,
+    /// The names of the supply nets.
+    #[serde(default)]
+    pub supply_nets: Vec<String>,
+    /// When to remove intermediate files from `work_dir` after the run.
+    ///
+    /// Reports listed by [`LvsTool::report_paths`] are kept.
+    #[serde(default)]
+    pub cleanup: CleanupPolicy,
 }
 
 /// An enumeration describing the high-level result of a LVS run.
@@ -65,10 +81,171 @@ pub struct LvsOutput {
     pub summary: LvsSummary,
     /// A list of errors encountered during the LVS run.
     pub errors: Vec<LvsError>,
+    /// Layout and schematic object counts, if reported by the LVS tool.
+    #[serde(default)]
+    pub stats: Option<LvsStats>,
+}
+
+/// Layout and schematic object counts reported by an LVS run.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct LvsStats {
+    /// The number of nets in the layout.
+    pub layout_nets: usize,
+    /// The number of nets in the schematic.
+    pub source_nets: usize,
+    /// Device counts for each device type.
+    pub devices: Vec<LvsDeviceCount>,
+}
+
+/// The number of devices of a given type in the layout and schematic.
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct LvsDeviceCount {
+    /// The device type, as named by the LVS tool.
+    pub device_type: ArcStr,
+    /// The class of the device type.
+    pub class: DeviceClass,
+    /// The number of devices in the layout.
+    pub layout: usize,
+    /// The number of devices in the schematic.
+    pub source: usize,
+}
+
+impl LvsDeviceCount {
+    /// Returns `true` if the layout and schematic have the same number of devices.
+    #[inline]
+    pub fn is_match(&self) -> bool {
+        self.layout == self.source
+    }
+
+    /// Describes the device count mismatch, if any.
+    ///
+    /// For example, `layout has 1 extra MN`.
+    pub fn mismatch(&self) -> Option<String> {
+        if self.layout > self.source {
+            Some(format!(
+                "layout has {} extra {}",
+                self.layout - self.source,
+                self.device_type
+            ))
+        } else if self.source > self.layout {
+            Some(format!(
+                "schematic has {} extra {}",
+                self.source - self.layout,
+                self.device_type
+            ))
+        } else {
+            None
+        }
+    }
+}
+
+impl LvsStats {
+    /// Parses the initial object counts from a Calibre LVS comparison report.
+    ///
+    /// Returns [`None`] if the report does not contain an object count summary.
+    pub fn parse_calibre_report(report: &str) -> Option<Self> {
+        let mut lines = report
+            .lines()
+            .skip_while(|line| !line.contains("INITIAL NUMBERS OF OBJECTS"));
+        lines.next()?;
+
+        let mut stats = Self::default();
+        let mut found_nets = false;
+        let mut in_instances = false;
+
+        for line in lines {
+            let line = line.trim();
+            if line.starts_with("Total Inst") || line.contains("NUMBERS OF OBJECTS") {
+                break;
+            }
+            if let Some(rest) = line.strip_prefix("Nets:") {
+                let (layout, source, _) = parse_calibre_counts(rest)?;
+                stats.layout_nets = layout;
+                stats.source_nets = source;
+                found_nets = true;
+                continue;
+            }
+
+            let rest = if let Some(rest) = line.strip_prefix("Instances:") {
+                in_instances = true;
+                rest
+            } else if in_instances {
+                line
+            } else {
+                continue;
+            };
+
+            if let Some((layout, source, device_type)) = parse_calibre_counts(rest) {
+                if !device_type.is_empty() {
+                    stats.devices.push(LvsDeviceCount {
+                        class: calibre_device_class(device_type),
+                        device_type: device_type.into(),
+                        layout,
+                        source,
+                    });
+                }
+            }
+        }
+
+        found_nets.then_some(stats)
+    }
+
+    /// Returns the device counts that differ between the layout and schematic.
+    pub fn mismatches(&self) -> impl Iterator<Item = &LvsDeviceCount> {
+        self.devices.iter().filter(|count| !count.is_match())
+    }
+
+    /// Returns the total number of layout and schematic devices of the given class.
+    pub fn class_counts(&self, class: DeviceClass) -> (usize, usize) {
+        self.devices
+            .iter()
+            .filter(|count| count.class == class)
+            .fold((0, 0), |(layout, source), count| {
+                (layout + count.layout, source + count.source)
+            })
+    }
+}
+
+/// Parses a line of the form `<layout count> <source count> [*] [component type]`.
+fn parse_calibre_counts(line: &str) -> Option<(usize, usize, &str)> {
+    let (layout, rest) = line.trim_start().split_once(char::is_whitespace)?;
+    let rest = rest.trim_start();
+    let (source, rest) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+    let device_type = rest.trim().trim_start_matches('*').trim();
+    Some((layout.parse().ok()?, source.parse().ok()?, device_type))
+}
+
+/// Infers a [`DeviceClass`] from the SPICE element letter of a Calibre component type.
+fn calibre_device_class(device_type: &str) -> DeviceClass {
+    match device_type.chars().next().map(|c| c.to_ascii_uppercase()) {
+        Some('M') => DeviceClass::Mos,
+        Some('R') => DeviceClass::Res,
+        Some('C') => DeviceClass::Cap,
+        Some('L') => DeviceClass::Ind,
+        Some('D') => DeviceClass::Diode,
+        _ => DeviceClass::Other,
+    }
 }
 
 /// The trait that LVS plugins must implement.
-pub trait LvsTool {
+pub trait LvsTool: Send + Sync {
     /// Runs the LVS tool on the provided input files.
     fn run_lvs(&self, input: LvsInput) -> Result<LvsOutput>;
+
+    /// Returns the paths of the reports written for `input`, relative to its working directory.
+    ///
+    /// These files are kept when intermediate files are removed according to
+    /// [`LvsInput::cleanup`].
+    ///
+    /// Defaults to an empty list.
+    fn report_paths(&self, _input: &LvsInput) -> Vec<PathBuf> {
+        Vec::new()
+    }
+
+    /// Returns the layout format this tool expects its input layout to be written in.
+    ///
+    /// Defaults to [`LayoutFormat::Gds`].
+    fn layout_format(&self) -> LayoutFormat {
+        LayoutFormat::Gds
+    }
 }
@@ -0,0 +1,71 @@
+use super::*;
+
+const CALIBRE_REPORT: &str = r#"
+                               CELL COMPARISON RESULTS ( TOP LEVEL )
+
+#  ###################       _   _
+#  #                 #       *   *
+#  #   INCORRECT     #         |
+#  #                 #       \___/
+#  ###################
+
+LAYOUT CELL NAME:         inv
+SOURCE CELL NAME:         inv
+
+**************************************************************************************************************
+                                      INITIAL NUMBERS OF OBJECTS
+                                      --------------------------
+
+                  Layout    Source         Component Type
+                  ------    ------         --------------
+ Ports:              4         4
+ Nets:               5         4    *
+
+ Instances:          2         1    *      MN (4 pins)
+                     1         1           MP (4 pins)
+                     1         1           R (2 pins)
+                 ------    ------
+ Total Inst:         4         3
+
+                             NUMBERS OF OBJECTS AFTER TRANSFORMATION
+                             ---------------------------------------
+
+                  Layout    Source         Component Type
+                  ------    ------         --------------
+ Nets:               4         4
+"#;
+
+#[test]
+fn parse_calibre_device_count_mismatch() {
+    let stats = LvsStats::parse_calibre_report(CALIBRE_REPORT).unwrap();
+    assert_eq!(stats.layout_nets, 5);
+    assert_eq!(stats.source_nets, 4);
+    assert_eq!(stats.devices.len(), 3);
+
+    assert_eq!(
+        stats.devices[0],
+        LvsDeviceCount {
+            device_type: "MN (4 pins)".into(),
+            class: DeviceClass::Mos,
+            layout: 2,
+            source: 1,
+        }
+    );
+    assert_eq!(stats.devices[2].class, DeviceClass::Res);
+
+    let mismatches = stats.mismatches().collect::<Vec<_>>();
+    assert_eq!(mismatches.len(), 1);
+    assert_eq!(
+        mismatches[0].mismatch().unwrap(),
+        "layout has 1 extra MN (4 pins)"
+    );
+    assert_eq!(stats.devices[1].mismatch(), None);
+
+    assert_eq!(stats.class_counts(DeviceClass::Mos), (3, 2));
+    assert_eq!(stats.class_counts(DeviceClass::Cap), (0, 0));
+}
+
+#[test]
+fn parse_calibre_report_without_summary() {
+    assert_eq!(LvsStats::parse_calibre_report("LVS completed."), None);
+}
@@ -1,7 +1,86 @@
 //! Verification utilities.
 
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::Result;
+
 pub mod drc;
 pub mod lvs;
 pub mod pex;
 pub mod simulation;
 pub mod timing;
+
+#[cfg(test)]
+mod tests;
+
+/// Specifies when verification tools should remove intermediate files
+/// from their working directories.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum CleanupPolicy {
+    /// Never remove intermediate files.
+    #[default]
+    Never,
+    /// Remove intermediate files only if the run succeeded.
+    ///
+    /// Useful for keeping artifacts around for debugging failed runs.
+    OnSuccess,
+    /// Always remove intermediate files.
+    Always,
+}
+
+impl CleanupPolicy {
+    /// Returns `true` if intermediate files should be removed
+    /// after a run with the given outcome.
+    pub fn should_clean(&self, success: bool) -> bool {
+        match self {
+            Self::Never => false,
+            Self::OnSuccess => success,
+            Self::Always => true,
+        }
+    }
+
+    /// Removes the contents of `work_dir` if required by this policy.
+    ///
+    /// Files and directories in `preserve`, such as reports, are not removed.
+    /// Relative paths in `preserve` are interpreted relative to `work_dir`.
+    /// Does nothing if `work_dir` does not exist.
+    pub fn apply(
+        &self,
+        work_dir: impl AsRef<Path>,
+        success: bool,
+        preserve: &[impl AsRef<Path>],
+    ) -> Result<()> {
+        let work_dir = work_dir.as_ref();
+        if !self.should_clean(success) || !work_dir.exists() {
+            return Ok(());
+        }
+        let preserve = preserve
+            .iter()
+            .map(|path| work_dir.join(path))
+            .collect::<Vec<_>>();
+        remove_contents(work_dir, &preserve)
+    }
+}
+
+/// Recursively removes the contents of `dir`, except for paths in `preserve`
+/// and their ancestors.
+fn remove_contents(dir: &Path, preserve: &[PathBuf]) -> Result<()> {
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if preserve.iter().any(|p| p == &path) {
+            continue;
+        }
+        if path.is_dir() {
+            if preserve.iter().any(|p| p.starts_with(&path)) {
+                remove_contents(&path, preserve)?;
+            } else {
+                std::fs::remove_dir_all(&path)?;
+            }
+        } else {
+            std::fs::remove_file(&path)?;
+        }
+    }
+    Ok(())
+}
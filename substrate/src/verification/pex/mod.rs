@@ -1,12 +1,15 @@
 //! PEX plugin API.
 
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::io::Write;
+use std::path::{Path, PathBuf};
 
 use serde::{Deserialize, Serialize};
 
+use super::simulation::SimInput;
+use super::CleanupPolicy;
 use crate::deps::arcstr::ArcStr;
-use crate::error::Result;
+use crate::error::{ErrorSource, Result};
 use crate::layout::LayoutFormat;
 
 /// Inputs passed to a [`PexTool`].
@@ -30,6 +33,11 @@ pub struct PexInput {
     pub opts: HashMap<ArcStr, ArcStr>,
     /// The name of the ground net.
     pub ground_net: String,
+    /// When to remove intermediate files from `work_dir` after the run.
+    ///
+    /// The extracted netlist and the reports listed by [`PexTool::report_paths`] are kept.
+    #[serde(default)]
+    pub cleanup: CleanupPolicy,
 }
 
 /// An enumeration describing the high-level result of a PEX run.
@@ -72,7 +80,173 @@ pub struct PexOutput {
 }
 
 /// The trait that PEX plugins must implement.
-pub trait PexTool {
+pub trait PexTool: Send + Sync {
     /// Runs the PEX tool on the provided input files.
     fn run_pex(&self, input: PexInput) -> Result<PexOutput>;
+
+    /// Returns the paths of the reports written for `input`, relative to its working directory.
+    ///
+    /// These files are kept when intermediate files are removed according to
+    /// [`PexInput::cleanup`].
+    ///
+    /// Defaults to an empty list.
+    fn report_paths(&self, _input: &PexInput) -> Vec<PathBuf> {
+        Vec::new()
+    }
+
+    /// Returns the layout format this tool expects its input layout to be written in.
+    ///
+    /// Defaults to [`LayoutFormat::Gds`].
+    fn layout_format(&self) -> LayoutFormat {
+        LayoutFormat::Gds
+    }
+}
+
+/// An extracted netlist produced by a [`PexTool`], for use in post-layout simulation.
+///
+/// Extraction tools may name the extracted subcircuit differently from the
+/// schematic cell, or reorder its ports. A [`PexNetlist`] records this mapping,
+/// so that netlists instantiating the schematic cell can be simulated
+/// against the extracted netlist without modification.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct PexNetlist {
+    /// The path to the extracted netlist.
+    pub path: PathBuf,
+    /// The name of the schematic (pre-extraction) cell.
+    pub source_cell_name: ArcStr,
+    /// The ports of the schematic cell, in order, if known.
+    pub source_ports: Option<Vec<ArcStr>>,
+    /// The name of the extracted subcircuit.
+    pub pex_cell_name: ArcStr,
+    /// The ports of the extracted subcircuit, in order.
+    pub pex_ports: Vec<ArcStr>,
+}
+
+impl PexNetlist {
+    /// The name of the file containing the subcircuit that maps the schematic
+    /// cell to the extracted cell.
+    pub const WRAPPER_FILE_NAME: &'static str = "pex_wrapper.spice";
+
+    /// Loads the extracted netlist at `path` generated for the schematic cell `source_cell_name`.
+    ///
+    /// SPICE names are case insensitive, so the extracted subcircuit is the
+    /// subcircuit named `source_cell_name`, ignoring case. If there is no such
+    /// subcircuit but the netlist defines exactly one subcircuit, that subcircuit is used.
+    pub fn load(path: impl Into<PathBuf>, source_cell_name: impl Into<ArcStr>) -> Result<Self> {
+        let path = path.into();
+        let source_cell_name = source_cell_name.into();
+        let spice = crate::io::read_to_string(&path)?;
+        let parsed = subspice::parse(&spice)?;
+
+        let subckt = parsed
+            .subcircuits()
+            .find(|ckt| ckt.name.eq_ignore_ascii_case(&source_cell_name))
+            .or_else(|| {
+                let mut subckts = parsed.subcircuits();
+                match (subckts.next(), subckts.next()) {
+                    (Some(ckt), None) => Some(ckt),
+                    _ => None,
+                }
+            })
+            .ok_or_else(|| ErrorSource::ModuleNotFound(source_cell_name.to_string()))?;
+
+        Ok(Self {
+            pex_cell_name: subckt.name.into(),
+            pex_ports: subckt.ports.iter().map(|&port| port.into()).collect(),
+            path,
+            source_cell_name,
+            source_ports: None,
+        })
+    }
+
+    /// Loads the extracted netlist produced by running PEX with the given [`PexInput`].
+    ///
+    /// The ports of the schematic cell are read from the input's source netlists, if possible.
+    pub fn from_input(input: &PexInput) -> Result<Self> {
+        let mut netlist = Self::load(&input.pex_netlist_path, input.source_cell_name.clone())?;
+        for path in input.source_paths.iter() {
+            let spice = crate::io::read_to_string(path)?;
+            let parsed = subspice::parse(&spice)?;
+            if let Some(subckt) = parsed.subcircuit_named(&input.source_cell_name) {
+                netlist.source_ports = Some(subckt.ports.iter().map(|&port| port.into()).collect());
+                break;
+            }
+        }
+        Ok(netlist)
+    }
+
+    /// Sets the ports of the schematic cell, in order.
+    pub fn with_source_ports(mut self, ports: impl IntoIterator<Item = impl Into<ArcStr>>) -> Self {
+        self.source_ports = Some(ports.into_iter().map(|port| port.into()).collect());
+        self
+    }
+
+    /// Returns `true` if the extracted subcircuit must be wrapped in a subcircuit
+    /// named after the schematic cell.
+    pub fn needs_wrapper(&self) -> bool {
+        !self
+            .pex_cell_name
+            .eq_ignore_ascii_case(&self.source_cell_name)
+    }
+
+    /// Writes a subcircuit named after the schematic cell that instantiates the extracted cell.
+    ///
+    /// If the ports of the schematic cell are known, the wrapper has the same ports
+    /// as the schematic cell and connects them to the extracted cell by name.
+    /// Otherwise, the wrapper has the same ports as the extracted cell.
+    pub fn write_wrapper(&self, out: &mut impl Write) -> Result<()> {
+        let ports = self.source_ports.as_ref().unwrap_or(&self.pex_ports);
+        let conns = self
+            .pex_ports
+            .iter()
+            .map(|pex_port| {
+                ports
+                    .iter()
+                    .find(|port| port.eq_ignore_ascii_case(pex_port))
+                    .ok_or_else(|| ErrorSource::PortNotFound(pex_port.clone()))
+            })
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+
+        writeln!(
+            out,
+            "* Maps schematic cell {} to extracted cell {}",
+            self.source_cell_name, self.pex_cell_name
+        )?;
+        write!(out, ".subckt {}", self.source_cell_name)?;
+        for port in ports.iter() {
+            write!(out, " {port}")?;
+        }
+        write!(out, "\nxpex")?;
+        for conn in conns {
+            write!(out, " {conn}")?;
+        }
+        writeln!(out, " {}\n.ends", self.pex_cell_name)?;
+        Ok(())
+    }
+
+    /// Creates a [`SimInput`] that includes the extracted netlist.
+    ///
+    /// If [`PexNetlist::needs_wrapper`] returns `true`, a wrapper subcircuit is written
+    /// to [`PexNetlist::WRAPPER_FILE_NAME`] in `work_dir` and included as well.
+    /// Netlists that instantiate the schematic cell, such as a testbench,
+    /// should be added to the includes of the returned [`SimInput`].
+    pub fn sim_input(&self, work_dir: impl AsRef<Path>) -> Result<SimInput> {
+        let work_dir = work_dir.as_ref();
+        let mut includes = vec![self.path.clone()];
+
+        if self.needs_wrapper() {
+            crate::io::create_dir_all(work_dir)?;
+            let wrapper_path = work_dir.join(Self::WRAPPER_FILE_NAME);
+            let mut out = crate::io::create_file(&wrapper_path)?;
+            self.write_wrapper(&mut out)?;
+            out.flush()?;
+            includes.push(wrapper_path);
+        }
+
+        Ok(SimInput {
+            work_dir: work_dir.to_owned(),
+            includes,
+            ..Default::default()
+        })
+    }
 }
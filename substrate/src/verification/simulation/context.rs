@@ -1,6 +1,6 @@
 use std::path::PathBuf;
 
-use super::{Analysis, OutputFormat, Save, SimInput, SimOutput};
+use super::{Analysis, Measurement, OutputFormat, Save, SimInput, SimOutput};
 use crate::units::SiValue;
 
 pub struct PreSimCtx {
@@ -22,6 +22,11 @@ impl PreSimCtx {
         self
     }
 
+    pub fn add_measurement(&mut self, measurement: Measurement) -> &mut Self {
+        self.input.measurements.push(measurement);
+        self
+    }
+
     pub fn save(&mut self, save: Save) -> &mut Self {
         self.input.save = save;
         self
@@ -60,7 +65,6 @@ impl PreSimCtx {
     }
 
     #[inline]
-    #[allow(unused)]
     pub(crate) fn inner(&self) -> &SimInput {
         &self.input
     }
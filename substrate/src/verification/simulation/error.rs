@@ -0,0 +1,36 @@
+//! Errors produced when a simulator fails.
+
+use thiserror::Error;
+
+/// The reason a simulator run failed.
+///
+/// Simulator plugins classify their failures into these variants,
+/// which are returned as [`ErrorSource::Simulator`](crate::error::ErrorSource::Simulator).
+/// Each variant contains the relevant portion of the simulator's output.
+#[derive(Debug, Clone, Eq, PartialEq, Error)]
+pub enum SimulatorError {
+    /// The simulator could not check out a license.
+    #[error("simulator could not check out a license:\n{0}")]
+    License(String),
+    /// The netlist contains a syntax error.
+    #[error("simulator found a syntax error in the netlist:\n{0}")]
+    Syntax(String),
+    /// An analysis failed to converge.
+    #[error("simulator failed to converge:\n{0}")]
+    Nonconvergence(String),
+    /// The simulator failed for an unrecognized reason.
+    #[error("simulator exited unsuccessfully:\n{0}")]
+    Other(String),
+}
+
+impl SimulatorError {
+    /// Returns the simulator output included with the error.
+    pub fn log(&self) -> &str {
+        match self {
+            Self::License(log)
+            | Self::Syntax(log)
+            | Self::Nonconvergence(log)
+            | Self::Other(log) => log,
+        }
+    }
+}
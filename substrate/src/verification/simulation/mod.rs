@@ -1,20 +1,25 @@
 use std::collections::{HashMap, HashSet};
 use std::path::PathBuf;
 
+use arcstr::ArcStr;
 use derive_builder::Builder;
 use itertools::Itertools;
 use serde::{Deserialize, Serialize};
 
-use self::waveform::{binary_search_before, SharedWaveform};
-use crate::error::Result;
+use self::waveform::{binary_search_before, EdgeDir, SharedWaveform, TimeWaveform};
+use crate::error::{ErrorSource, Result};
 use crate::schematic::signal::NamedSignalPathBuf;
 use crate::units::SiValue;
 
 pub mod bits;
 pub mod context;
+pub mod error;
 pub mod testbench;
 pub mod waveform;
 
+#[cfg(test)]
+mod tests;
+
 #[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
 pub struct SimInput {
     pub work_dir: PathBuf,
@@ -23,6 +28,9 @@ pub struct SimInput {
     pub libs: Vec<Lib>,
     pub save: Save,
     /// Initial conditions for transient analysis.
+    ///
+    /// Keys are node names. How hierarchical names are interpreted
+    /// depends on the simulator plugin.
     pub ic: HashMap<String, SiValue>,
     pub measurements: Vec<Measurement>,
     pub analyses: Vec<Analysis>,
@@ -48,6 +56,73 @@ pub struct SimOutput {
     pub data: Vec<AnalysisData>,
 }
 
+impl SimOutput {
+    /// Returns the results of the analysis at index `idx`,
+    /// checking that it is of type `expected`.
+    fn analysis(&self, idx: usize, expected: AnalysisType) -> Result<&AnalysisData> {
+        let data = self
+            .data
+            .get(idx)
+            .ok_or(ErrorSource::AnalysisIndexOutOfBounds {
+                index: idx,
+                len: self.data.len(),
+            })?;
+        let found = data.analysis_type();
+        if found != expected {
+            return Err(ErrorSource::AnalysisTypeMismatch {
+                index: idx,
+                expected,
+                found,
+            }
+            .into());
+        }
+        Ok(data)
+    }
+
+    /// Returns the results of the operating point analysis at index `idx`.
+    ///
+    /// Returns an error if there is no analysis at index `idx`,
+    /// or if that analysis is not an operating point analysis.
+    pub fn op(&self, idx: usize) -> Result<&OpData> {
+        Ok(self.analysis(idx, AnalysisType::Op)?.op())
+    }
+
+    /// Returns the results of the transient analysis at index `idx`.
+    ///
+    /// Returns an error if there is no analysis at index `idx`,
+    /// or if that analysis is not a transient analysis.
+    pub fn tran(&self, idx: usize) -> Result<&TranData> {
+        Ok(self.analysis(idx, AnalysisType::Tran)?.tran())
+    }
+
+    /// Returns the results of the AC analysis at index `idx`.
+    ///
+    /// Returns an error if there is no analysis at index `idx`,
+    /// or if that analysis is not an AC analysis.
+    pub fn ac(&self, idx: usize) -> Result<&AcData> {
+        Ok(self.analysis(idx, AnalysisType::Ac)?.ac())
+    }
+
+    /// Returns the results of the DC analysis at index `idx`.
+    ///
+    /// Returns an error if there is no analysis at index `idx`,
+    /// or if that analysis is not a DC analysis.
+    pub fn dc(&self, idx: usize) -> Result<&DcData> {
+        Ok(self.analysis(idx, AnalysisType::Dc)?.dc())
+    }
+
+    /// Returns the results of the first transient analysis.
+    ///
+    /// Returns an error if there are no transient analyses.
+    pub fn first_tran(&self) -> Result<&TranData> {
+        self.data
+            .iter()
+            .find(|data| data.analysis_type() == AnalysisType::Tran)
+            .map(AnalysisData::tran)
+            .ok_or_else(|| ErrorSource::AnalysisNotFound(AnalysisType::Tran).into())
+    }
+}
+
 #[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
 pub struct SimOpts {
     /// Simulation temperature, in degrees Celsius.
@@ -74,7 +149,32 @@ pub enum Save {
     #[default]
     All,
     None,
-    Signals(HashSet<String>),
+    Signals(HashSet<SaveItem>),
+}
+
+/// A quantity to save during a simulation.
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum SaveItem {
+    /// The voltage of a node.
+    NodeVoltage(NamedSignalPathBuf),
+    /// The current into a terminal of a device.
+    DeviceCurrent(NamedTerminalPathBuf),
+    /// A raw simulator expression or signal name, passed to the simulator verbatim.
+    Raw(String),
+}
+
+/// A path to a terminal of a device, such as the drain of a transistor.
+///
+/// Like [`NamedSignalPathBuf`]s, `NamedTerminalPathBuf`s are NOT preserved
+/// after automatic module and instance renaming.
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct NamedTerminalPathBuf {
+    /// The names of the instances containing the device, starting from the top level.
+    pub insts: Vec<ArcStr>,
+    /// The name of the device, exactly as it appears in the netlist (eg. `M0`).
+    pub device: ArcStr,
+    /// The name of the terminal (eg. `d`).
+    pub terminal: ArcStr,
 }
 
 #[derive(Debug, Clone, PartialEq, Hash, Serialize, Deserialize)]
@@ -84,12 +184,134 @@ pub struct Measurement {
     expr: String,
 }
 
+/// Functions that may appear in measurement expressions.
+///
+/// Expressions using other functions are accepted with a warning,
+/// since simulators differ in the functions they support.
+const MEASUREMENT_FUNCTIONS: &[&str] = &[
+    "v", "i", "vm", "vp", "vr", "vi", "vdb", "im", "ip", "ir", "ii", "idb", "abs", "sqrt", "exp",
+    "ln", "log", "log10", "pow", "pwr", "min", "max", "avg", "rms", "integ", "deriv", "db", "mag",
+    "ph", "phase", "real", "imag", "sin", "cos", "tan", "atan", "sgn", "floor", "ceil", "int",
+];
+
+impl Measurement {
+    /// Creates a new measurement named `name` in analysis `analysis_mode`.
+    ///
+    /// Returns an error if the measurement expression `expr` is malformed.
+    /// See [`Measurement::validate`].
+    pub fn new(
+        analysis_mode: impl Into<String>,
+        name: impl Into<String>,
+        expr: impl Into<String>,
+    ) -> Result<Self> {
+        let measurement = Self {
+            analysis_mode: analysis_mode.into(),
+            name: name.into(),
+            expr: expr.into(),
+        };
+        measurement.validate()?;
+        Ok(measurement)
+    }
+
+    #[inline]
+    pub fn analysis_mode(&self) -> &str {
+        &self.analysis_mode
+    }
+
+    #[inline]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    #[inline]
+    pub fn expr(&self) -> &str {
+        &self.expr
+    }
+
+    /// Checks the syntax of the measurement expression.
+    ///
+    /// Returns an error if the name or expression is empty, or if the expression
+    /// has unbalanced parentheses or quotes. Functions not in a list of commonly
+    /// supported functions are logged as warnings, but are not rejected.
+    pub fn validate(&self) -> Result<()> {
+        let err = |reason: &str| ErrorSource::InvalidMeasurement {
+            name: self.name.clone(),
+            reason: reason.to_string(),
+        };
+
+        if self.name.trim().is_empty() {
+            return Err(err("measurement name is empty").into());
+        }
+        if self.expr.trim().is_empty() {
+            return Err(err("measurement expression is empty").into());
+        }
+
+        let mut depth = 0usize;
+        let mut quote = None;
+        let mut ident = String::new();
+        for c in self.expr.chars() {
+            if let Some(q) = quote {
+                if c == q {
+                    quote = None;
+                }
+                continue;
+            }
+            match c {
+                '\'' | '"' => quote = Some(c),
+                '(' => {
+                    if !ident.is_empty()
+                        && !MEASUREMENT_FUNCTIONS.contains(&ident.to_ascii_lowercase().as_str())
+                    {
+                        crate::log::warn!(
+                            "unknown function `{ident}` in measurement `{}`",
+                            self.name
+                        );
+                    }
+                    depth += 1;
+                }
+                ')' => {
+                    depth = depth
+                        .checked_sub(1)
+                        .ok_or_else(|| err("unmatched closing parenthesis"))?;
+                }
+                _ => {}
+            }
+            if c.is_alphanumeric() || c == '_' {
+                ident.push(c);
+            } else {
+                ident.clear();
+            }
+        }
+
+        if quote.is_some() {
+            return Err(err("unterminated quote").into());
+        }
+        if depth > 0 {
+            return Err(err("unclosed parenthesis").into());
+        }
+        Ok(())
+    }
+}
+
+impl SimInput {
+    /// Validates the simulation input before it is passed to a simulator.
+    ///
+    /// See [`Measurement::validate`].
+    pub fn validate(&self) -> Result<()> {
+        for measurement in self.measurements.iter() {
+            measurement.validate()?;
+        }
+        Ok(())
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum Analysis {
     Op(OpAnalysis),
     Dc(DcAnalysis),
     Tran(TranAnalysis),
     Ac(AcAnalysis),
+    Noise(NoiseAnalysis),
     MonteCarlo(MonteCarloAnalysis),
 }
 
@@ -99,6 +321,7 @@ pub enum AnalysisType {
     Dc,
     Tran,
     Ac,
+    Noise,
     MonteCarlo,
     Other,
 }
@@ -119,6 +342,23 @@ pub struct OpData {
     pub data: HashMap<String, ScalarSignal>,
 }
 
+/// The result of checking an operating point value against limits.
+///
+/// See [`OpData::check`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OpCheckResult {
+    /// The name of the checked signal.
+    pub name: String,
+    /// The value of the signal, or [`None`] if the signal was not saved.
+    pub value: Option<f64>,
+    /// The lower limit (inclusive).
+    pub lo: f64,
+    /// The upper limit (inclusive).
+    pub hi: f64,
+    /// Whether the signal was found and lay within the limits.
+    pub passed: bool,
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct TranData {
     /// All saved signals, not including time.
@@ -139,6 +379,15 @@ pub struct DcData {
     pub data: HashMap<String, RealSignal>,
 }
 
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NoiseData {
+    /// The output noise spectral density, such as in V/sqrt(Hz).
+    pub output: RealSignal,
+    /// The noise spectral density referred to the input source, such as in V/sqrt(Hz).
+    pub input: RealSignal,
+    pub freq: RealSignal,
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct MonteCarloData {
     /// All saved analyses.
@@ -205,6 +454,30 @@ impl AcAnalysis {
     }
 }
 
+#[derive(Debug, Clone, Builder, PartialEq, Serialize, Deserialize)]
+pub struct NoiseAnalysis {
+    /// The node at which output noise is measured, relative to ground.
+    #[builder(setter(into))]
+    pub output: String,
+    /// The name of the input source to which noise is referred.
+    #[builder(setter(into))]
+    pub input_source: String,
+    pub fstart: f64,
+    pub fstop: f64,
+    pub points: usize,
+    pub sweep: SweepMode,
+    /// Simulator-specific options.
+    #[builder(default)]
+    pub opts: HashMap<String, String>,
+}
+
+impl NoiseAnalysis {
+    #[inline]
+    pub fn builder() -> NoiseAnalysisBuilder {
+        NoiseAnalysisBuilder::default()
+    }
+}
+
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum Variations {
     #[default]
@@ -273,6 +546,7 @@ pub enum AnalysisData {
     Tran(TranData),
     Ac(AcData),
     Dc(DcData),
+    Noise(NoiseData),
     MonteCarlo(MonteCarloData),
     Other,
 }
@@ -284,6 +558,7 @@ impl AnalysisData {
             Self::Tran(_) => AnalysisType::Tran,
             Self::Ac(_) => AnalysisType::Ac,
             Self::Dc(_) => AnalysisType::Dc,
+            Self::Noise(_) => AnalysisType::Noise,
             Self::MonteCarlo(_) => AnalysisType::MonteCarlo,
             Self::Other => AnalysisType::Other,
         }
@@ -337,6 +612,18 @@ impl AnalysisData {
         }
     }
 
+    /// Get the results of a noise analysis.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if this analysis does not correspond to a noise analysis.
+    pub fn noise(&self) -> &NoiseData {
+        match self {
+            Self::Noise(x) => x,
+            _ => panic!("Expected noise analysis, got {:?}", self.analysis_type()),
+        }
+    }
+
     /// Get the results of a Monte Carlo analysis.
     ///
     /// # Panics
@@ -370,6 +657,11 @@ impl From<DcData> for AnalysisData {
         Self::Dc(value)
     }
 }
+impl From<NoiseData> for AnalysisData {
+    fn from(value: NoiseData) -> Self {
+        Self::Noise(value)
+    }
+}
 impl From<MonteCarloData> for AnalysisData {
     fn from(value: MonteCarloData) -> Self {
         Self::MonteCarlo(value)
@@ -381,7 +673,7 @@ pub struct SimulatorOpts {
     pub opts: HashMap<String, String>,
 }
 
-pub trait Simulator {
+pub trait Simulator: Send + Sync {
     fn new(opts: SimulatorOpts) -> Result<Self>
     where
         Self: Sized;
@@ -396,6 +688,7 @@ impl Analysis {
             Analysis::Tran(_) => AnalysisType::Tran,
             Analysis::Ac(_) => AnalysisType::Ac,
             Analysis::Dc(_) => AnalysisType::Dc,
+            Analysis::Noise(_) => AnalysisType::Noise,
             Analysis::MonteCarlo(_) => AnalysisType::MonteCarlo,
         }
     }
@@ -425,6 +718,12 @@ impl From<AcAnalysis> for Analysis {
     }
 }
 
+impl From<NoiseAnalysis> for Analysis {
+    fn from(value: NoiseAnalysis) -> Self {
+        Self::Noise(value)
+    }
+}
+
 impl From<MonteCarloAnalysis> for Analysis {
     fn from(value: MonteCarloAnalysis) -> Self {
         Self::MonteCarlo(value)
@@ -464,6 +763,207 @@ impl RealSignal {
     }
 }
 
+impl ComplexSignal {
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.real.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.real.is_empty()
+    }
+
+    /// Returns the magnitude of each value.
+    ///
+    /// The magnitude has the same [`Quantity`] as this signal.
+    pub fn magnitude(&self) -> RealSignal {
+        RealSignal {
+            values: self
+                .real
+                .iter()
+                .zip(self.imag.iter())
+                .map(|(re, im)| re.hypot(*im))
+                .collect(),
+            quantity: self.quantity,
+        }
+    }
+
+    /// Returns the magnitude of each value in decibels, computed as `20 * log10(|x|)`.
+    ///
+    /// Values with zero magnitude map to negative infinity.
+    pub fn magnitude_db(&self) -> RealSignal {
+        let mut mag = self.magnitude();
+        for v in mag.values.iter_mut() {
+            *v = 20. * v.log10();
+        }
+        mag.quantity = Quantity::Unknown;
+        mag
+    }
+
+    /// Returns the phase of each value in degrees, in the range `[-180, 180]`.
+    pub fn phase_degrees(&self) -> RealSignal {
+        RealSignal {
+            values: self
+                .real
+                .iter()
+                .zip(self.imag.iter())
+                .map(|(re, im)| im.atan2(*re).to_degrees())
+                .collect(),
+            quantity: Quantity::Unknown,
+        }
+    }
+}
+
+impl ScalarSignal {
+    /// Returns `true` if the value lies within `[lo, hi]`, inclusive.
+    #[inline]
+    pub fn in_range(&self, lo: f64, hi: f64) -> bool {
+        lo <= self.value && self.value <= hi
+    }
+}
+
+impl OpData {
+    pub fn signal(&self, name: &str) -> Option<&ScalarSignal> {
+        self.data.get(name)
+    }
+
+    /// Checks saved signals against `(name, lo, hi)` limits.
+    ///
+    /// Returns one [`OpCheckResult`] per constraint, in the order given.
+    /// A constraint on a signal that was not saved fails.
+    pub fn check(&self, constraints: &[(String, f64, f64)]) -> Vec<OpCheckResult> {
+        constraints
+            .iter()
+            .map(|(name, lo, hi)| {
+                let signal = self.signal(name);
+                OpCheckResult {
+                    name: name.clone(),
+                    value: signal.map(|s| s.value),
+                    lo: *lo,
+                    hi: *hi,
+                    passed: signal.map(|s| s.in_range(*lo, *hi)).unwrap_or(false),
+                }
+            })
+            .collect()
+    }
+
+    /// Returns the operating point voltage of the node at `path`.
+    ///
+    /// The path is converted to a signal name using [`Simulator::node_voltage_string`].
+    pub fn value_at(&self, path: &NamedSignalPathBuf, sim: &dyn Simulator) -> Option<f64> {
+        Some(self.signal(&sim.node_voltage_string(path))?.value)
+    }
+}
+
+impl DcData {
+    pub fn signal(&self, name: &str) -> Option<&RealSignal> {
+        self.data.get(name)
+    }
+
+    /// Returns the swept voltage of the node at `path`.
+    ///
+    /// The path is converted to a signal name using [`Simulator::node_voltage_string`].
+    pub fn signal_at(&self, path: &NamedSignalPathBuf, sim: &dyn Simulator) -> Option<&RealSignal> {
+        self.signal(&sim.node_voltage_string(path))
+    }
+
+    /// Computes the derivative of the signal `of` with respect to the signal `wrt`,
+    /// such as a transconductance from a drain current swept against a gate voltage.
+    ///
+    /// Interior points use central differences over their two neighbors, and the endpoints
+    /// use one-sided differences. Differences are taken between adjacent points in sweep order,
+    /// so `wrt` may be unevenly spaced or non-monotonic. Points at which `wrt` does not change
+    /// have an undefined (NaN or infinite) derivative.
+    ///
+    /// Returns [`None`] if either signal was not saved, the signals have different lengths,
+    /// or there are fewer than two points.
+    pub fn derivative(&self, of: &str, wrt: &str) -> Option<RealSignal> {
+        let (y, x) = (&self.signal(of)?.values, &self.signal(wrt)?.values);
+        let n = y.len();
+        if n != x.len() || n < 2 {
+            return None;
+        }
+
+        let values = (0..n)
+            .map(|i| {
+                let (lo, hi) = (i.saturating_sub(1), (i + 1).min(n - 1));
+                (y[hi] - y[lo]) / (x[hi] - x[lo])
+            })
+            .collect();
+
+        Some(RealSignal {
+            values,
+            quantity: Quantity::Unknown,
+        })
+    }
+}
+
+impl AcData {
+    pub fn signal(&self, name: &str) -> Option<&ComplexSignal> {
+        self.data.get(name)
+    }
+
+    /// Returns the lowest frequency at which the magnitude of `signal` falls below unity.
+    ///
+    /// The crossover is found by linearly interpolating the magnitude (in dB) between
+    /// adjacent frequency points. Returns [`None`] if the signal was not saved
+    /// or its magnitude never falls below unity.
+    pub fn unity_gain_frequency(&self, signal: &str) -> Option<f64> {
+        let mag = self.signal(signal)?.magnitude_db();
+        falling_crossing(&self.freq.values, &mag.values, 0.)
+    }
+
+    /// Returns the phase margin of `signal` in degrees.
+    ///
+    /// The phase margin is `180` plus the phase at the [unity gain frequency](AcData::unity_gain_frequency).
+    /// The phase is unwrapped starting from the first frequency point,
+    /// so `signal` should be a non-inverting loop gain whose phase starts near zero.
+    /// Returns [`None`] if the signal was not saved or never crosses unity gain.
+    pub fn phase_margin(&self, signal: &str) -> Option<f64> {
+        let ugf = self.unity_gain_frequency(signal)?;
+        let phase = unwrapped_phase(self.signal(signal)?);
+        Some(180. + interp_sorted(&self.freq.values, &phase, ugf))
+    }
+
+    /// Returns the gain margin of `signal` in dB.
+    ///
+    /// The gain margin is the negated magnitude (in dB) at the lowest frequency at which
+    /// the unwrapped phase falls below -180 degrees. See [`AcData::phase_margin`]
+    /// for how the phase is unwrapped. Returns [`None`] if the signal was not saved
+    /// or its phase never crosses -180 degrees.
+    pub fn gain_margin(&self, signal: &str) -> Option<f64> {
+        let signal = self.signal(signal)?;
+        let phase = unwrapped_phase(signal);
+        let f180 = falling_crossing(&self.freq.values, &phase, -180.)?;
+        let mag = signal.magnitude_db();
+        Some(-interp_sorted(&self.freq.values, &mag.values, f180))
+    }
+}
+
+/// Returns the phase of `signal` in degrees, with jumps of more than 180 degrees
+/// between adjacent points removed by adding multiples of 360 degrees.
+fn unwrapped_phase(signal: &ComplexSignal) -> Vec<f64> {
+    let mut phase = signal.phase_degrees().values;
+    let mut offset = 0.;
+    for i in 1..phase.len() {
+        let raw = phase[i] + offset;
+        let delta = raw - phase[i - 1];
+        offset -= 360. * (delta / 360.).round();
+        phase[i] += offset;
+    }
+    phase
+}
+
+/// Returns the first `x` at which `y` falls from at least `level` to below `level`,
+/// linearly interpolating between adjacent points.
+fn falling_crossing(x: &[f64], y: &[f64], level: f64) -> Option<f64> {
+    x.windows(2).zip(y.windows(2)).find_map(|(x, y)| {
+        (y[0] >= level && y[1] < level)
+            .then(|| x[0] + (level - y[0]) / (y[1] - y[0]) * (x[1] - x[0]))
+    })
+}
+
 impl std::ops::Index<usize> for RealSignal {
     type Output = f64;
     fn index(&self, index: usize) -> &Self::Output {
@@ -490,10 +990,137 @@ impl TranData {
     pub fn time_waveform(&self) -> SharedWaveform<'_> {
         SharedWaveform::from_signal(&self.time, &self.time)
     }
+
+    /// Keeps every `factor`-th sample of the time vector and all signals,
+    /// starting with the first sample.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `factor` is 0.
+    pub fn decimate(&self, factor: usize) -> TranData {
+        assert!(factor > 0, "decimation factor must be positive");
+        let decimate = |signal: &RealSignal| RealSignal {
+            values: signal.values.iter().copied().step_by(factor).collect(),
+            quantity: signal.quantity,
+        };
+        TranData {
+            data: self
+                .data
+                .iter()
+                .map(|(name, signal)| (name.clone(), decimate(signal)))
+                .collect(),
+            time: decimate(&self.time),
+        }
+    }
+
+    /// Linearly interpolates the time vector and all signals onto
+    /// a uniform time grid with spacing `dt`.
+    ///
+    /// The new grid starts at the first time point and includes every
+    /// multiple of `dt` up to and including the last time point.
+    /// Grid points within floating point error of the last time point
+    /// are included and clamped to it, so resampling from 0 to 0.3
+    /// with `dt = 0.1` produces four points ending exactly at 0.3.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `dt` is not positive.
+    pub fn resample_uniform(&self, dt: f64) -> TranData {
+        assert!(dt > 0.0, "resampling interval must be positive");
+
+        let times = match (self.time.values.first(), self.time.values.last()) {
+            (Some(&start), Some(&stop)) => {
+                let n = ((stop - start) / dt + 1e-9).floor() as usize;
+                (0..=n).map(|i| (start + i as f64 * dt).min(stop)).collect()
+            }
+            _ => Vec::new(),
+        };
+
+        let resample = |signal: &RealSignal| RealSignal {
+            values: times
+                .iter()
+                .map(|&t| interp_sorted(&self.time.values, &signal.values, t))
+                .collect(),
+            quantity: signal.quantity,
+        };
+
+        TranData {
+            data: self
+                .data
+                .iter()
+                .map(|(name, signal)| (name.clone(), resample(signal)))
+                .collect(),
+            time: RealSignal {
+                values: times.clone(),
+                quantity: self.time.quantity,
+            },
+        }
+    }
+
+    /// Returns the times at which the signal named `signal` crosses `threshold` in direction `dir`,
+    /// in increasing order.
+    ///
+    /// Crossing times are linearly interpolated between adjacent samples.
+    /// Returns an empty vector if there is no signal named `signal`.
+    pub fn crossing_times(&self, signal: &str, threshold: f64, dir: EdgeDir) -> Vec<f64> {
+        match self.waveform(signal) {
+            Some(waveform) if !waveform.is_empty() => waveform
+                .edges(threshold)
+                .filter(|edge| edge.dir() == dir)
+                .map(|edge| edge.t())
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+}
+
+/// Linearly interpolates the value of `x` at `target`,
+/// where `t` is monotonically increasing.
+///
+/// Values outside the range of `t` are clamped to the first/last value of `x`.
+fn interp_sorted(t: &[f64], x: &[f64], target: f64) -> f64 {
+    let idx = t.partition_point(|&v| v <= target);
+    if idx == 0 {
+        return x[0];
+    }
+    if idx == t.len() {
+        return x[idx - 1];
+    }
+    let (t0, t1) = (t[idx - 1], t[idx]);
+    let c = (target - t0) / (t1 - t0);
+    x[idx - 1] + c * (x[idx] - x[idx - 1])
+}
+
+impl From<NamedSignalPathBuf> for SaveItem {
+    #[inline]
+    fn from(value: NamedSignalPathBuf) -> Self {
+        Self::NodeVoltage(value)
+    }
+}
+
+impl From<NamedTerminalPathBuf> for SaveItem {
+    #[inline]
+    fn from(value: NamedTerminalPathBuf) -> Self {
+        Self::DeviceCurrent(value)
+    }
+}
+
+impl From<String> for SaveItem {
+    #[inline]
+    fn from(value: String) -> Self {
+        Self::Raw(value)
+    }
+}
+
+impl From<&str> for SaveItem {
+    #[inline]
+    fn from(value: &str) -> Self {
+        Self::Raw(value.to_string())
+    }
 }
 
 impl Save {
-    pub fn add(&mut self, value: impl Into<String>) {
+    pub fn add(&mut self, value: impl Into<SaveItem>) {
         match self {
             Self::All => (),
             Self::None => {
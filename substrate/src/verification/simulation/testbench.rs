@@ -1,7 +1,9 @@
 use std::any::Any;
+use std::path::Path;
 
 use super::context::{PostSimCtx, PreSimCtx};
 use crate::component::Component;
+use crate::data::SubstrateCtx;
 use crate::deps::arcstr::ArcStr;
 use crate::error::Result;
 
@@ -34,3 +36,46 @@ pub trait Testbench: Component + Any {
     /// Cleans up any files generated by the testbench.
     fn cleanup(&mut self) {}
 }
+
+/// A self-test bundled with a [`Component`].
+///
+/// Returned by [`Component::testbench`] and run by
+/// [`SubstrateCtx::simulate_component`].
+pub trait ComponentTestbench {
+    /// Simulates the testbench in the given working directory.
+    ///
+    /// Returns an error if the simulation fails or if its results
+    /// do not pass the testbench's checks.
+    fn simulate(&self, ctx: &SubstrateCtx, work_dir: &Path) -> Result<()>;
+}
+
+/// A [`ComponentTestbench`] that simulates testbench `T` with fixed parameters.
+pub struct BundledTestbench<T: Testbench> {
+    params: T::Params,
+    check: fn(&T::Output) -> Result<()>,
+}
+
+impl<T: Testbench> BundledTestbench<T> {
+    /// Creates a bundled testbench that passes whenever `T` simulates successfully.
+    pub fn new(params: T::Params) -> Self {
+        Self {
+            params,
+            check: |_| Ok(()),
+        }
+    }
+
+    /// Sets a function for checking the output of the testbench.
+    ///
+    /// The testbench fails if the check returns an error.
+    pub fn with_check(mut self, check: fn(&T::Output) -> Result<()>) -> Self {
+        self.check = check;
+        self
+    }
+}
+
+impl<T: Testbench> ComponentTestbench for BundledTestbench<T> {
+    fn simulate(&self, ctx: &SubstrateCtx, work_dir: &Path) -> Result<()> {
+        let output = ctx.write_simulation::<T>(&self.params, work_dir)?;
+        (self.check)(&output)
+    }
+}
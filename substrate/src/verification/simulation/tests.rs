@@ -0,0 +1,428 @@
+use std::collections::HashMap;
+
+use super::*;
+
+struct MockSimulator;
+
+impl Simulator for MockSimulator {
+    fn new(_opts: SimulatorOpts) -> Result<Self> {
+        Ok(Self)
+    }
+
+    fn simulate(&self, _input: SimInput) -> Result<SimOutput> {
+        Ok(SimOutput { data: Vec::new() })
+    }
+
+    fn node_voltage_string(&self, path: &NamedSignalPathBuf) -> String {
+        let mut s = String::from("v(");
+        for inst in path.insts.iter() {
+            s.push_str(inst);
+            s.push('.');
+        }
+        s.push_str(&path.signal);
+        if let Some(idx) = path.idx {
+            s.push_str(&format!("[{idx}]"));
+        }
+        s.push(')');
+        s
+    }
+}
+
+fn op_data() -> OpData {
+    let mut data = HashMap::new();
+    data.insert(
+        "v(vout)".to_string(),
+        ScalarSignal {
+            value: 1.02,
+            quantity: Quantity::Voltage,
+        },
+    );
+    data.insert(
+        "v(xdut.xbias.vbias)".to_string(),
+        ScalarSignal {
+            value: 0.45,
+            quantity: Quantity::Voltage,
+        },
+    );
+    OpData { data }
+}
+
+#[test]
+fn scalar_signal_in_range() {
+    let signal = ScalarSignal {
+        value: 1.0,
+        quantity: Quantity::Voltage,
+    };
+    assert!(signal.in_range(0.9, 1.1));
+    assert!(signal.in_range(1.0, 1.0));
+    assert!(!signal.in_range(1.05, 1.1));
+}
+
+#[test]
+fn op_data_check() {
+    let data = op_data();
+    let results = data.check(&[
+        ("v(vout)".to_string(), 0.9, 1.1),
+        ("v(xdut.xbias.vbias)".to_string(), 0.5, 0.7),
+        ("vmissing".to_string(), 0.0, 1.8),
+    ]);
+
+    assert_eq!(results.len(), 3);
+    assert_eq!(results[0].name, "v(vout)");
+    assert_eq!(results[0].value, Some(1.02));
+    assert!(results[0].passed);
+    assert_eq!(results[1].value, Some(0.45));
+    assert!(!results[1].passed);
+    assert_eq!(results[2].value, None);
+    assert!(!results[2].passed);
+}
+
+#[test]
+fn op_data_value_at() {
+    let data = op_data();
+    let path = NamedSignalPathBuf {
+        insts: vec![arcstr::literal!("xdut"), arcstr::literal!("xbias")],
+        signal: arcstr::literal!("vbias"),
+        idx: None,
+    };
+    assert_eq!(data.value_at(&path, &MockSimulator), Some(0.45));
+
+    let missing = NamedSignalPathBuf {
+        insts: vec![arcstr::literal!("xdut")],
+        signal: arcstr::literal!("vbias"),
+        idx: None,
+    };
+    assert_eq!(data.value_at(&missing, &MockSimulator), None);
+}
+
+fn tran_data() -> TranData {
+    let mut data = HashMap::new();
+    data.insert(
+        "v(out)".to_string(),
+        RealSignal {
+            values: vec![0.0, 1.0, 2.0, 3.0, 4.0, 5.0, 6.0],
+            quantity: Quantity::Voltage,
+        },
+    );
+    TranData {
+        data,
+        time: RealSignal {
+            values: vec![0.0, 0.5, 1.0, 2.0, 2.5, 3.0, 4.0],
+            quantity: Quantity::Time,
+        },
+    }
+}
+
+#[test]
+fn tran_data_decimate() {
+    let data = tran_data().decimate(3);
+    assert_eq!(data.time.values, vec![0.0, 2.0, 4.0]);
+    assert_eq!(data.time.quantity, Quantity::Time);
+
+    let out = data.signal("v(out)").unwrap();
+    assert_eq!(out.values, vec![0.0, 3.0, 6.0]);
+    assert_eq!(out.quantity, Quantity::Voltage);
+}
+
+#[test]
+fn tran_data_resample_uniform() {
+    let data = tran_data().resample_uniform(1.5);
+    assert_eq!(data.time.values, vec![0.0, 1.5, 3.0]);
+
+    let out = data.signal("v(out)").unwrap();
+    assert_eq!(out.len(), data.time.len());
+    assert_eq!(out.values, vec![0.0, 2.5, 5.0]);
+    assert_eq!(out.quantity, Quantity::Voltage);
+}
+
+#[test]
+fn tran_data_resample_uniform_keeps_last_point() {
+    // 0.3 / 0.1 evaluates to slightly less than 3 in floating point.
+    let data = TranData {
+        data: HashMap::from([(
+            "v(out)".to_string(),
+            RealSignal {
+                values: vec![0.0, 3.0],
+                quantity: Quantity::Voltage,
+            },
+        )]),
+        time: RealSignal {
+            values: vec![0.0, 0.3],
+            quantity: Quantity::Time,
+        },
+    }
+    .resample_uniform(0.1);
+
+    assert_eq!(data.time.len(), 4);
+    assert_eq!(data.time.values.last(), Some(&0.3));
+
+    let out = data.signal("v(out)").unwrap();
+    for (value, expected) in out.values.iter().zip([0.0, 1.0, 2.0, 3.0]) {
+        assert!(
+            (value - expected).abs() < 1e-9,
+            "expected {expected}, got {value}"
+        );
+    }
+}
+
+#[test]
+fn sim_output_accessors() {
+    let output = SimOutput {
+        data: vec![
+            AnalysisData::Op(OpData {
+                data: HashMap::new(),
+            }),
+            AnalysisData::Tran(tran_data()),
+        ],
+    };
+
+    assert!(output.op(0).unwrap().data.is_empty());
+    assert_eq!(output.tran(1).unwrap(), &tran_data());
+    assert_eq!(output.first_tran().unwrap(), &tran_data());
+
+    let err = output.tran(0).unwrap_err();
+    assert!(matches!(
+        err.source(),
+        ErrorSource::AnalysisTypeMismatch {
+            index: 0,
+            expected: AnalysisType::Tran,
+            found: AnalysisType::Op,
+        }
+    ));
+    assert_eq!(
+        err.source().to_string(),
+        "expected analysis 0 to be of type Tran, but found Op"
+    );
+
+    let err = output.ac(2).unwrap_err();
+    assert!(matches!(
+        err.source(),
+        ErrorSource::AnalysisIndexOutOfBounds { index: 2, len: 2 }
+    ));
+
+    let empty = SimOutput { data: Vec::new() };
+    assert!(matches!(
+        empty.first_tran().unwrap_err().source(),
+        ErrorSource::AnalysisNotFound(AnalysisType::Tran)
+    ));
+}
+
+#[test]
+fn tran_data_crossing_times() {
+    // A triangle wave rising from 0 to 4 V over 4 s, then falling back to 0 V.
+    let data = TranData {
+        data: HashMap::from_iter([(
+            "v(out)".to_string(),
+            RealSignal {
+                values: vec![0.0, 1.0, 2.0, 3.0, 4.0, 2.0, 0.0],
+                quantity: Quantity::Voltage,
+            },
+        )]),
+        time: RealSignal {
+            values: vec![0.0, 1.0, 2.0, 3.0, 4.0, 5.0, 6.0],
+            quantity: Quantity::Time,
+        },
+    };
+
+    assert_eq!(
+        data.crossing_times("v(out)", 2.5, EdgeDir::Rising),
+        vec![2.5]
+    );
+    assert_eq!(
+        data.crossing_times("v(out)", 3.0, EdgeDir::Falling),
+        vec![4.5]
+    );
+    assert_eq!(
+        data.crossing_times("v(out)", 1.0, EdgeDir::Falling),
+        vec![5.5]
+    );
+    assert!(data
+        .crossing_times("v(out)", 5.0, EdgeDir::Rising)
+        .is_empty());
+    assert!(data
+        .crossing_times("v(in)", 2.5, EdgeDir::Rising)
+        .is_empty());
+}
+
+#[test]
+fn measurement_balanced_expr() {
+    let meas = Measurement::new(
+        "tran",
+        "delay",
+        "TRIG v(in) VAL=0.5 RISE=1 TARG v(out) VAL=0.5 RISE=1",
+    )
+    .unwrap();
+    assert_eq!(
+        meas.expr(),
+        "TRIG v(in) VAL=0.5 RISE=1 TARG v(out) VAL=0.5 RISE=1"
+    );
+
+    // Unknown functions are permitted.
+    Measurement::new("tran", "power", "avg(myfunc(i(vdd))*1.8) from=1n to=2n").unwrap();
+    // Parentheses inside quotes are ignored.
+    Measurement::new("tran", "param", "param='(a+b'").unwrap();
+
+    let input = SimInput {
+        measurements: vec![meas],
+        ..Default::default()
+    };
+    input.validate().unwrap();
+}
+
+#[test]
+fn measurement_unbalanced_expr() {
+    for expr in ["max v(out", "max v(out))", "", "param='a+b"] {
+        let err = Measurement::new("tran", "vmax", expr).unwrap_err();
+        assert!(
+            matches!(err.source(), ErrorSource::InvalidMeasurement { name, .. } if name == "vmax"),
+            "expected invalid measurement error for {expr:?}, got {err}"
+        );
+    }
+}
+
+#[test]
+fn complex_signal_magnitude_phase() {
+    let h = std::f64::consts::FRAC_1_SQRT_2;
+    let signal = ComplexSignal {
+        real: vec![h, 0., -2.],
+        imag: vec![h, 1., 0.],
+        quantity: Quantity::Voltage,
+    };
+
+    let mag = signal.magnitude();
+    assert_eq!(mag.quantity, Quantity::Voltage);
+    for (actual, expected) in mag.values.iter().zip([1., 1., 2.]) {
+        assert!((actual - expected).abs() < 1e-12);
+    }
+
+    let db = signal.magnitude_db();
+    assert_eq!(db.quantity, Quantity::Unknown);
+    assert!(db[0].abs() < 1e-12);
+    assert!((db[2] - 20. * 2f64.log10()).abs() < 1e-12);
+
+    let phase = signal.phase_degrees();
+    assert_eq!(phase.quantity, Quantity::Unknown);
+    for (actual, expected) in phase.values.iter().zip([45., 90., 180.]) {
+        assert!((actual - expected).abs() < 1e-12);
+    }
+}
+
+/// The response `a / (1 + jf/p)^n`, sampled at 100 points per decade from 1 Hz to 100 MHz.
+fn pole_response(a: f64, p: f64, n: i32) -> AcData {
+    let freq = (0..=800)
+        .map(|i| 10f64.powf(i as f64 / 100.))
+        .collect::<Vec<_>>();
+    let (real, imag) = freq
+        .iter()
+        .map(|f| {
+            let x = f / p;
+            let mag = a / (1. + x * x).powf(n as f64 / 2.);
+            let phase = -(n as f64) * x.atan();
+            (mag * phase.cos(), mag * phase.sin())
+        })
+        .unzip();
+    AcData {
+        data: HashMap::from_iter([(
+            "v(out)".to_string(),
+            ComplexSignal {
+                real,
+                imag,
+                quantity: Quantity::Voltage,
+            },
+        )]),
+        freq: RealSignal {
+            values: freq,
+            quantity: Quantity::Frequency,
+        },
+    }
+}
+
+#[test]
+fn ac_data_single_pole_margins() {
+    let data = pole_response(1000., 1e3, 1);
+
+    let ugf = data.unity_gain_frequency("v(out)").unwrap();
+    assert!((ugf / 1e6 - 1.).abs() < 1e-3, "ugf = {ugf}");
+
+    let pm = data.phase_margin("v(out)").unwrap();
+    assert!((pm - 90.).abs() < 0.1, "pm = {pm}");
+
+    // A single pole never accumulates 180 degrees of phase shift.
+    assert_eq!(data.gain_margin("v(out)"), None);
+    assert_eq!(data.unity_gain_frequency("v(in)"), None);
+}
+
+#[test]
+fn ac_data_three_pole_margins() {
+    let data = pole_response(4., 1e3, 3);
+
+    // |H| = 1 when (1 + x^2)^1.5 = 4.
+    let x = (4f64.powf(2. / 3.) - 1.).sqrt();
+    let ugf = data.unity_gain_frequency("v(out)").unwrap();
+    assert!((ugf / (x * 1e3) - 1.).abs() < 1e-3, "ugf = {ugf}");
+
+    let pm = data.phase_margin("v(out)").unwrap();
+    let expected = 180. - 3. * x.atan().to_degrees();
+    assert!((pm - expected).abs() < 0.1, "pm = {pm}");
+
+    // The phase reaches -180 degrees at x = sqrt(3), where |H| = 4 / 8.
+    let gm = data.gain_margin("v(out)").unwrap();
+    assert!((gm - 20. * 2f64.log10()).abs() < 0.01, "gm = {gm}");
+}
+
+#[test]
+fn ac_data_no_unity_crossing() {
+    let data = pole_response(0.5, 1e3, 1);
+    assert_eq!(data.unity_gain_frequency("v(out)"), None);
+    assert_eq!(data.phase_margin("v(out)"), None);
+}
+
+fn dc_data(x: Vec<f64>, f: impl Fn(f64) -> f64) -> DcData {
+    let y = x.iter().map(|&x| f(x)).collect();
+    DcData {
+        data: HashMap::from_iter([
+            (
+                "v(in)".to_string(),
+                RealSignal {
+                    values: x,
+                    quantity: Quantity::Voltage,
+                },
+            ),
+            (
+                "i(vdd)".to_string(),
+                RealSignal {
+                    values: y,
+                    quantity: Quantity::Current,
+                },
+            ),
+        ]),
+    }
+}
+
+#[test]
+fn dc_data_derivative_linear() {
+    // Unevenly spaced and non-monotonic.
+    let data = dc_data(vec![0., 0.1, 0.4, 0.5, 0.2, -0.3], |x| 3. * x + 1.);
+    let d = data.derivative("i(vdd)", "v(in)").unwrap();
+    assert_eq!(d.len(), 6);
+    for v in d.values {
+        assert!((v - 3.).abs() < 1e-9, "derivative = {v}");
+    }
+
+    assert_eq!(data.derivative("i(vdd)", "v(out)"), None);
+}
+
+#[test]
+fn dc_data_derivative_quadratic() {
+    let x = (0..=20).map(|i| i as f64 * 0.05).collect::<Vec<_>>();
+    let data = dc_data(x.clone(), |x| x * x);
+    let d = data.derivative("i(vdd)", "v(in)").unwrap();
+
+    // Central differences are exact for quadratics on a uniform grid.
+    for (v, x) in d.values.iter().zip(x.iter()).skip(1).take(19) {
+        assert!((v - 2. * x).abs() < 1e-9);
+    }
+    // One-sided differences at the endpoints are first-order accurate.
+    assert!((d[0] - 0.05).abs() < 1e-9);
+    assert!((d[20] - 1.95).abs() < 1e-9);
+}
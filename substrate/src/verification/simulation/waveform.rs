@@ -177,6 +177,86 @@ pub trait TimeWaveform {
 
         integral
     }
+
+    /// Counts the transitions in the given direction between `low_threshold` and `high_threshold`.
+    ///
+    /// See [`TimeWaveform::transitions`].
+    fn count_transitions(&self, dir: EdgeDir, low_threshold: f64, high_threshold: f64) -> usize {
+        self.transitions(low_threshold, high_threshold)
+            .filter(|t| t.dir == dir)
+            .count()
+    }
+
+    /// Estimates the frequency of a periodic digital waveform with supply voltage `vdd`.
+    ///
+    /// Computes the average spacing between rising edges at `vdd / 2`.
+    /// Returns [`None`] if the waveform has fewer than two rising edges.
+    fn estimate_frequency(&self, vdd: f64) -> Option<f64> {
+        let mut rising = self.edges(vdd / 2.0).filter(|e| e.dir.is_rising());
+        let first = rising.next()?;
+        let (n, last) = rising.fold((0, first), |(n, _), e| (n + 1, e));
+        if n == 0 {
+            return None;
+        }
+        let period = (last.t - first.t) / n as f64;
+        Some(1.0 / period)
+    }
+
+    /// Returns the maximum absolute slope between adjacent points on this waveform.
+    ///
+    /// Returns [`None`] if the length of the waveform is less than 2.
+    fn max_slew_rate(&self) -> Option<f64> {
+        let mut max = None;
+        for i in 0..self.len().saturating_sub(1) {
+            let p0 = self.get(i).unwrap();
+            let p1 = self.get(i + 1).unwrap();
+            let rate = ((p1.x - p0.x) / (p1.t - p0.t)).abs();
+            if max.map(|max| rate > max).unwrap_or(true) {
+                max = Some(rate);
+            }
+        }
+        max
+    }
+
+    /// Returns the amount by which this waveform exceeds `steady_state`.
+    ///
+    /// Returns 0.0 if the waveform never exceeds `steady_state`,
+    /// and [`None`] if the waveform is empty.
+    fn overshoot(&self, steady_state: f64) -> Option<f64> {
+        Some((self.max_x()? - steady_state).max(0.0))
+    }
+
+    /// Returns the time after which this waveform remains within
+    /// `tol` of `final_value`.
+    ///
+    /// The time at which the waveform enters the tolerance band for the last
+    /// time is found by linearly interpolating between adjacent points.
+    /// Returns [`None`] if the waveform is empty or does not end within the band.
+    fn settling_time(&self, final_value: f64, tol: f64) -> Option<f64> {
+        let outside = |p: TimePoint| (p.x - final_value).abs() > tol;
+
+        let last = self.last()?;
+        if outside(last) {
+            return None;
+        }
+
+        let idx = match (0..self.len())
+            .rev()
+            .find(|&i| outside(self.get(i).unwrap()))
+        {
+            Some(idx) => idx,
+            None => return self.first_t(),
+        };
+
+        let p0 = self.get(idx).unwrap();
+        let p1 = self.get(idx + 1).unwrap();
+        let edge = if p0.x > final_value {
+            final_value + tol
+        } else {
+            final_value - tol
+        };
+        Some(edge_crossing_time(p0.t, p0.x, p1.t, p1.x, edge))
+    }
 }
 
 fn linear_interp(t0: f64, y0: f64, t1: f64, y1: f64, t: f64) -> f64 {
@@ -744,4 +824,57 @@ mod tests {
         let integral = wav.integral();
         assert!(float_eq!(integral, expected, r2nd <= 1e-8));
     }
+
+    #[test]
+    fn waveform_step_response_measurements() {
+        let wav = Waveform {
+            values: into_vec![
+                (0., 0.),
+                (1., 0.),
+                (2., 1.2),
+                (3., 0.9),
+                (4., 1.05),
+                (5., 0.98),
+                (6., 1.01),
+                (7., 1.)
+            ],
+        };
+
+        let slew = wav.max_slew_rate().unwrap();
+        assert!(float_eq!(slew, 1.2, r2nd <= 1e-8));
+
+        let overshoot = wav.overshoot(1.0).unwrap();
+        assert!(float_eq!(overshoot, 0.2, r2nd <= 1e-8));
+        assert_eq!(wav.overshoot(2.0), Some(0.0));
+
+        // Enters the 2.5% band for the last time between t = 4 and t = 5, crossing 1.025.
+        let settling = wav.settling_time(1.0, 0.025).unwrap();
+        assert!(float_eq!(settling, 4.0 + 0.025 / 0.07, r2nd <= 1e-8));
+
+        // The waveform does not end within a 0.1% band.
+        assert_eq!(wav.settling_time(1.1, 0.001), None);
+        // The waveform is always within a wide band.
+        assert_eq!(wav.settling_time(0.6, 1.0), Some(0.));
+    }
+
+    #[test]
+    fn waveform_count_transitions_and_frequency() {
+        let mut wav = Waveform::with_initial_value(0.);
+        for i in 0..4 {
+            let t0 = 10. * i as f64;
+            wav.push_high(t0 + 5., 1.8, 0.1);
+            wav.push_low(t0 + 10., 1.8, 0.1);
+        }
+
+        assert_eq!(wav.count_transitions(EdgeDir::Rising, 0.18, 1.62), 4);
+        assert_eq!(wav.count_transitions(EdgeDir::Falling, 0.18, 1.62), 4);
+
+        let freq = wav.estimate_frequency(1.8).unwrap();
+        assert!(float_eq!(freq, 0.1, r2nd <= 1e-8));
+
+        let wav = Waveform {
+            values: into_vec![(0., 0.), (1., 1.), (2., 1.)],
+        };
+        assert_eq!(wav.estimate_frequency(1.), None);
+    }
 }
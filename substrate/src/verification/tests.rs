@@ -0,0 +1,58 @@
+use std::fs;
+
+use tempdir::TempDir;
+
+use super::*;
+
+fn populate(dir: &Path) {
+    fs::create_dir_all(dir.join("svdb/reports")).unwrap();
+    fs::create_dir_all(dir.join("scratch")).unwrap();
+    fs::write(dir.join("layout.gds"), "").unwrap();
+    fs::write(dir.join("drc.report"), "").unwrap();
+    fs::write(dir.join("svdb/intermediate.db"), "").unwrap();
+    fs::write(dir.join("svdb/reports/lvs.report"), "").unwrap();
+    fs::write(dir.join("scratch/tmp.txt"), "").unwrap();
+}
+
+#[test]
+fn cleanup_on_success_removes_intermediate_files() {
+    let dir = TempDir::new("cleanup").unwrap();
+    let dir = dir.path();
+    populate(dir);
+
+    let preserve = ["drc.report", "svdb/reports/lvs.report"];
+    CleanupPolicy::OnSuccess
+        .apply(dir, true, &preserve)
+        .unwrap();
+
+    assert!(dir.join("drc.report").exists());
+    assert!(dir.join("svdb/reports/lvs.report").exists());
+    assert!(!dir.join("layout.gds").exists());
+    assert!(!dir.join("svdb/intermediate.db").exists());
+    assert!(!dir.join("scratch").exists());
+}
+
+#[test]
+fn cleanup_on_success_keeps_files_on_failure() {
+    let dir = TempDir::new("cleanup").unwrap();
+    let dir = dir.path();
+    populate(dir);
+
+    CleanupPolicy::OnSuccess
+        .apply(dir, false, &["drc.report"])
+        .unwrap();
+    assert!(dir.join("layout.gds").exists());
+    assert!(dir.join("scratch/tmp.txt").exists());
+
+    CleanupPolicy::Never
+        .apply(dir, true, &["drc.report"])
+        .unwrap();
+    assert!(dir.join("layout.gds").exists());
+
+    CleanupPolicy::Always
+        .apply(dir, false, &["drc.report"])
+        .unwrap();
+    assert!(dir.join("drc.report").exists());
+    assert!(!dir.join("layout.gds").exists());
+    assert!(!dir.join("svdb").exists());
+}
@@ -5,9 +5,9 @@ use std::path::Path;
 use derive_builder::Builder;
 use serde::{Deserialize, Serialize};
 use slotmap::new_key_type;
-use sublut::{Extrapolation, FloatLut1, FloatLut2};
+use sublut::{FloatLut1, FloatLut2, InterpMode};
 
-use super::simulation::waveform::{EdgeDir, SharedWaveform, TimeWaveform};
+use super::simulation::waveform::{edge_crossing_time, EdgeDir, SharedWaveform, TimeWaveform};
 use super::simulation::{Simulator, TranData};
 use crate::log::Log;
 use crate::pdk::corner::Pvt;
@@ -20,6 +20,12 @@ use crate::units::SiPrefix;
 
 pub mod context;
 
+#[cfg(test)]
+mod tests;
+
+/// The default maximum number of setup and hold checks retained in a [`TimingReport`].
+pub const DEFAULT_REPORT_CAPACITY: usize = 8192;
+
 new_key_type! {
     /// A key for referencing signals in the timing API.
     pub struct TimingSignalKey;
@@ -42,6 +48,18 @@ pub struct TimingConfig {
     ///
     /// We do not support separate thresholds for rise and fall transitions.
     slew_thresholds: [f64; 2],
+    /// The maximum number of worst-slack setup checks and hold checks
+    /// retained in generated timing reports.
+    ///
+    /// Defaults to [`DEFAULT_REPORT_CAPACITY`].
+    #[builder(default = "DEFAULT_REPORT_CAPACITY")]
+    #[serde(default = "default_report_capacity")]
+    report_capacity: usize,
+}
+
+#[inline]
+fn default_report_capacity() -> usize {
+    DEFAULT_REPORT_CAPACITY
 }
 
 impl TimingConfigBuilder {
@@ -54,6 +72,9 @@ impl TimingConfigBuilder {
                 ));
             }
         }
+        if self.report_capacity == Some(0) {
+            return Err("Timing report capacity must be positive".to_string());
+        }
         Ok(())
     }
 }
@@ -76,6 +97,43 @@ impl TimingConfig {
     pub fn time_unit(&self) -> SiPrefix {
         self.time_unit
     }
+    #[inline]
+    pub fn report_capacity(&self) -> usize {
+        self.report_capacity
+    }
+
+    /// Finds the times at which `wf` crosses the slew thresholds, scaled by `vdd`.
+    ///
+    /// Returns one `(lower_crossing_time, upper_crossing_time, direction)` tuple
+    /// per transition, using the same thresholds as the timing engine.
+    /// Crossing times are linearly interpolated between samples. Note that
+    /// for falling transitions, the upper threshold is crossed first.
+    pub fn crossings(&self, wf: SharedWaveform, vdd: f64) -> Vec<(f64, f64, EdgeDir)> {
+        let low = self.slew_lower_thresh() * vdd;
+        let high = self.slew_upper_thresh() * vdd;
+        let cross = |idx: usize, thresh: f64| {
+            let p0 = wf.get(idx).unwrap();
+            let p1 = wf.get(idx + 1).unwrap();
+            edge_crossing_time(p0.t(), p0.x(), p1.t(), p1.x(), thresh)
+        };
+
+        wf.transitions(low, high)
+            .map(|tr| {
+                // All samples strictly between `start_idx` and `end_idx`
+                // lie between the two thresholds.
+                let (first, second) = match tr.dir() {
+                    EdgeDir::Rising => (low, high),
+                    EdgeDir::Falling => (high, low),
+                };
+                let t_first = cross(tr.start_idx(), first);
+                let t_second = cross(tr.end_idx() - 1, second);
+                match tr.dir() {
+                    EdgeDir::Rising => (t_first, t_second, EdgeDir::Rising),
+                    EdgeDir::Falling => (t_second, t_first, EdgeDir::Falling),
+                }
+            })
+            .collect()
+    }
 
     /// Converts a value in seconds to a value in units of `time_unit`.
     #[inline]
@@ -249,9 +307,11 @@ impl PartialEq for MinSlack {
 
 impl Eq for MinSlack {}
 
+/// Orders checks by slack, so that the top of a [`BinaryHeap`] of checks
+/// is the check with the largest slack; ie. the first to be evicted.
 impl Ord for MinSlack {
     fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        other.0.slack.total_cmp(&self.0.slack)
+        self.0.slack.total_cmp(&other.0.slack)
     }
 }
 
@@ -287,6 +347,18 @@ impl TimingReport {
         TimingReportBuilder::default()
     }
 
+    /// The retained setup checks, sorted in order of increasing slack.
+    #[inline]
+    pub fn setup_checks(&self) -> &[TimingCheck] {
+        &self.setup_checks
+    }
+
+    /// The retained hold checks, sorted in order of increasing slack.
+    #[inline]
+    pub fn hold_checks(&self) -> &[TimingCheck] {
+        &self.hold_checks
+    }
+
     pub fn is_failure(&self) -> bool {
         let setup_fail = self
             .setup_checks
@@ -310,7 +382,7 @@ impl TimingReport {
 
 impl Default for TimingReportBuilder {
     fn default() -> Self {
-        Self::with_capacity(8192)
+        Self::with_capacity(DEFAULT_REPORT_CAPACITY)
     }
 }
 
@@ -325,8 +397,18 @@ impl TimingReportBuilder {
 
     pub fn build(self) -> TimingReport {
         TimingReport {
-            setup_checks: self.setup_checks.into_iter().map(|m| m.0).collect(),
-            hold_checks: self.hold_checks.into_iter().map(|m| m.0).collect(),
+            setup_checks: self
+                .setup_checks
+                .into_sorted_vec()
+                .into_iter()
+                .map(|m| m.0)
+                .collect(),
+            hold_checks: self
+                .hold_checks
+                .into_sorted_vec()
+                .into_iter()
+                .map(|m| m.0)
+                .collect(),
         }
     }
 }
@@ -380,7 +462,7 @@ impl TimingReportBuilder {
         } else {
             let max_slack = self.hold_checks.peek().unwrap().0.slack;
             if slack < max_slack {
-                self.setup_checks.pop();
+                self.hold_checks.pop();
                 self.hold_checks.push(MinSlack(check()));
             }
         }
@@ -509,6 +591,21 @@ impl<'a> TopConstraintDb<'a> {
     }
 }
 
+/// Looks up a setup or hold time, clamping out of range transition times
+/// to the range of the table.
+///
+/// Logs a warning if the lookup requires extrapolation.
+fn lookup_constraint(lut: &FloatLut2, idx1: f64, idx2: f64) -> f64 {
+    if let Some(value) = lut.getf(idx1, idx2) {
+        return value;
+    }
+    crate::log::warn!(
+        "transition times ({idx1}, {idx2}) are outside the range of the constraint table; clamping to the nearest entry"
+    );
+    lut.getf_with(idx1, idx2, InterpMode::ClampedLinear)
+        .expect("constraint table should not be empty")
+}
+
 pub(crate) fn verify_setup_hold_constraint(
     constraint: &SetupHoldConstraint,
     port: SharedWaveform,
@@ -546,17 +643,10 @@ pub(crate) fn verify_setup_hold_constraint(
                 ) {
                     let idx1 = config.to_time_unit(tr.duration());
                     let idx2 = config.to_time_unit(clk_edge.duration());
-                    // TODO handle extrapolation and add warning
                     let tsu = if tr.dir().is_rising() {
-                        constraint
-                            .rise
-                            .getf_extrapolate(idx1, idx2, Extrapolation::RoundUp)
-                            .unwrap()
+                        lookup_constraint(&constraint.rise, idx1, idx2)
                     } else {
-                        constraint
-                            .fall
-                            .getf_extrapolate(idx1, idx2, Extrapolation::RoundUp)
-                            .unwrap()
+                        lookup_constraint(&constraint.fall, idx1, idx2)
                     };
 
                     let tsu = config.from_time_unit(tsu);
@@ -578,17 +668,10 @@ pub(crate) fn verify_setup_hold_constraint(
                 ) {
                     let idx1 = config.to_time_unit(tr.duration());
                     let idx2 = config.to_time_unit(clk_edge.duration());
-                    // TODO handle extrapolation and add warning
                     let t_hold = if tr.dir().is_rising() {
-                        constraint
-                            .rise
-                            .getf_extrapolate(idx1, idx2, Extrapolation::RoundUp)
-                            .unwrap()
+                        lookup_constraint(&constraint.rise, idx1, idx2)
                     } else {
-                        constraint
-                            .fall
-                            .getf_extrapolate(idx1, idx2, Extrapolation::RoundUp)
-                            .unwrap()
+                        lookup_constraint(&constraint.fall, idx1, idx2)
                     };
 
                     let t_hold = config.from_time_unit(t_hold);
@@ -612,7 +695,7 @@ pub(crate) fn generate_timing_report<'a>(
     simulator: &'a dyn Simulator,
     config: &'a TimingConfig,
 ) -> TimingReport {
-    let mut report = TimingReport::builder();
+    let mut report = TimingReportBuilder::with_capacity(config.report_capacity());
     for constraint in constraints {
         match constraint.constraint {
             TimingConstraint::SetupHold(c) => {
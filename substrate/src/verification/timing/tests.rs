@@ -0,0 +1,96 @@
+use super::*;
+
+fn check(slack: f64) -> TimingCheck {
+    let path = NamedSignalPathBuf {
+        insts: Vec::new(),
+        signal: arcstr::literal!("d"),
+        idx: None,
+    };
+    TimingCheck {
+        slack,
+        time: 0.0,
+        port: path.clone(),
+        related_port: path,
+    }
+}
+
+#[test]
+fn timing_config_report_capacity() {
+    let config = TimingConfig::builder()
+        .time_unit(SiPrefix::Nano)
+        .slew_thresholds([0.2, 0.8])
+        .build()
+        .unwrap();
+    assert_eq!(config.report_capacity(), DEFAULT_REPORT_CAPACITY);
+
+    let config = TimingConfig::builder()
+        .time_unit(SiPrefix::Nano)
+        .slew_thresholds([0.2, 0.8])
+        .report_capacity(50)
+        .build()
+        .unwrap();
+    assert_eq!(config.report_capacity(), 50);
+
+    assert!(TimingConfig::builder()
+        .time_unit(SiPrefix::Nano)
+        .slew_thresholds([0.2, 0.8])
+        .report_capacity(0)
+        .build()
+        .is_err());
+}
+
+#[test]
+fn timing_config_crossings() {
+    let config = TimingConfig::builder()
+        .time_unit(SiPrefix::Nano)
+        .slew_thresholds([0.2, 0.8])
+        .build()
+        .unwrap();
+
+    // Rises from 0 to 1 between t=1 and t=2, then falls back to 0 between t=4 and t=6.
+    let t = [0.0, 1.0, 2.0, 3.0, 4.0, 6.0, 7.0];
+    let x = [0.0, 0.0, 1.0, 1.0, 1.0, 0.0, 0.0];
+    let wf = SharedWaveform::new(&t, &x);
+
+    let crossings = config.crossings(wf, 1.0);
+    assert_eq!(crossings.len(), 2);
+
+    let (lo, hi, dir) = crossings[0];
+    assert_eq!(dir, EdgeDir::Rising);
+    assert!((lo - 1.2).abs() < 1e-12);
+    assert!((hi - 1.8).abs() < 1e-12);
+
+    let (lo, hi, dir) = crossings[1];
+    assert_eq!(dir, EdgeDir::Falling);
+    assert!((lo - 5.6).abs() < 1e-12);
+    assert!((hi - 4.4).abs() < 1e-12);
+
+    // Thresholds scale with `vdd`.
+    let x = x.map(|x| 2.0 * x);
+    let crossings = config.crossings(SharedWaveform::new(&t, &x), 2.0);
+    assert_eq!(crossings.len(), 2);
+    assert!((crossings[0].0 - 1.2).abs() < 1e-12);
+    assert!((crossings[1].1 - 4.4).abs() < 1e-12);
+}
+
+#[test]
+fn timing_report_retains_worst_checks() {
+    let mut report = TimingReportBuilder::with_capacity(50);
+    for i in 0..100 {
+        // Interleave slacks so that the worst checks are not added contiguously.
+        let slack = ((i * 37) % 100) as f64 - 10.0;
+        report.add_setup_check(slack, || check(slack));
+        report.add_hold_check(slack, || check(slack));
+    }
+    let report = report.build();
+
+    assert_eq!(report.setup_checks().len(), 50);
+    assert_eq!(report.hold_checks().len(), 50);
+    for (i, c) in report.setup_checks().iter().enumerate() {
+        assert_eq!(c.slack(), i as f64 - 10.0);
+    }
+    for (i, c) in report.hold_checks().iter().enumerate() {
+        assert_eq!(c.slack(), i as f64 - 10.0);
+    }
+    assert!(report.is_failure());
+}
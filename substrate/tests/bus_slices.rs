@@ -0,0 +1,106 @@
+use arcstr::ArcStr;
+use common::setup_ctx;
+use substrate::component::{Component, NoParams};
+use substrate::index::IndexOwned;
+use substrate::schematic::circuit::{Direction, PortError, PortSlice};
+use substrate::schematic::signal::Signal;
+
+mod common;
+
+/// A 4-bit register with an opaque implementation.
+pub struct Reg4;
+
+impl Component for Reg4 {
+    type Params = NoParams;
+    fn new(
+        _params: &Self::Params,
+        _ctx: &substrate::data::SubstrateCtx,
+    ) -> substrate::error::Result<Self> {
+        Ok(Self)
+    }
+
+    fn name(&self) -> ArcStr {
+        arcstr::literal!("reg4")
+    }
+
+    fn schematic(
+        &self,
+        ctx: &mut substrate::schematic::context::SchematicCtx,
+    ) -> substrate::error::Result<()> {
+        let _d = ctx.bus_port("d", 4, Direction::Input);
+        let _q = ctx.bus_port("q", 4, Direction::Output);
+
+        ctx.set_spice("* An opaque implementation of a 4-bit register");
+        Ok(())
+    }
+}
+
+/// Registers the upper half of an 8-bit bus, swapping the halves of the output.
+pub struct UpperReg;
+
+impl Component for UpperReg {
+    type Params = NoParams;
+    fn new(
+        _params: &Self::Params,
+        _ctx: &substrate::data::SubstrateCtx,
+    ) -> substrate::error::Result<Self> {
+        Ok(Self)
+    }
+
+    fn name(&self) -> ArcStr {
+        arcstr::literal!("upper_reg")
+    }
+
+    fn schematic(
+        &self,
+        ctx: &mut substrate::schematic::context::SchematicCtx,
+    ) -> substrate::error::Result<()> {
+        let a = ctx.bus_port("a", 8, Direction::Input);
+        let y = ctx.bus_port("y", 4, Direction::Output);
+
+        ctx.instantiate::<Reg4>(&NoParams)?
+            .named("reg0")
+            .with_connection_slice(PortSlice::new("d", 0..4), a.index(4..8))?
+            .with_connection_slice(PortSlice::new("q", 0..2), y.index(2..4))?
+            .with_connection_slice(PortSlice::new("q", 2..4), y.index(0..2))?
+            .add_to(ctx);
+        Ok(())
+    }
+}
+
+#[test]
+fn test_bus_slice_netlist() {
+    let ctx = setup_ctx();
+    let mut out = Vec::new();
+    ctx.write_schematic::<UpperReg, _>(&NoParams, &mut out)
+        .expect("failed to write schematic");
+    let netlist = String::from_utf8(out).unwrap();
+    println!("{netlist}");
+
+    assert!(netlist.contains(
+        "Xreg0\n+ a[4]\n+ a[5]\n+ a[6]\n+ a[7]\n+ y[2]\n+ y[3]\n+ y[0]\n+ y[1]\n+ reg4\n"
+    ));
+}
+
+#[test]
+fn test_bus_slice_errors() {
+    let ctx = setup_ctx();
+    let mut inst = ctx.instantiate_schematic::<Reg4>(&NoParams).unwrap();
+
+    assert!(matches!(
+        inst.connect_slice(PortSlice::new("d", 0..4), Signal::new(Vec::new())),
+        Err(PortError::WidthMismatch {
+            expected: 4,
+            found: 0,
+            ..
+        })
+    ));
+    assert!(matches!(
+        inst.connect_slice(PortSlice::new("d", 5..5), Signal::new(Vec::new())),
+        Err(PortError::SliceOutOfBounds { width: 4, .. })
+    ));
+    assert!(matches!(
+        inst.connect_slice(PortSlice::new("e", 0..0), Signal::new(Vec::new())),
+        Err(PortError::PortNotFound(_))
+    ));
+}
@@ -4,9 +4,11 @@ use std::path::PathBuf;
 
 use ngspice::Ngspice;
 use sky130_open_pdk::Sky130OpenPdk;
-use substrate::data::{SubstrateConfig, SubstrateCtx};
+use substrate::data::{SubstrateConfig, SubstrateConfigBuilder, SubstrateCtx};
 use substrate::pdk::PdkParams;
 use substrate::schematic::netlist::impls::spice::SpiceNetlister;
+use substrate::schematic::netlist::interface::Netlister;
+use substrate::verification::drc::DrcTool;
 use substrate::verification::simulation::{Simulator, SimulatorOpts};
 use substrate::verification::timing::TimingConfig;
 
@@ -32,6 +34,25 @@ pub fn out_path(test_name: &str, file_name: &str) -> PathBuf {
 }
 
 pub fn setup_ctx() -> SubstrateCtx {
+    setup_ctx_with_netlister(SpiceNetlister::new())
+}
+
+/// Creates a [`SubstrateCtx`] that uses the given netlister.
+pub fn setup_ctx_with_netlister(netlister: impl Netlister + 'static) -> SubstrateCtx {
+    let cfg = config_builder().netlister(netlister).build();
+    SubstrateCtx::from_config(cfg).unwrap()
+}
+
+/// Creates a [`SubstrateCtx`] that uses the given DRC tool.
+pub fn setup_ctx_with_drc_tool(drc_tool: impl DrcTool + 'static) -> SubstrateCtx {
+    let cfg = config_builder()
+        .netlister(SpiceNetlister::new())
+        .drc_tool(drc_tool)
+        .build();
+    SubstrateCtx::from_config(cfg).unwrap()
+}
+
+fn config_builder() -> SubstrateConfigBuilder {
     let simulator = Ngspice::new(SimulatorOpts::default()).unwrap();
     let pdk_root = std::env::var("SKY130_OPEN_PDK_ROOT").expect("the SKY130_OPEN_PDK_ROOT environment variable should be set to the root of the skywater-pdk repository").into();
 
@@ -41,11 +62,10 @@ pub fn setup_ctx() -> SubstrateCtx {
         .build()
         .unwrap();
 
-    let cfg = SubstrateConfig::builder()
-        .netlister(SpiceNetlister::new())
+    let mut builder = SubstrateConfig::builder();
+    builder
         .simulator(simulator)
         .timing_config(timing_config)
-        .pdk(Sky130OpenPdk::new(&PdkParams { pdk_root }).unwrap())
-        .build();
-    SubstrateCtx::from_config(cfg).unwrap()
+        .pdk(Sky130OpenPdk::new(&PdkParams { pdk_root }).unwrap());
+    builder
 }
@@ -2,12 +2,16 @@ use arcstr::ArcStr;
 use subgeom::{Point, Rect};
 use substrate::component::{Component, NoParams};
 use substrate::data::SubstrateCtx;
+use substrate::error::ErrorSource;
 use substrate::layout::context::LayoutCtx;
 use substrate::layout::layers::selector::Selector;
 use substrate::schematic::circuit::Direction;
 use substrate::schematic::context::SchematicCtx;
 use substrate::schematic::elements::resistor::Resistor;
 use substrate::units::{SiPrefix, SiValue};
+use substrate::verification::simulation::testbench::{BundledTestbench, ComponentTestbench};
+
+use self::tb::VDividerTb;
 
 pub mod array;
 pub mod tb;
@@ -47,4 +51,20 @@ impl Component for VDivider {
     fn name(&self) -> ArcStr {
         arcstr::literal!("vdivider")
     }
+
+    fn testbench(&self) -> Option<Box<dyn ComponentTestbench>> {
+        Some(Box::new(
+            BundledTestbench::<VDividerTb>::new(NoParams).with_check(|output| {
+                // The voltage divider ratio should be 1/3
+                if (output.ratio - 1.0 / 3.0).abs() > 1e-6 {
+                    return Err(ErrorSource::TestbenchFailed(format!(
+                        "expected a divider ratio of 1/3, got {}",
+                        output.ratio
+                    ))
+                    .into());
+                }
+                Ok(())
+            }),
+        ))
+    }
 }
@@ -4,6 +4,7 @@ mod common;
 use common::common_source::CommonSourceAmp;
 use common::vdivider::array::VDividerArray;
 use common::vdivider::tb::VDividerTb;
+use common::vdivider::VDivider;
 use common::{out_path, setup_ctx};
 
 #[test]
@@ -26,6 +27,25 @@ fn test_vdivider() {
     assert_eq!(output.ratio, 1.0 / 3.0);
 }
 
+#[test]
+#[ignore = "slow"]
+fn test_vdivider_bundled_testbench() {
+    let ctx = setup_ctx();
+    ctx.write_component_simulation::<VDivider>(
+        &NoParams,
+        out_path("test_vdivider_bundled_testbench", "sim"),
+    )
+    .expect("bundled testbench failed");
+}
+
+#[test]
+fn test_common_source_has_no_testbench() {
+    let ctx = setup_ctx();
+    assert!(ctx
+        .simulate_component::<CommonSourceAmp>(&NoParams)
+        .is_err());
+}
+
 #[test]
 fn test_common_source() {
     let ctx = setup_ctx();
@@ -0,0 +1,69 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+
+use arcstr::ArcStr;
+use common::{out_path, setup_ctx_with_drc_tool};
+use substrate::error::Result;
+use substrate::layout::LayoutFormat;
+use substrate::verification::drc::{DrcInput, DrcOutput, DrcSummary, DrcTool};
+use substrate::verification::CleanupPolicy;
+
+mod common;
+
+/// A DRC tool that writes an intermediate database and a report to its working directory.
+struct MockDrcTool;
+
+impl DrcTool for MockDrcTool {
+    fn run_drc(&self, input: DrcInput) -> Result<DrcOutput> {
+        fs::create_dir_all(input.work_dir.join("svdb"))?;
+        fs::write(input.work_dir.join("svdb/drc.db"), "")?;
+        fs::write(input.work_dir.join("drc.report"), "")?;
+        Ok(DrcOutput {
+            summary: DrcSummary::Pass,
+            errors: Vec::new(),
+        })
+    }
+
+    fn rule_checks(&self, _input: &DrcInput) -> Result<Option<Vec<ArcStr>>> {
+        Ok(Some(vec!["M1.W.1".into(), "M1.S.1".into()]))
+    }
+
+    fn report_paths(&self, _input: &DrcInput) -> Vec<PathBuf> {
+        vec![PathBuf::from("drc.report")]
+    }
+}
+
+fn input(work_dir: PathBuf, partitions: usize) -> DrcInput {
+    DrcInput {
+        cell_name: "top".into(),
+        layout_path: work_dir.join("layout.gds"),
+        work_dir,
+        layout_format: LayoutFormat::Gds,
+        opts: HashMap::new(),
+        waivers: Vec::new(),
+        rules: None,
+        partitions,
+        cleanup: CleanupPolicy::OnSuccess,
+    }
+}
+
+#[test]
+fn test_drc_cleanup_keeps_reports() {
+    let ctx = setup_ctx_with_drc_tool(MockDrcTool);
+
+    let work_dir = out_path("test_drc_cleanup_keeps_reports", "drc");
+    let _ = fs::remove_dir_all(&work_dir);
+    let output = ctx.run_drc(input(work_dir.clone(), 1)).unwrap();
+    assert!(output.summary.is_ok());
+    assert!(work_dir.join("drc.report").exists());
+    assert!(!work_dir.join("svdb").exists());
+
+    let work_dir = out_path("test_drc_cleanup_keeps_reports", "drc_partitioned");
+    let _ = fs::remove_dir_all(&work_dir);
+    ctx.run_drc(input(work_dir.clone(), 2)).unwrap();
+    for partition in ["partition_0", "partition_1"] {
+        assert!(work_dir.join(partition).join("drc.report").exists());
+        assert!(!work_dir.join(partition).join("svdb").exists());
+    }
+}
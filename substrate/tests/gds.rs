@@ -6,6 +6,7 @@ use subgeom::Shape;
 use substrate::component::{Component, NoParams, View};
 use substrate::data::SubstrateCtx;
 use substrate::layout::layers::{GdsLayerSpec, LayerPurpose};
+use substrate::layout::LayoutFormat;
 
 mod common;
 use common::vdivider::array::VDividerArray;
@@ -129,6 +130,30 @@ fn test_gds_export() {
     assert_eq!(array.insts().count(), 10);
 }
 
+#[test]
+fn test_write_cell_layout() {
+    let gds_path = out_path(
+        "test_write_cell_layout",
+        &format!("layout.{}", LayoutFormat::Gds.extension()),
+    );
+    let ctx = setup_ctx();
+    let inst = ctx
+        .instantiate_layout::<VDividerArray>(&NoParams)
+        .expect("failed to instantiate layout");
+    ctx.write_cell_layout(inst.cell().clone(), &gds_path, LayoutFormat::Gds)
+        .expect("failed to write layout");
+
+    let name = VDividerArray::new(&NoParams, &ctx)
+        .expect("failed to create VDividerArray struct")
+        .name();
+
+    let cell_map = setup_ctx()
+        .from_gds(gds_path)
+        .expect("failed to import GDS file");
+    let array = cell_map.get(&name).unwrap();
+    assert_eq!(array.insts().count(), 10);
+}
+
 #[test]
 fn test_gds_reexport() {
     let gds_path = out_path("test_gds_reexport", "layout.gds");
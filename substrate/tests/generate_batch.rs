@@ -0,0 +1,189 @@
+use std::sync::Arc;
+
+use arcstr::ArcStr;
+use common::setup_ctx;
+use substrate::component::{Component, NoParams, View};
+use substrate::schematic::circuit::Direction;
+
+mod common;
+
+pub struct Buffer;
+
+impl Component for Buffer {
+    type Params = NoParams;
+    fn new(
+        _params: &Self::Params,
+        _ctx: &substrate::data::SubstrateCtx,
+    ) -> substrate::error::Result<Self> {
+        Ok(Self)
+    }
+
+    fn name(&self) -> ArcStr {
+        arcstr::literal!("buffer")
+    }
+
+    fn schematic(
+        &self,
+        ctx: &mut substrate::schematic::context::SchematicCtx,
+    ) -> substrate::error::Result<()> {
+        let _input = ctx.port("input", Direction::Input);
+        let _output = ctx.port("output", Direction::Output);
+
+        ctx.set_spice("* An opaque implementation of a buffer");
+        Ok(())
+    }
+}
+
+/// A chain of buffers whose length is given by the parameter.
+pub struct BufferChain(usize);
+
+impl Component for BufferChain {
+    type Params = usize;
+
+    fn new(
+        params: &Self::Params,
+        _ctx: &substrate::data::SubstrateCtx,
+    ) -> substrate::error::Result<Self> {
+        Ok(Self(*params))
+    }
+
+    fn name(&self) -> ArcStr {
+        arcstr::format!("buffer_chain_{}", self.0)
+    }
+
+    fn schematic(
+        &self,
+        ctx: &mut substrate::schematic::context::SchematicCtx,
+    ) -> substrate::error::Result<()> {
+        let input = ctx.port("input", Direction::Input);
+        let out = ctx.port("out", Direction::Output);
+
+        let mut prev = input;
+        for i in 0..self.0 {
+            let next = if i + 1 == self.0 {
+                out
+            } else {
+                ctx.signal(arcstr::format!("mid_{i}"))
+            };
+            let mut buf = ctx.instantiate::<Buffer>(&NoParams)?;
+            buf.set_name(arcstr::format!("buf{i}"));
+            buf.connect("input", prev);
+            buf.connect("output", next);
+            ctx.add_instance(buf);
+            prev = next;
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn test_generate_batch_schematics() {
+    let ctx = setup_ctx();
+    let params = (1..=16).collect::<Vec<usize>>();
+
+    let results = ctx.generate_batch::<BufferChain>(&params, View::Schematic);
+    assert_eq!(results.len(), params.len());
+    for result in results {
+        result.unwrap();
+    }
+
+    // Generated schematics are cached, so re-instantiating them
+    // yields the same modules.
+    let first = ctx.instantiate_schematic::<BufferChain>(&4).unwrap();
+    let second = ctx.instantiate_schematic::<BufferChain>(&4).unwrap();
+    assert!(Arc::ptr_eq(
+        &first.module().local().unwrap(),
+        &second.module().local().unwrap()
+    ));
+}
+
+#[test]
+fn test_generate_batch_unsupported_view() {
+    let ctx = setup_ctx();
+    let results = ctx.generate_batch::<BufferChain>(&[1, 2], View::Layout);
+    assert_eq!(results.len(), 2);
+    assert!(results.iter().all(|result| result.is_err()));
+}
+
+/// A component whose schematic generator always panics.
+pub struct PanickingBuffer;
+
+impl Component for PanickingBuffer {
+    type Params = NoParams;
+    fn new(
+        _params: &Self::Params,
+        _ctx: &substrate::data::SubstrateCtx,
+    ) -> substrate::error::Result<Self> {
+        Ok(Self)
+    }
+
+    fn name(&self) -> ArcStr {
+        arcstr::literal!("panicking_buffer")
+    }
+
+    fn schematic(
+        &self,
+        _ctx: &mut substrate::schematic::context::SchematicCtx,
+    ) -> substrate::error::Result<()> {
+        panic!("failed to generate buffer");
+    }
+}
+
+/// Instantiates a [`PanickingBuffer`] if the parameter is odd, and a [`Buffer`] otherwise.
+pub struct MaybePanicking(usize);
+
+impl Component for MaybePanicking {
+    type Params = usize;
+
+    fn new(
+        params: &Self::Params,
+        _ctx: &substrate::data::SubstrateCtx,
+    ) -> substrate::error::Result<Self> {
+        Ok(Self(*params))
+    }
+
+    fn name(&self) -> ArcStr {
+        arcstr::format!("maybe_panicking_{}", self.0)
+    }
+
+    fn schematic(
+        &self,
+        ctx: &mut substrate::schematic::context::SchematicCtx,
+    ) -> substrate::error::Result<()> {
+        let input = ctx.port("input", Direction::Input);
+        let output = ctx.port("output", Direction::Output);
+
+        let mut buf = if self.0 % 2 == 1 {
+            ctx.instantiate::<PanickingBuffer>(&NoParams)?
+        } else {
+            ctx.instantiate::<Buffer>(&NoParams)?
+        };
+        buf.set_name("buf");
+        buf.connect("input", input);
+        buf.connect("output", output);
+        ctx.add_instance(buf);
+        Ok(())
+    }
+}
+
+#[test]
+fn test_generate_batch_panicking_generator() {
+    let ctx = setup_ctx();
+    let params = (0..16).collect::<Vec<usize>>();
+
+    // Every odd entry shares the panicking subcomponent, so threads waiting on it
+    // must not hang when the thread generating it panics.
+    let results = ctx.generate_batch::<MaybePanicking>(&params, View::Schematic);
+    assert_eq!(results.len(), params.len());
+    for (i, result) in results.into_iter().enumerate() {
+        assert_eq!(
+            result.is_err(),
+            i % 2 == 1,
+            "unexpected result for entry {i}"
+        );
+    }
+
+    // The panicking component is aborted rather than left loading.
+    let results = ctx.generate_batch::<MaybePanicking>(&[1], View::Schematic);
+    assert!(results[0].is_err());
+}
@@ -1,11 +1,43 @@
 use std::path::PathBuf;
 
 use common::{setup_ctx, DATA_DIR};
-use subgeom::Span;
-use substrate::layout::straps::{SingleSupplyNet, Strap, StrapConfig};
+use subgeom::{Point, Rect, Span};
+use substrate::component::{Component, NoParams};
+use substrate::error::ErrorSource;
+use substrate::layout::straps::{PowerStrapError, SingleSupplyNet, Strap, StrapConfig};
 
 mod common;
 
+/// Stitches the two layers of the example Hammer power straps together with vias.
+pub struct StrapGrid;
+
+impl Component for StrapGrid {
+    type Params = NoParams;
+    fn new(
+        _params: &Self::Params,
+        _ctx: &substrate::data::SubstrateCtx,
+    ) -> substrate::error::Result<Self> {
+        Ok(Self)
+    }
+
+    fn name(&self) -> arcstr::ArcStr {
+        arcstr::literal!("strap_grid")
+    }
+
+    fn layout(
+        &self,
+        ctx: &mut substrate::layout::context::LayoutCtx,
+    ) -> substrate::error::Result<()> {
+        let path = PathBuf::from(DATA_DIR).join("hammer/power_straps_sky130.json");
+        let straps =
+            StrapConfig::<SingleSupplyNet>::from_hammer_json_file(path, "macro", ctx.inner())?;
+        let vias =
+            straps.connect_layers(ctx, Rect::new(Point::zero(), Point::new(22_000, 23_000)))?;
+        ctx.draw(vias)?;
+        Ok(())
+    }
+}
+
 #[test]
 fn test_hammer_straps() {
     let ctx = setup_ctx();
@@ -43,3 +75,179 @@ fn test_hammer_straps() {
     assert_eq!(top.strap_pitch(), 1_000);
     assert_eq!(top.straps_until(23_000).count(), 6);
 }
+
+#[test]
+fn test_hammer_straps_invalid_layers() {
+    let ctx = setup_ctx();
+    let json = r#"
+[
+    {
+        "macro": [
+            {
+                "layer": "met99",
+                "direction": "horizontal",
+                "net_order": ["VSS", "VDD"],
+                "width": 600,
+                "spacing": 400,
+                "group_pitch": 10000,
+                "inst_paths": [],
+                "inst_orientations": []
+            },
+            {
+                "layer": "poly",
+                "direction": "vertical",
+                "net_order": ["VSS", "VDD"],
+                "width": 1200,
+                "spacing": 1400,
+                "group_pitch": 10000,
+                "inst_paths": [],
+                "inst_orientations": []
+            }
+        ]
+    }
+]
+"#;
+    let err = StrapConfig::<SingleSupplyNet>::from_hammer_json(json, "macro", &ctx).unwrap_err();
+    match err.source() {
+        ErrorSource::PowerStrapError(PowerStrapError::InvalidHammerLayers {
+            missing,
+            not_metal,
+        }) => {
+            assert_eq!(missing, &vec!["met99".to_string()]);
+            assert_eq!(not_metal, &vec!["poly".to_string()]);
+        }
+        source => panic!("unexpected error: {source}"),
+    }
+}
+
+#[test]
+fn test_hammer_straps_ignores_other_macros() {
+    let ctx = setup_ctx();
+    let json = r#"
+[
+    {
+        "other": [
+            {
+                "layer": "met99",
+                "direction": "horizontal",
+                "net_order": ["VSS", "VDD"],
+                "width": 600,
+                "spacing": 400,
+                "group_pitch": 10000,
+                "inst_paths": [],
+                "inst_orientations": []
+            }
+        ]
+    },
+    {
+        "macro": [
+            {
+                "layer": "met3",
+                "direction": "horizontal",
+                "net_order": ["VSS", "VDD"],
+                "width": 600,
+                "spacing": 400,
+                "group_pitch": 10000,
+                "inst_paths": [],
+                "inst_orientations": []
+            }
+        ]
+    }
+]
+"#;
+    // Invalid layers in unrelated macros do not prevent loading the requested macro.
+    StrapConfig::<SingleSupplyNet>::from_hammer_json(json, "macro", &ctx).unwrap();
+    let err = StrapConfig::<SingleSupplyNet>::from_hammer_json(json, "other", &ctx).unwrap_err();
+    assert!(matches!(
+        err.source(),
+        ErrorSource::PowerStrapError(PowerStrapError::InvalidHammerLayers { .. })
+    ));
+}
+
+#[test]
+fn test_hammer_straps_within() {
+    let ctx = setup_ctx();
+    let path = PathBuf::from(DATA_DIR).join("hammer/power_straps_sky130.json");
+    let straps =
+        StrapConfig::<SingleSupplyNet>::from_hammer_json_file(path, "macro", &ctx).unwrap();
+    let top = straps.top();
+
+    // Without clipping, the final strap would overhang the boundary at 21,000.
+    assert_eq!(
+        top.straps_until(21_000).last().unwrap().span().stop(),
+        21_100
+    );
+
+    let clipped = top
+        .straps_within(Span::new(1_000, 21_000))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        clipped,
+        vec![
+            Strap::new(SingleSupplyNet::Vss, Span::new(1_000, 1_100)),
+            Strap::new(SingleSupplyNet::Vdd, Span::new(1_500, 2_100)),
+            Strap::new(SingleSupplyNet::Vss, Span::new(10_500, 11_100)),
+            Strap::new(SingleSupplyNet::Vdd, Span::new(11_500, 12_100)),
+            Strap::new(SingleSupplyNet::Vss, Span::new(20_500, 21_000)),
+        ]
+    );
+    assert!(clipped
+        .iter()
+        .all(|strap| Span::new(1_000, 21_000).contains(strap.span())));
+}
+
+#[test]
+fn test_hammer_straps_connect_layers() {
+    let ctx = setup_ctx();
+    let inst = ctx.instantiate_layout::<StrapGrid>(&NoParams).unwrap();
+
+    // Within the area, there are 3 VSS and 3 VDD straps on met3,
+    // and 3 VSS and 2 VDD straps on met4.
+    // Only straps on the same net should be connected.
+    assert_eq!(inst.cell().insts().count(), 3 * 3 + 3 * 2);
+}
+
+#[test]
+fn test_hammer_straps_for_net() {
+    let ctx = setup_ctx();
+    let json = r#"
+[
+    {
+        "macro": [
+            {
+                "layer": "met3",
+                "direction": "horizontal",
+                "net_order": ["VDD", "VSS", "VDD"],
+                "width": 600,
+                "spacing": 400,
+                "group_pitch": 10000,
+                "offset": 500,
+                "inst_paths": [],
+                "inst_orientations": []
+            }
+        ]
+    }
+]
+"#;
+    let straps = StrapConfig::<SingleSupplyNet>::from_hammer_json(json, "macro", &ctx).unwrap();
+    let top = straps.top();
+
+    assert_eq!(
+        top.straps_for_net(&SingleSupplyNet::Vdd, 13_000)
+            .collect::<Vec<_>>(),
+        vec![
+            Strap::new(SingleSupplyNet::Vdd, Span::new(500, 1_100)),
+            Strap::new(SingleSupplyNet::Vdd, Span::new(2_500, 3_100)),
+            Strap::new(SingleSupplyNet::Vdd, Span::new(10_500, 11_100)),
+            Strap::new(SingleSupplyNet::Vdd, Span::new(12_500, 13_100)),
+        ]
+    );
+    assert_eq!(
+        top.straps_for_net(&SingleSupplyNet::Vss, 13_000)
+            .collect::<Vec<_>>(),
+        vec![
+            Strap::new(SingleSupplyNet::Vss, Span::new(1_500, 2_100)),
+            Strap::new(SingleSupplyNet::Vss, Span::new(11_500, 12_100)),
+        ]
+    );
+}
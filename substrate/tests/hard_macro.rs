@@ -5,8 +5,9 @@ use common::sp_cell::{
     SpCell, SpCellArray, SpCellArrayCenter, SpCellArrayCornerTop, SpCellArrayLeft,
     SpCellArrayParams, SpCellArrayTop,
 };
-use common::{hm_toml_path, out_path, setup_ctx};
-use substrate::hard_macro::Config;
+use common::{hm_toml_path, out_path, setup_ctx, DATA_DIR};
+use substrate::hard_macro::{infer_directions, Config};
+use substrate::schematic::circuit::Direction;
 
 pub struct ManualSchematicImport;
 
@@ -33,6 +34,58 @@ impl Component for ManualSchematicImport {
     }
 }
 
+/// Imports `example_01` with an explicit port order.
+pub struct OrderedSchematicImport;
+
+impl Component for OrderedSchematicImport {
+    type Params = NoParams;
+    fn new(
+        _params: &Self::Params,
+        _ctx: &substrate::data::SubstrateCtx,
+    ) -> substrate::error::Result<Self> {
+        Ok(Self)
+    }
+
+    fn name(&self) -> arcstr::ArcStr {
+        arcstr::literal!("ordered_schematic_import")
+    }
+
+    fn schematic(
+        &self,
+        ctx: &mut substrate::schematic::context::SchematicCtx,
+    ) -> substrate::error::Result<()> {
+        let cfg = Config::from_toml_file(hm_toml_path("example_01_ordered"))?;
+        ctx.import_hard_macro_config(cfg)?;
+        Ok(())
+    }
+}
+
+/// Imports `example_02`, inferring port directions from the SPICE subcircuit.
+pub struct InferredSchematicImport;
+
+impl Component for InferredSchematicImport {
+    type Params = NoParams;
+    fn new(
+        _params: &Self::Params,
+        _ctx: &substrate::data::SubstrateCtx,
+    ) -> substrate::error::Result<Self> {
+        Ok(Self)
+    }
+
+    fn name(&self) -> arcstr::ArcStr {
+        arcstr::literal!("inferred_schematic_import")
+    }
+
+    fn schematic(
+        &self,
+        ctx: &mut substrate::schematic::context::SchematicCtx,
+    ) -> substrate::error::Result<()> {
+        let cfg = Config::from_toml_file(hm_toml_path("example_02"))?;
+        ctx.import_hard_macro_config(cfg)?;
+        Ok(())
+    }
+}
+
 #[test]
 fn test_sp_cell() {
     let ctx = setup_ctx();
@@ -98,3 +151,78 @@ fn test_manual_schematic_import_from_toml() {
     )
     .expect("failed to write schematic");
 }
+
+#[test]
+fn test_hard_macro_port_order() {
+    let ctx = setup_ctx();
+    let mut out = Vec::new();
+    ctx.write_schematic::<OrderedSchematicImport, _>(&NoParams, &mut out)
+        .expect("failed to write schematic");
+    let netlist = String::from_utf8(out).unwrap();
+    println!("{netlist}");
+
+    assert!(netlist.contains(
+        ".subckt example_01_wrapper\n+ control\n+ output\n+ input[0]\n+ input[1]\n+ input[2]\n"
+    ));
+    // The instance connects ports in the order declared by the SPICE subcircuit.
+    assert!(
+        netlist.contains("+ input[1]\n+ input[0]\n+ output\n+ input[2]\n+ control\n+ example_01\n")
+    );
+}
+
+#[test]
+fn test_hard_macro_port_order_validation() {
+    let cfg = r#"
+port_order = ["a", "c"]
+
+[ports]
+a = { }
+b = { }
+"#;
+    assert!(Config::from_toml(cfg).is_err());
+
+    let cfg = r#"
+port_order = ["a", "a"]
+
+[ports]
+a = { }
+"#;
+    assert!(Config::from_toml(cfg).is_err());
+
+    let cfg = r#"
+port_order = ["b", "a"]
+
+[ports]
+a = { }
+b = { }
+"#;
+    assert_eq!(
+        Config::from_toml(cfg).unwrap().port_order,
+        vec![arcstr::literal!("b"), arcstr::literal!("a")]
+    );
+}
+
+#[test]
+fn test_hard_macro_direction_inference() {
+    let ctx = setup_ctx();
+    let inst = ctx
+        .instantiate_schematic::<InferredSchematicImport>(&NoParams)
+        .unwrap();
+
+    for (port, direction) in [
+        ("clk", Direction::Input),
+        ("d", Direction::Input),
+        ("q", Direction::Output),
+        ("en", Direction::Input),
+        ("vdd", Direction::InOut),
+        ("vss", Direction::InOut),
+    ] {
+        assert_eq!(inst.port(port).unwrap().direction(), direction, "{port}");
+    }
+
+    // Subcircuits without `.PININFO` comments have no inferred directions.
+    let path = std::path::PathBuf::from(DATA_DIR).join("schematics/example_01.spice");
+    assert!(infer_directions(path, "example_01", Default::default())
+        .unwrap()
+        .is_empty());
+}
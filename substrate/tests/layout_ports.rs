@@ -1,9 +1,15 @@
+use std::collections::HashMap;
+
 use arcstr::ArcStr;
-use subgeom::{Point, Rect};
+use subgeom::{Dims, Point, Rect};
 use substrate::component::{Component, NoParams};
 use substrate::data::SubstrateCtx;
-use substrate::layout::cell::PortId;
+use substrate::layout::cell::{
+    compose, except, only, prefix_with_instance_name, rename_map, Instance, Port,
+    PortConflictStrategy, PortId,
+};
 use substrate::layout::context::LayoutCtx;
+use substrate::layout::group::Group;
 use substrate::layout::layers::selector::Selector;
 
 mod common;
@@ -87,3 +93,145 @@ fn test_layout_bus_port_invalid() {
     )
     .expect_err("expected failed layout generation");
 }
+
+#[test]
+fn test_expose_ports_prefix_with_instance_name() {
+    let ctx = setup_ctx();
+    let m2 = ctx.layers().get(Selector::Metal(2)).unwrap();
+    let mut a = ctx
+        .instantiate_layout::<FivePort>(&NoParams)
+        .expect("failed to instantiate layout");
+    let mut b = a.clone();
+    a.set_name("a");
+    b.set_name("b");
+    b.set_loc(Point::new(0, 400));
+
+    let mut group = Group::new();
+    group.add_instance(a);
+    group.add_instance(b);
+
+    // Identical instances conflict without prefixing.
+    group
+        .clone()
+        .expose_ports(
+            |port: CellPort, _: Instance| Some(port),
+            PortConflictStrategy::Error,
+        )
+        .expect_err("expected conflicting ports");
+
+    group
+        .expose_ports(prefix_with_instance_name(), PortConflictStrategy::Error)
+        .expect("failed to expose ports");
+    assert_eq!(group.ports().count(), 10);
+    assert_eq!(
+        group
+            .port(PortId::new("b_data", 3))
+            .unwrap()
+            .largest_rect(m2)
+            .unwrap(),
+        Rect::new(Point::new(600, 400), Point::new(800, 600))
+    );
+    assert!(group.port(PortId::new("a_data", 0)).is_ok());
+    assert!(group.port(PortId::new("data", 0)).is_err());
+}
+
+#[test]
+fn test_expose_ports_rename_map() {
+    let ctx = setup_ctx();
+    let inst = ctx
+        .instantiate_layout::<FivePort>(&NoParams)
+        .expect("failed to instantiate layout");
+    let mut group = Group::new();
+    group.add_instance(inst);
+    group
+        .expose_ports(
+            rename_map(HashMap::from([(
+                arcstr::literal!("data"),
+                arcstr::literal!("bus"),
+            )])),
+            PortConflictStrategy::Error,
+        )
+        .expect("failed to expose ports");
+    assert_eq!(group.ports().count(), 5);
+    assert!(group.port(PortId::new("bus", 4)).is_ok());
+    assert!(group.port(PortId::new("data", 4)).is_err());
+}
+
+#[test]
+fn test_expose_ports_only() {
+    let ctx = setup_ctx();
+    let inst = ctx
+        .instantiate_layout::<FivePort>(&NoParams)
+        .expect("failed to instantiate layout");
+    let mut a = inst.clone();
+    a.set_name("a");
+    let mut b = inst;
+    b.set_name("b");
+    b.set_loc(Point::new(0, 400));
+
+    let mut group = Group::new();
+    group.add_instance(a);
+    group.add_instance(b);
+
+    let mut none = group.clone();
+    none.expose_ports(except(&["data"]), PortConflictStrategy::Error)
+        .expect("failed to expose ports");
+    assert_eq!(none.ports().count(), 0);
+
+    let mut missing = group.clone();
+    missing
+        .expose_ports(only(&["clk"]), PortConflictStrategy::Error)
+        .expect("failed to expose ports");
+    assert_eq!(missing.ports().count(), 0);
+
+    group
+        .expose_ports(
+            compose(only(&["clk", "data"]), prefix_with_instance_name()),
+            PortConflictStrategy::Error,
+        )
+        .expect("failed to expose ports");
+    assert_eq!(group.ports().count(), 10);
+    assert!(group.port(PortId::new("a_data", 4)).is_ok());
+    assert!(group.port(PortId::new("b_data", 0)).is_ok());
+}
+
+#[test]
+fn test_group_add_array() {
+    let ctx = setup_ctx();
+    let m2 = ctx.layers().get(Selector::Metal(2)).unwrap();
+    let inst = ctx
+        .instantiate_layout::<FivePort>(&NoParams)
+        .expect("failed to instantiate layout");
+
+    let mut group = Group::new();
+    group
+        .add_array(inst, Dims::new(1200, 300), 3, 2)
+        .expect("failed to add array");
+
+    let names = group
+        .instances()
+        .map(|inst| inst.name().to_string())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        names,
+        [
+            "five_port_0_0",
+            "five_port_1_0",
+            "five_port_2_0",
+            "five_port_0_1",
+            "five_port_1_1",
+            "five_port_2_1"
+        ]
+    );
+    assert_eq!(group.ports().count(), 30);
+    assert_eq!(group.bus_ports().count(), 6);
+    assert_eq!(
+        group
+            .port(PortId::new("data_2_1", 4))
+            .unwrap()
+            .largest_rect(m2)
+            .unwrap(),
+        Rect::new(Point::new(3200, 300), Point::new(3400, 500))
+    );
+    assert!(group.port(PortId::new("data", 0)).is_err());
+}
@@ -1,7 +1,8 @@
 use common::{out_path, setup_ctx};
 use substrate::layout::elements::mos::LayoutMos;
+use substrate::pdk::mos::error::MosError;
 use substrate::pdk::mos::spec::MosId;
-use substrate::pdk::mos::{LayoutMosParams, MosParams};
+use substrate::pdk::mos::{GateContactStrategy, LayoutMosParams, MosParams};
 
 mod common;
 
@@ -34,3 +35,102 @@ fn test_sky130_mos_nand2() {
     )
     .expect("failed to write layout");
 }
+
+#[test]
+fn test_mos_params_round_trip() {
+    let params = MosParams {
+        w: 4200,
+        l: 150,
+        m: 2,
+        nf: 4,
+        id: MosId::new(3),
+    };
+    let parsed: MosParams = params
+        .to_string()
+        .parse()
+        .expect("failed to parse MOS params");
+    assert_eq!(params, parsed);
+
+    assert!("w4200_l150_m2_nf4".parse::<MosParams>().is_err());
+    assert!("w4200_l150_m2_nf4_id3_x1".parse::<MosParams>().is_err());
+    assert!("l150_w4200_m2_nf4_id3".parse::<MosParams>().is_err());
+}
+
+#[test]
+fn test_mos_validate_skip_sd_metal() {
+    let device = MosParams {
+        w: 2000,
+        l: 150,
+        m: 1,
+        nf: 2,
+        id: MosId::new(0),
+    };
+    let mut params = LayoutMosParams {
+        skip_sd_metal: vec![vec![2], vec![]],
+        deep_nwell: false,
+        contact_strategy: GateContactStrategy::SingleSide,
+        devices: vec![device.clone(), device],
+    };
+    params.validate().expect("skip indices should be valid");
+
+    params.skip_sd_metal = vec![vec![], vec![0, 3]];
+    assert!(matches!(
+        params.validate(),
+        Err(MosError::InvalidSkipIndex {
+            device: 1,
+            index: 3,
+            nf: 2
+        })
+    ));
+
+    params.skip_sd_metal = vec![vec![]];
+    assert!(matches!(
+        params.validate(),
+        Err(MosError::MismatchedSkipLength {
+            expected: 2,
+            found: 1
+        })
+    ));
+}
+
+#[test]
+fn test_mos_validate_all() {
+    let params = LayoutMosParams {
+        skip_sd_metal: vec![vec![], vec![5]],
+        deep_nwell: false,
+        contact_strategy: GateContactStrategy::SingleSide,
+        devices: vec![
+            MosParams {
+                w: 2000,
+                l: 150,
+                m: 1,
+                nf: 2,
+                id: MosId::new(0),
+            },
+            MosParams {
+                w: 2000,
+                l: 300,
+                m: 1,
+                nf: 2,
+                id: MosId::new(1),
+            },
+        ],
+    };
+
+    let errors = params.validate_all().expect_err("params should be invalid");
+    assert_eq!(errors.len(), 2);
+    assert!(matches!(errors[0], MosError::MismatchedLengths));
+    assert!(matches!(
+        errors[1],
+        MosError::InvalidSkipIndex {
+            device: 1,
+            index: 5,
+            nf: 2
+        }
+    ));
+
+    assert!(matches!(
+        params.validate(),
+        Err(MosError::MismatchedLengths)
+    ));
+}
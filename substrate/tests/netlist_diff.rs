@@ -0,0 +1,111 @@
+use arcstr::ArcStr;
+use common::setup_ctx;
+use substrate::component::{Component, NoParams};
+use substrate::schematic::circuit::Direction;
+use substrate::schematic::netlist::diff::InstanceChange;
+
+mod common;
+
+pub struct Buffer;
+
+impl Component for Buffer {
+    type Params = NoParams;
+    fn new(
+        _params: &Self::Params,
+        _ctx: &substrate::data::SubstrateCtx,
+    ) -> substrate::error::Result<Self> {
+        Ok(Self)
+    }
+
+    fn name(&self) -> ArcStr {
+        arcstr::literal!("buffer")
+    }
+
+    fn schematic(
+        &self,
+        ctx: &mut substrate::schematic::context::SchematicCtx,
+    ) -> substrate::error::Result<()> {
+        let _input = ctx.port("input", Direction::Input);
+        let _output = ctx.port("output", Direction::Output);
+
+        ctx.set_spice("* An opaque implementation of a buffer");
+        Ok(())
+    }
+}
+
+/// Two buffers in series.
+///
+/// If the parameter is `true`, the second buffer is incorrectly
+/// driven by the input rather than by the first buffer.
+pub struct Chain(bool);
+
+impl Component for Chain {
+    type Params = bool;
+
+    fn new(
+        params: &Self::Params,
+        _ctx: &substrate::data::SubstrateCtx,
+    ) -> substrate::error::Result<Self> {
+        Ok(Self(*params))
+    }
+
+    fn name(&self) -> ArcStr {
+        arcstr::literal!("chain")
+    }
+
+    fn schematic(
+        &self,
+        ctx: &mut substrate::schematic::context::SchematicCtx,
+    ) -> substrate::error::Result<()> {
+        let out = ctx.port("out", Direction::Output);
+        let input = ctx.port("input", Direction::Input);
+
+        let mid = ctx.signal("mid");
+
+        let mut buf = ctx.instantiate::<Buffer>(&NoParams)?;
+        buf.set_name("buf0");
+        buf.connect("input", input);
+        buf.connect("output", mid);
+        ctx.add_instance(buf);
+
+        let mut buf = ctx.instantiate::<Buffer>(&NoParams)?;
+        buf.set_name("buf1");
+        buf.connect("input", if self.0 { input } else { mid });
+        buf.connect("output", out);
+        ctx.add_instance(buf);
+        Ok(())
+    }
+}
+
+#[test]
+fn test_netlist_diff_unchanged() {
+    let ctx = setup_ctx();
+    let diff = ctx.diff_schematics::<Chain, Chain>(&false, &false).unwrap();
+    assert!(diff.is_empty());
+}
+
+#[test]
+fn test_netlist_diff_changed_connection() {
+    let ctx = setup_ctx();
+    let diff = ctx.diff_schematics::<Chain, Chain>(&false, &true).unwrap();
+    println!("{diff}");
+
+    assert!(diff.added_modules.is_empty());
+    assert!(diff.removed_modules.is_empty());
+    assert_eq!(diff.modules.len(), 1);
+
+    let module = &diff.modules[0];
+    assert_eq!(module.changes.len(), 1);
+    assert_eq!(
+        module.changes[0],
+        InstanceChange::ConnectionChanged {
+            instance: arcstr::literal!("buf1"),
+            port: arcstr::literal!("input"),
+            old: Some("mid".to_string()),
+            new: Some("input".to_string()),
+        }
+    );
+    assert!(diff
+        .to_string()
+        .contains("~ instance buf1, port input: mid -> input"));
+}
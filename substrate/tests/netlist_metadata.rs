@@ -0,0 +1,98 @@
+use arcstr::ArcStr;
+use common::{setup_ctx, setup_ctx_with_netlister};
+use subspice::parser::SpiceLine;
+use substrate::component::Component;
+use substrate::schematic::circuit::Direction;
+use substrate::schematic::netlist::impls::spice::SpiceNetlister;
+
+mod common;
+
+/// A delay line with an opaque implementation, whose length is given by the parameter.
+pub struct DelayLine(usize);
+
+impl Component for DelayLine {
+    type Params = usize;
+
+    fn new(
+        params: &Self::Params,
+        _ctx: &substrate::data::SubstrateCtx,
+    ) -> substrate::error::Result<Self> {
+        Ok(Self(*params))
+    }
+
+    fn name(&self) -> ArcStr {
+        arcstr::format!("delay_line_{}", self.0)
+    }
+
+    fn schematic(
+        &self,
+        ctx: &mut substrate::schematic::context::SchematicCtx,
+    ) -> substrate::error::Result<()> {
+        let _input = ctx.port("input", Direction::Input);
+        let _output = ctx.port("output", Direction::Output);
+
+        ctx.set_spice("* An opaque implementation of a delay line");
+        Ok(())
+    }
+}
+
+/// Returns the text of all comments in the given netlist.
+fn comments(netlist: &str) -> Vec<String> {
+    subspice::parse(&netlist)
+        .expect("failed to parse netlist")
+        .lines()
+        .filter_map(|line| match line {
+            SpiceLine::Comment(comment) => Some(comment.to_string()),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn test_netlist_metadata() {
+    let ctx = setup_ctx_with_netlister(SpiceNetlister::new().with_metadata());
+
+    let mut netlists = Vec::new();
+    for params in [4, 8] {
+        let mut out = Vec::new();
+        ctx.write_schematic::<DelayLine, _>(&params, &mut out)
+            .expect("failed to write schematic");
+        let netlist = String::from_utf8(out).unwrap();
+        println!("{netlist}");
+        netlists.push(netlist);
+    }
+
+    let all_comments = netlists.iter().map(|n| comments(n)).collect::<Vec<_>>();
+    for comments in all_comments.iter() {
+        assert!(comments.contains(&format!(
+            "Component: {}",
+            std::any::type_name::<DelayLine>()
+        )));
+        assert!(comments.contains(&format!("Substrate version: {}", env!("CARGO_PKG_VERSION"))));
+    }
+
+    let hashes = all_comments
+        .iter()
+        .map(|comments| {
+            comments
+                .iter()
+                .find(|comment| comment.starts_with("Parameter hash: "))
+                .expect("parameter hash comment not found")
+                .clone()
+        })
+        .collect::<Vec<_>>();
+    assert_ne!(hashes[0], hashes[1]);
+}
+
+#[test]
+fn test_netlist_metadata_disabled() {
+    let ctx = setup_ctx();
+    let mut out = Vec::new();
+    ctx.write_schematic::<DelayLine, _>(&4, &mut out)
+        .expect("failed to write schematic");
+    let netlist = String::from_utf8(out).unwrap();
+
+    assert!(comments(&netlist)
+        .iter()
+        .all(|comment| !comment.starts_with("Component: ")));
+}
@@ -0,0 +1,127 @@
+use std::collections::HashSet;
+
+use arcstr::ArcStr;
+use common::setup_ctx;
+use common::vdivider::array::VDividerArrayWrapper;
+use common::vdivider::VDivider;
+use substrate::component::{Component, NoParams};
+use substrate::data::SubstrateCtx;
+use substrate::schematic::circuit::Direction;
+use substrate::schematic::context::SchematicCtx;
+
+mod common;
+
+fn netlist_of<T: Component<Params = NoParams>>() -> String {
+    let ctx = setup_ctx();
+    let mut out = Vec::new();
+    ctx.write_schematic::<T, _>(&NoParams, &mut out)
+        .expect("failed to write schematic");
+    String::from_utf8(out).unwrap()
+}
+
+fn netlist() -> String {
+    netlist_of::<VDividerArrayWrapper>()
+}
+
+/// A wrapper around a [`VDivider`] that has the same name as the [`VDivider`].
+pub struct VDividerNameClash;
+
+impl Component for VDividerNameClash {
+    type Params = NoParams;
+
+    fn new(_params: &Self::Params, _ctx: &SubstrateCtx) -> substrate::error::Result<Self> {
+        Ok(Self)
+    }
+
+    fn name(&self) -> ArcStr {
+        arcstr::literal!("vdivider")
+    }
+
+    fn schematic(&self, ctx: &mut SchematicCtx) -> substrate::error::Result<()> {
+        let out = ctx.port("out", Direction::Output);
+        let vdd = ctx.port("vdd", Direction::InOut);
+        let vss = ctx.port("vss", Direction::InOut);
+
+        ctx.instantiate::<VDivider>(&NoParams)?
+            .with_connections([("out", &out), ("vdd", &vdd), ("vss", &vss)])
+            .named("inner")
+            .add_to(ctx);
+        Ok(())
+    }
+}
+
+#[test]
+fn test_netlist_order_is_deterministic() {
+    let first = netlist();
+    for _ in 0..4 {
+        assert_eq!(netlist(), first);
+    }
+}
+
+/// Joins SPICE continuation lines (lines beginning with `+`) onto the preceding line.
+fn logical_lines(netlist: &str) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    for line in netlist.lines().map(str::trim) {
+        match (line.strip_prefix('+'), lines.last_mut()) {
+            (Some(rest), Some(prev)) => {
+                prev.push(' ');
+                prev.push_str(rest.trim());
+            }
+            _ => lines.push(line.to_string()),
+        }
+    }
+    lines
+}
+
+/// Returns the name of the subcircuit defined by `line`, if any.
+fn subckt_name(line: &str) -> Option<String> {
+    line.strip_prefix(".subckt ")
+        .and_then(|rest| rest.split_whitespace().next())
+        .map(str::to_string)
+}
+
+#[test]
+fn test_netlist_order_defines_before_use() {
+    let lines = logical_lines(&netlist());
+
+    let all_defined = lines
+        .iter()
+        .filter_map(|line| subckt_name(line))
+        .collect::<HashSet<_>>();
+    assert!(all_defined.contains("vdivider_array_wrapper"));
+    assert!(all_defined.contains("vdivider_array"));
+
+    let mut defined = HashSet::new();
+    let mut last = None;
+    for line in lines.iter() {
+        if let Some(name) = subckt_name(line) {
+            defined.insert(name.clone());
+            last = Some(name);
+        } else if line.starts_with('X') {
+            // The referenced subcircuit is the last token of an instance line.
+            let module = line.split_whitespace().last().unwrap();
+            if all_defined.contains(module) {
+                assert!(
+                    defined.contains(module),
+                    "subcircuit `{module}` instantiated before it is defined"
+                );
+            }
+        }
+    }
+
+    assert_eq!(last.as_deref(), Some("vdivider_array_wrapper"));
+}
+
+#[test]
+fn test_netlist_order_top_keeps_name() {
+    let lines = logical_lines(&netlist_of::<VDividerNameClash>());
+    let subckts = lines
+        .iter()
+        .filter_map(|line| subckt_name(line))
+        .collect::<Vec<_>>();
+    assert_eq!(subckts.last().map(String::as_str), Some("vdivider"));
+    assert!(subckts.iter().any(|name| name == "vdivider_1"));
+    assert!(lines
+        .iter()
+        .any(|line| line.starts_with("Xinner") && line.ends_with(" vdivider_1")));
+}
@@ -0,0 +1,102 @@
+use arcstr::ArcStr;
+use common::setup_ctx_with_netlister;
+use substrate::component::{Component, NoParams};
+use substrate::schematic::circuit::Direction;
+use substrate::schematic::netlist::impls::spice::SpiceNetlister;
+
+mod common;
+
+const MAX_LINE_WIDTH: usize = 40;
+
+/// A 64-bit register with an opaque implementation.
+pub struct Reg64;
+
+impl Component for Reg64 {
+    type Params = NoParams;
+    fn new(
+        _params: &Self::Params,
+        _ctx: &substrate::data::SubstrateCtx,
+    ) -> substrate::error::Result<Self> {
+        Ok(Self)
+    }
+
+    fn name(&self) -> ArcStr {
+        arcstr::literal!("reg64")
+    }
+
+    fn schematic(
+        &self,
+        ctx: &mut substrate::schematic::context::SchematicCtx,
+    ) -> substrate::error::Result<()> {
+        let _clk = ctx.port("clk", Direction::Input);
+        let _d = ctx.bus_port("d", 64, Direction::Input);
+        let _q = ctx.bus_port("q", 64, Direction::Output);
+
+        ctx.set_spice("* An opaque implementation of a 64-bit register");
+        Ok(())
+    }
+}
+
+/// A wrapper around a single [`Reg64`] instance.
+pub struct Reg64Wrapper;
+
+impl Component for Reg64Wrapper {
+    type Params = NoParams;
+    fn new(
+        _params: &Self::Params,
+        _ctx: &substrate::data::SubstrateCtx,
+    ) -> substrate::error::Result<Self> {
+        Ok(Self)
+    }
+
+    fn name(&self) -> ArcStr {
+        arcstr::literal!("reg64_wrapper")
+    }
+
+    fn schematic(
+        &self,
+        ctx: &mut substrate::schematic::context::SchematicCtx,
+    ) -> substrate::error::Result<()> {
+        let clk = ctx.port("clk", Direction::Input);
+        let d = ctx.bus_port("d", 64, Direction::Input);
+        let q = ctx.bus_port("q", 64, Direction::Output);
+
+        ctx.instantiate::<Reg64>(&NoParams)?
+            .named("reg0")
+            .with_connections([("clk", clk), ("d", d), ("q", q)])
+            .add_to(ctx);
+        Ok(())
+    }
+}
+
+#[test]
+fn test_netlist_line_wrapping() {
+    let ctx = setup_ctx_with_netlister(SpiceNetlister::new().with_max_line_width(MAX_LINE_WIDTH));
+    let mut out = Vec::new();
+    ctx.write_schematic::<Reg64Wrapper, _>(&NoParams, &mut out)
+        .expect("failed to write schematic");
+    let netlist = String::from_utf8(out).unwrap();
+    println!("{netlist}");
+
+    for line in netlist.lines() {
+        assert!(
+            line.len() <= MAX_LINE_WIDTH,
+            "line exceeds maximum width of {MAX_LINE_WIDTH}: {line:?}"
+        );
+    }
+    assert!(netlist.lines().any(|line| line.starts_with("+ ")));
+    assert!(netlist.contains("Xreg0 clk d[0] d[1]"));
+
+    let parsed = subspice::parse(&netlist).expect("failed to parse wrapped netlist");
+    let ports = ["clk".to_string()]
+        .into_iter()
+        .chain((0..64).map(|i| format!("d[{i}]")))
+        .chain((0..64).map(|i| format!("q[{i}]")))
+        .collect::<Vec<_>>();
+    for name in ["reg64", "reg64_wrapper"] {
+        let subckt = parsed
+            .subcircuit_named(name)
+            .unwrap_or_else(|| panic!("subcircuit {name} not found"));
+        assert_eq!(subckt.ports, ports);
+    }
+}
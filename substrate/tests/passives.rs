@@ -0,0 +1,92 @@
+use arcstr::ArcStr;
+use common::setup_ctx;
+use substrate::component::Component;
+use substrate::error::ErrorSource;
+use substrate::schematic::circuit::Direction;
+use substrate::schematic::elements::capacitor::Capacitor;
+use substrate::schematic::elements::resistor::Resistor;
+use substrate::units::{SiPrefix, SiValue};
+
+mod common;
+
+/// A resistor and capacitor in parallel, with values given by the parameters.
+pub struct Rc(SiValue, SiValue);
+
+impl Component for Rc {
+    type Params = (SiValue, SiValue);
+
+    fn new(
+        params: &Self::Params,
+        _ctx: &substrate::data::SubstrateCtx,
+    ) -> substrate::error::Result<Self> {
+        Ok(Self(params.0, params.1))
+    }
+
+    fn name(&self) -> ArcStr {
+        arcstr::literal!("rc")
+    }
+
+    fn schematic(
+        &self,
+        ctx: &mut substrate::schematic::context::SchematicCtx,
+    ) -> substrate::error::Result<()> {
+        let a = ctx.port("a", Direction::InOut);
+        let b = ctx.port("b", Direction::InOut);
+
+        ctx.instantiate::<Resistor>(&self.0)?
+            .with_connections([("p", &a), ("n", &b)])
+            .named("R0")
+            .add_to(ctx);
+        ctx.instantiate::<Capacitor>(&self.1)?
+            .with_connections([("p", &a), ("n", &b)])
+            .named("C0")
+            .add_to(ctx);
+        Ok(())
+    }
+}
+
+fn netlist(params: (SiValue, SiValue)) -> substrate::error::Result<String> {
+    let ctx = setup_ctx();
+    let mut out = Vec::new();
+    ctx.write_schematic::<Rc, _>(&params, &mut out)?;
+    Ok(String::from_utf8(out).unwrap())
+}
+
+#[test]
+fn test_zero_valued_passives() {
+    let netlist = netlist((SiValue::zero(), SiValue::zero())).unwrap();
+    println!("{netlist}");
+
+    assert!(netlist.contains(".subckt resistor_short\n+ p\n+ n\n\nV1 p n 0\n"));
+    assert!(netlist.contains(".subckt capacitor_0\n+ p\n+ n\n\nC1 p n 0\n"));
+    assert!(!netlist.contains("R1"));
+}
+
+#[test]
+fn test_nonzero_passives() {
+    let netlist = netlist((
+        SiValue::new(2, SiPrefix::Kilo),
+        SiValue::new(5, SiPrefix::Femto),
+    ))
+    .unwrap();
+
+    assert!(netlist.contains("R1 p n 2K"));
+    assert!(netlist.contains("C1 p n 5f"));
+}
+
+#[test]
+fn test_negative_passives() {
+    let err = netlist((SiValue::new(-1, SiPrefix::Kilo), SiValue::zero())).unwrap_err();
+    assert!(matches!(err.source(), ErrorSource::InvalidArgs(_)));
+
+    let err = netlist((SiValue::zero(), SiValue::new(-1, SiPrefix::Femto))).unwrap_err();
+    assert!(matches!(err.source(), ErrorSource::InvalidArgs(_)));
+}
+
+#[test]
+fn test_si_value_sign() {
+    assert!(SiValue::new(0, SiPrefix::Kilo).is_zero());
+    assert!(!SiValue::new(0, SiPrefix::Kilo).is_negative());
+    assert!(SiValue::new(-3, SiPrefix::Milli).is_negative());
+    assert!(!SiValue::new(3, SiPrefix::Milli).is_zero());
+}
@@ -0,0 +1,10 @@
+use common::setup_ctx;
+
+mod common;
+
+#[test]
+fn test_pdk_name_and_process() {
+    let ctx = setup_ctx();
+    assert_eq!(ctx.pdk_name(), "sky130-open");
+    assert_eq!(ctx.pdk_process(), "sky130");
+}
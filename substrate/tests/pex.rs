@@ -0,0 +1,180 @@
+use std::path::PathBuf;
+
+use arcstr::ArcStr;
+use subgeom::{Point, Rect};
+use substrate::component::{Component, NoParams};
+use substrate::data::SubstrateCtx;
+use substrate::error::ErrorSource;
+use substrate::layout::context::LayoutCtx;
+use substrate::layout::layers::selector::Selector;
+use substrate::schematic::circuit::Direction;
+use substrate::schematic::context::SchematicCtx;
+use substrate::schematic::elements::resistor::Resistor;
+use substrate::units::{SiPrefix, SiValue};
+use substrate::verification::pex::{PexInput, PexNetlist};
+
+mod common;
+use common::{out_path, setup_ctx};
+
+const PEX_NETLIST: &str = r#"* Extracted netlist
+.subckt INV_PEX VSS Y A
++ VDD
+MM0 Y A VSS VSS nmos
+MM1 Y A VDD VDD pmos
+C0 Y VSS 1e-15
+.ends
+"#;
+
+const SOURCE_NETLIST: &str = r#"
+.subckt inv vdd vss a y
+M0 y a vss vss nmos
+M1 y a vdd vdd pmos
+.ends
+"#;
+
+fn write_file(test_name: &str, file_name: &str, contents: &str) -> PathBuf {
+    let path = out_path(test_name, file_name);
+    std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+    std::fs::write(&path, contents).unwrap();
+    path
+}
+
+#[test]
+fn test_pex_netlist_same_name() {
+    let netlist = ".subckt inv a y vdd vss\nC0 y vss 1e-15\n.ends\n";
+    let path = write_file("test_pex_netlist_same_name", "inv.pex.spice", netlist);
+    let work_dir = out_path("test_pex_netlist_same_name", "sim");
+
+    let pex = PexNetlist::load(&path, "INV").unwrap();
+    assert_eq!(pex.pex_cell_name, "inv");
+    assert!(!pex.needs_wrapper());
+
+    let input = pex.sim_input(&work_dir).unwrap();
+    assert_eq!(input.includes, vec![path]);
+}
+
+#[test]
+fn test_pex_netlist_renamed() {
+    let test_name = "test_pex_netlist_renamed";
+    let pex_path = write_file(test_name, "inv.pex.spice", PEX_NETLIST);
+    let source_path = write_file(test_name, "netlist.spice", SOURCE_NETLIST);
+    let work_dir = out_path(test_name, "sim");
+
+    let pex = PexNetlist::from_input(&PexInput {
+        work_dir: work_dir.clone(),
+        layout_path: out_path(test_name, "layout.gds"),
+        layout_cell_name: "inv".into(),
+        layout_format: Default::default(),
+        source_paths: vec![source_path],
+        source_cell_name: "inv".into(),
+        pex_netlist_path: pex_path.clone(),
+        opts: Default::default(),
+        ground_net: "vss".to_string(),
+        cleanup: Default::default(),
+    })
+    .unwrap();
+
+    assert_eq!(pex.pex_cell_name, "INV_PEX");
+    assert_eq!(pex.pex_ports, vec!["VSS", "Y", "A", "VDD"]);
+    assert_eq!(
+        pex.source_ports,
+        Some(vec!["vdd".into(), "vss".into(), "a".into(), "y".into()])
+    );
+    assert!(pex.needs_wrapper());
+
+    let input = pex.sim_input(&work_dir).unwrap();
+    let wrapper_path = work_dir.join(PexNetlist::WRAPPER_FILE_NAME);
+    assert_eq!(input.includes, vec![pex_path, wrapper_path.clone()]);
+
+    let wrapper = std::fs::read_to_string(wrapper_path).unwrap();
+    assert!(wrapper.contains(".subckt inv vdd vss a y\nxpex vss y a vdd INV_PEX\n.ends"));
+}
+
+#[test]
+fn test_pex_netlist_missing_port() {
+    let path = write_file(
+        "test_pex_netlist_missing_port",
+        "inv.pex.spice",
+        PEX_NETLIST,
+    );
+    let pex = PexNetlist::load(path, "inv")
+        .unwrap()
+        .with_source_ports(["vdd", "vss", "a"]);
+
+    let mut out = Vec::new();
+    assert!(pex.write_wrapper(&mut out).is_err());
+}
+
+#[test]
+fn test_pex_netlist_ambiguous() {
+    let netlist = format!("{PEX_NETLIST}\n{SOURCE_NETLIST}");
+    let path = write_file("test_pex_netlist_ambiguous", "inv.pex.spice", &netlist);
+    assert!(PexNetlist::load(&path, "inv").is_ok());
+    assert!(PexNetlist::load(&path, "buf").is_err());
+}
+
+/// A resistor between two supplies, with its ground net marked.
+///
+/// The ground net is not named `vss` to make sure it is not picked up by default.
+pub struct MarkedResistor;
+
+impl Component for MarkedResistor {
+    type Params = NoParams;
+
+    fn new(_params: &Self::Params, _ctx: &SubstrateCtx) -> substrate::error::Result<Self> {
+        Ok(Self)
+    }
+
+    fn name(&self) -> ArcStr {
+        arcstr::literal!("marked_resistor")
+    }
+
+    fn schematic(&self, ctx: &mut SchematicCtx) -> substrate::error::Result<()> {
+        let vpwr = ctx.port("vpwr", Direction::InOut);
+        let gnd = ctx.port("gnd", Direction::InOut);
+        ctx.mark_supply(vpwr, 1.8);
+        ctx.mark_ground(gnd);
+
+        ctx.instantiate::<Resistor>(&SiValue::new(1, SiPrefix::Kilo))?
+            .with_connections([("p", &vpwr), ("n", &gnd)])
+            .named("R0")
+            .add_to(ctx);
+        Ok(())
+    }
+
+    fn layout(&self, ctx: &mut LayoutCtx) -> substrate::error::Result<()> {
+        let layer = ctx.layers().get(Selector::Routing(1)).unwrap();
+        ctx.draw_rect(layer, Rect::new(Point::new(0, 0), Point::new(250, 500)));
+        Ok(())
+    }
+}
+
+#[test]
+fn test_pex_input_marked_ground() {
+    let ctx = setup_ctx();
+    let work_dir = out_path("test_pex_input_marked_ground", "pex");
+    let pex_path = work_dir.join("marked_resistor.pex.spice");
+
+    let input = ctx
+        .pex_input::<MarkedResistor>(&NoParams, &work_dir, &pex_path)
+        .unwrap();
+    assert_eq!(input.ground_net, "gnd");
+    assert_eq!(input.source_cell_name, "marked_resistor");
+    assert_eq!(input.pex_netlist_path, pex_path);
+
+    let input = ctx
+        .lvs_input::<MarkedResistor>(&NoParams, out_path("test_pex_input_marked_ground", "lvs"))
+        .unwrap();
+    assert_eq!(input.ground_net.as_deref(), Some("gnd"));
+    assert_eq!(input.supply_nets, vec!["vpwr".to_string()]);
+}
+
+#[test]
+fn test_pex_input_no_marked_ground() {
+    let ctx = setup_ctx();
+    let work_dir = out_path("test_pex_input_no_marked_ground", "pex");
+    let err = ctx
+        .pex_input::<common::vdivider::VDivider>(&NoParams, &work_dir, work_dir.join("out.spice"))
+        .unwrap_err();
+    assert!(matches!(err.source(), ErrorSource::GroundNetNotSpecified));
+}
@@ -0,0 +1,72 @@
+use arcstr::ArcStr;
+use common::setup_ctx;
+use substrate::component::Component;
+use substrate::verification::simulation::{Analysis, TranAnalysis};
+
+mod common;
+
+/// A resistor divider with the given number of taps.
+pub struct Divider(usize);
+
+impl Component for Divider {
+    type Params = usize;
+
+    fn new(
+        params: &Self::Params,
+        _ctx: &substrate::data::SubstrateCtx,
+    ) -> substrate::error::Result<Self> {
+        Ok(Self(*params))
+    }
+
+    fn name(&self) -> ArcStr {
+        arcstr::format!("divider_{}", self.0)
+    }
+}
+
+/// A component with the same name and parameters as [`Divider`].
+pub struct OtherDivider(usize);
+
+impl Component for OtherDivider {
+    type Params = usize;
+
+    fn new(
+        params: &Self::Params,
+        _ctx: &substrate::data::SubstrateCtx,
+    ) -> substrate::error::Result<Self> {
+        Ok(Self(*params))
+    }
+
+    fn name(&self) -> ArcStr {
+        arcstr::format!("divider_{}", self.0)
+    }
+}
+
+fn tran(stop: f64) -> Analysis {
+    TranAnalysis::builder()
+        .stop(stop)
+        .step(stop / 100.0)
+        .build()
+        .unwrap()
+        .into()
+}
+
+#[test]
+fn test_sim_work_dir() {
+    let ctx = setup_ctx();
+
+    let dir = ctx.sim_work_dir::<Divider, _>(&4, &tran(1e-9));
+    assert!(dir.starts_with(ctx.scratch_dir()));
+    assert_eq!(dir, ctx.sim_work_dir::<Divider, _>(&4, &tran(1e-9)));
+
+    let others = [
+        ctx.sim_work_dir::<Divider, _>(&8, &tran(1e-9)),
+        ctx.sim_work_dir::<Divider, _>(&4, &tran(2e-9)),
+        ctx.sim_work_dir::<Divider, _>(&4, &"tran"),
+    ];
+    for other in others {
+        assert_ne!(dir, other);
+    }
+
+    // Distinct component types with the same name and parameters do not collide.
+    assert_ne!(dir, ctx.sim_work_dir::<OtherDivider, _>(&4, &tran(1e-9)));
+}
@@ -0,0 +1,41 @@
+use subgeom::{Point, Rect};
+use substrate::layout::validation::spacing::{min_spacing, spacing};
+
+fn rect(x0: i64, y0: i64, x1: i64, y1: i64) -> Rect {
+    Rect::new(Point::new(x0, y0), Point::new(x1, y1))
+}
+
+#[test]
+fn test_spacing() {
+    let a = rect(0, 0, 100, 100);
+    assert_eq!(spacing(a, rect(130, 20, 200, 80)), 30);
+    assert_eq!(spacing(a, rect(20, -70, 80, -40)), 40);
+    // Diagonally offset rectangles use the larger of the two gaps.
+    assert_eq!(spacing(a, rect(110, 150, 200, 200)), 50);
+    // Touching, overlapping, and identical rectangles have zero spacing.
+    assert_eq!(spacing(a, rect(100, 0, 200, 100)), 0);
+    assert_eq!(spacing(a, rect(50, 50, 150, 150)), 0);
+    assert_eq!(spacing(a, a), 0);
+}
+
+#[test]
+fn test_min_spacing() {
+    let rects = [
+        rect(0, 0, 100, 100),
+        rect(300, 0, 400, 100),
+        rect(0, 250, 100, 350),
+        rect(425, 0, 500, 100),
+        rect(140, 250, 200, 350),
+    ];
+    assert_eq!(min_spacing(&rects), Some((25, (1, 3))));
+
+    let mut overlapping = rects.to_vec();
+    overlapping.push(rect(450, 50, 550, 150));
+    assert_eq!(min_spacing(&overlapping), Some((0, (3, 5))));
+
+    let identical = [rect(0, 0, 10, 10), rect(0, 0, 10, 10)];
+    assert_eq!(min_spacing(&identical), Some((0, (0, 1))));
+
+    assert_eq!(min_spacing(&rects[..1]), None);
+    assert_eq!(min_spacing(&[]), None);
+}
@@ -1,8 +1,96 @@
-use common::{out_path, setup_ctx};
-use substrate::pdk::stdcell::StdCell;
+use std::path::PathBuf;
+
+use arcstr::ArcStr;
+use common::{out_path, setup_ctx, DATA_DIR};
+use subgeom::bbox::BoundBox;
+use substrate::component::{Component, NoParams};
+use substrate::error::ErrorSource;
+use substrate::layout::placement::align::AlignRect;
+use substrate::pdk::stdcell::error::StdCellError;
+use substrate::pdk::stdcell::{Function, StdCell, StdCellData, StdCellLibData, StdCellPin};
+use substrate::schematic::circuit::Direction;
 
 mod common;
 
+/// A row of inverters from the default standard cell library,
+/// each driving the next.
+pub struct InvRow;
+
+const INV_ROW_LEN: usize = 4;
+
+impl Component for InvRow {
+    type Params = NoParams;
+    fn new(
+        _params: &Self::Params,
+        _ctx: &substrate::data::SubstrateCtx,
+    ) -> substrate::error::Result<Self> {
+        Ok(Self)
+    }
+
+    fn name(&self) -> ArcStr {
+        arcstr::literal!("inv_row")
+    }
+
+    fn schematic(
+        &self,
+        ctx: &mut substrate::schematic::context::SchematicCtx,
+    ) -> substrate::error::Result<()> {
+        let din = ctx.port("din", Direction::Input);
+        let dout = ctx.port("dout", Direction::Output);
+        let [vdd, vss] = ctx.ports(["vdd", "vss"], Direction::InOut);
+
+        let stdcells = ctx.inner().std_cell_db();
+        let inv = stdcells
+            .lib_named("sky130_fd_sc_hd")
+            .unwrap()
+            .try_cell_named("sky130_fd_sc_hd__inv_2")?;
+
+        let mut input = din;
+        for i in 0..INV_ROW_LEN {
+            let output = if i == INV_ROW_LEN - 1 {
+                dout
+            } else {
+                ctx.signal(format!("x{i}"))
+            };
+            inv.instantiate_schematic(ctx)?
+                .with_connections([
+                    ("A", input),
+                    ("Y", output),
+                    ("VGND", vss),
+                    ("VNB", vss),
+                    ("VPB", vdd),
+                    ("VPWR", vdd),
+                ])
+                .named(format!("inv{i}"))
+                .add_to(ctx);
+            input = output;
+        }
+        Ok(())
+    }
+
+    fn layout(
+        &self,
+        ctx: &mut substrate::layout::context::LayoutCtx,
+    ) -> substrate::error::Result<()> {
+        let stdcells = ctx.inner().std_cell_db();
+        let inv = stdcells
+            .lib_named("sky130_fd_sc_hd")
+            .unwrap()
+            .try_cell_named("sky130_fd_sc_hd__inv_2")?;
+
+        let mut prev = None;
+        for _ in 0..INV_ROW_LEN {
+            let mut inst = inv.instantiate_layout(ctx)?;
+            if let Some(prev) = prev {
+                inst.align_to_the_right_of(prev, 0);
+            }
+            prev = Some(inst.bbox());
+            ctx.draw(inst)?;
+        }
+        Ok(())
+    }
+}
+
 #[test]
 fn test_sky130_standard_cells() {
     let ctx = setup_ctx();
@@ -28,3 +116,124 @@ fn test_sky130_standard_cells() {
         .expect("failed to write layout");
     }
 }
+
+#[test]
+fn test_std_cell_lib_validation() {
+    let ctx = setup_ctx();
+    ctx.std_cell_db()
+        .validate()
+        .expect("sky130 library sources should exist");
+
+    let source = PathBuf::from(DATA_DIR).join("schematics/example_01.spice");
+    let missing = PathBuf::from(DATA_DIR).join("schematics/missing.spice");
+
+    let mut lib = StdCellLibData::new("test_lib");
+    lib.set_schematic_source(&source);
+    lib.add_cell(
+        StdCellData::builder()
+            .name("inv")
+            .function(Function::Inv)
+            .build()
+            .unwrap(),
+    );
+    lib.validate().unwrap();
+
+    lib.add_cell(
+        StdCellData::builder()
+            .name("buf")
+            .schematic_source(&missing)
+            .function(Function::Buf)
+            .build()
+            .unwrap(),
+    );
+    let err = lib.validate().unwrap_err();
+    match err.source() {
+        ErrorSource::StdCell(StdCellError::SourceUnreadable { owner, path, .. }) => {
+            assert_eq!(owner, "cell `buf` in library `test_lib`");
+            assert_eq!(path, &missing);
+        }
+        source => panic!("unexpected error: {source}"),
+    }
+}
+
+#[test]
+fn test_std_cell_lib_from_liberty() {
+    let lib = StdCellLibData::from_liberty(PathBuf::from(DATA_DIR).join("liberty/small.lib"))
+        .expect("failed to load Liberty file");
+    assert_eq!(lib.name(), "small_lib");
+    assert_eq!(lib.cells().count(), 4);
+
+    let inv = lib.try_cell_named("INVX2").unwrap();
+    assert_eq!(inv.function(), &Function::Inv);
+    assert_eq!(inv.strength(), 2);
+    assert_eq!(inv.area(), Some(1.5));
+    assert_eq!(
+        inv.pins(),
+        &[
+            StdCellPin {
+                name: arcstr::literal!("A"),
+                direction: Direction::Input,
+            },
+            StdCellPin {
+                name: arcstr::literal!("Y"),
+                direction: Direction::Output,
+            },
+        ]
+    );
+
+    let buf = lib.try_cell_named("buf_4").unwrap();
+    assert_eq!(buf.function(), &Function::Buf);
+    assert_eq!(buf.strength(), 4);
+
+    let nand = lib.try_cell_named("nand2_1").unwrap();
+    assert_eq!(nand.function(), &Function::Nand2);
+    assert_eq!(nand.strength(), 1);
+    assert_eq!(nand.pins().len(), 3);
+
+    let tap = lib.try_cell_named("tapvpwrvgnd").unwrap();
+    assert!(tap.pins().is_empty());
+    assert_eq!(tap.area(), Some(0.5));
+}
+
+#[test]
+fn test_function_from_expr() {
+    for (expr, function) in [
+        ("A", Function::Buf),
+        ("!A", Function::Inv),
+        ("A'", Function::Inv),
+        ("A&B", Function::And2),
+        ("A B C", Function::And3),
+        ("!(A&B)", Function::Nand2),
+        ("A' + B'", Function::Nand2),
+        ("!(A&B&C)", Function::Nand3),
+        ("(A|B)", Function::Or2),
+        ("A+B+C", Function::Or3),
+        ("!(A|B)", Function::Nor2),
+        ("!A&!B&!C", Function::Nor3),
+        ("A^B", Function::Xor2),
+        ("A^B^C^D", Function::Xor4),
+        ("!(A^B)", Function::Xnor2),
+        ("!(A^B^C)", Function::Xnor3),
+        ("(A0&!S)|(A1&S)", Function::Mux2),
+        ("S ? A1 : A0", Function::Other("S ? A1 : A0".to_string())),
+        ("(A&B)|C", Function::Other("(A&B)|C".to_string())),
+        ("!(A&B", Function::Other("!(A&B".to_string())),
+    ] {
+        assert_eq!(Function::from_expr(expr), function, "expression: {expr:?}");
+    }
+}
+
+#[test]
+fn test_std_cell_ref_instantiate() {
+    let ctx = setup_ctx();
+    ctx.write_schematic_to_file::<InvRow>(
+        &NoParams,
+        out_path("test_std_cell_ref_instantiate", "schematic.spice"),
+    )
+    .expect("failed to write schematic");
+    ctx.write_layout::<InvRow>(
+        &NoParams,
+        out_path("test_std_cell_ref_instantiate", "layout.gds"),
+    )
+    .expect("failed to write layout");
+}
@@ -40,6 +40,36 @@ impl Component for SimpleRectangle {
     }
 }
 
+pub struct OutlinedRectangle;
+
+impl Component for OutlinedRectangle {
+    type Params = NoParams;
+
+    fn new(
+        _params: &Self::Params,
+        _ctx: &substrate::data::SubstrateCtx,
+    ) -> substrate::error::Result<Self> {
+        Ok(Self)
+    }
+
+    fn name(&self) -> arcstr::ArcStr {
+        arcstr::literal!("outlined_rectangle")
+    }
+
+    fn layout(
+        &self,
+        ctx: &mut substrate::layout::context::LayoutCtx,
+    ) -> substrate::error::Result<()> {
+        let l = ctx.layers();
+        let m1 = l.get(Selector::Metal(1))?;
+        let outline = l.get(Selector::Name("outline"))?;
+        ctx.draw_rect(m1, Rect::new(Point::new(100, 100), Point::new(200, 400)));
+        ctx.draw_rect(outline, Rect::new(Point::new(0, 0), Point::new(300, 500)));
+
+        Ok(())
+    }
+}
+
 pub struct GroupTransformations;
 
 impl Component for GroupTransformations {
@@ -126,3 +156,68 @@ fn test_group_transformations() {
         );
     }
 }
+
+#[test]
+fn test_group_from_cell() {
+    let ctx = setup_ctx();
+    let inst = ctx
+        .instantiate_layout::<GroupTransformations>(&NoParams)
+        .expect("failed to instantiate layout");
+    let cell = inst.cell();
+
+    let mut group = Group::from(cell.as_ref());
+    assert_eq!(group.elements().count(), cell.elems().count());
+    assert_eq!(group.instances().count(), cell.insts().count());
+    assert_eq!(group.bbox(), cell.bbox());
+
+    let pt = Point::new(30, -50);
+    group.translate(pt);
+    assert_eq!(
+        group.bbox(),
+        cell.bbox().into_rect().translate_owned(pt).bbox()
+    );
+
+    let inst = ctx
+        .instantiate_layout::<SimpleRectangle>(&NoParams)
+        .expect("failed to instantiate layout");
+    let group = Group::from(inst.cell().as_ref());
+    let elts = group.elements().collect_vec();
+    assert_eq!(elts.len(), 1);
+    assert_eq!(
+        elts[0].inner.as_rect().unwrap(),
+        Rect::new(Point::new(100, 100), Point::new(200, 400))
+    );
+    assert_eq!(elts[0].layer, inst.cell().elems().next().unwrap().layer);
+}
+
+#[test]
+fn test_group_bbox_excluding() {
+    let ctx = setup_ctx();
+    let layers = ctx.layers();
+    let m1 = layers.get(Selector::Metal(1)).unwrap();
+    let outline = layers.get(Selector::Name("outline")).unwrap();
+
+    let mut inst = ctx
+        .instantiate_layout::<OutlinedRectangle>(&NoParams)
+        .expect("failed to instantiate layout");
+    inst.set_loc((1000, 0));
+    assert_eq!(
+        inst.bbox_excluding(&[outline]).into_rect(),
+        Rect::new(Point::new(1100, 100), Point::new(1200, 400))
+    );
+
+    let mut group = Group::new();
+    group.add_instance(inst);
+    group.add_rect(outline, Rect::new(Point::new(-50, -50), Point::new(50, 50)));
+    group.add_rect(m1, Rect::new(Point::new(0, 0), Point::new(20, 20)));
+
+    assert_eq!(
+        group.bbox().into_rect(),
+        Rect::new(Point::new(-50, -50), Point::new(1300, 500))
+    );
+    assert_eq!(
+        group.bbox_excluding(&[outline]).into_rect(),
+        Rect::new(Point::new(0, 0), Point::new(1200, 400))
+    );
+    assert!(group.bbox_excluding(&[m1, outline]).is_empty());
+}
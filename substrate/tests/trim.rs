@@ -1,7 +1,9 @@
 use common::{out_path, setup_ctx};
-use subgeom::{Point, Rect};
+use subgeom::{Path, Point, Rect, Shape};
 use substrate::component::{Component, NoParams};
+use substrate::layout::cell::Element;
 use substrate::layout::layers::selector::Selector;
+use substrate::layout::layers::LayerSpec;
 
 mod common;
 
@@ -51,3 +53,72 @@ fn trim_basic() {
     ctx.write_layout::<TrimBasic>(&NoParams, out_path("trim_basic", "layout.gds"))
         .expect("failed to write layout");
 }
+
+pub struct TrimPath;
+
+impl Component for TrimPath {
+    type Params = NoParams;
+
+    fn new(
+        _params: &Self::Params,
+        _ctx: &substrate::data::SubstrateCtx,
+    ) -> substrate::error::Result<Self> {
+        Ok(Self)
+    }
+
+    fn name(&self) -> arcstr::ArcStr {
+        arcstr::literal!("trim_path")
+    }
+
+    fn layout(
+        &self,
+        ctx: &mut substrate::layout::context::LayoutCtx,
+    ) -> substrate::error::Result<()> {
+        let l = ctx.layers();
+        let m1 = l.get(Selector::Metal(1))?;
+
+        // A U-shaped path that leaves the trim bounds and re-enters them.
+        let path = Path {
+            points: vec![
+                Point::new(0, 400),
+                Point::new(0, 0),
+                Point::new(400, 0),
+                Point::new(400, 400),
+            ],
+            width: 40,
+        };
+        let trim = Rect::new(Point::new(-100, 200), Point::new(500, 500));
+        for piece in Shape::Path(path).trim_split(&trim).unwrap() {
+            ctx.draw(Element::with_net_name("net", LayerSpec::drawing(m1), piece))?;
+        }
+
+        let elems = ctx.elems().collect::<Vec<_>>();
+        assert_eq!(elems.len(), 2, "expected both arms of the path to be kept");
+        for elem in elems.iter() {
+            assert_eq!(elem.net.as_deref(), Some("net"));
+            assert_eq!(elem.layer, LayerSpec::drawing(m1));
+        }
+        assert_eq!(
+            elems[0].inner,
+            Shape::Path(Path {
+                points: vec![Point::new(0, 400), Point::new(0, 200)],
+                width: 40,
+            })
+        );
+        assert_eq!(
+            elems[1].inner,
+            Shape::Path(Path {
+                points: vec![Point::new(400, 200), Point::new(400, 400)],
+                width: 40,
+            })
+        );
+        Ok(())
+    }
+}
+
+#[test]
+fn trim_path() {
+    let ctx = setup_ctx();
+    ctx.write_layout::<TrimPath>(&NoParams, out_path("trim_path", "layout.gds"))
+        .expect("failed to write layout");
+}
@@ -3,7 +3,7 @@ use subgeom::{Point, Rect};
 use substrate::component::{Component, NoParams};
 use substrate::data::SubstrateCtx;
 use substrate::layout::context::LayoutCtx;
-use substrate::layout::elements::via::{Via, ViaParams};
+use substrate::layout::elements::via::{Via, ViaExpansion, ViaParams};
 use substrate::layout::layers::selector::Selector;
 
 mod common;
@@ -46,9 +46,53 @@ impl Component for MyRouting {
     }
 }
 
+pub struct ViaTooSmall;
+
+impl Component for ViaTooSmall {
+    type Params = NoParams;
+    fn new(_params: &Self::Params, _ctx: &SubstrateCtx) -> substrate::error::Result<Self> {
+        Ok(Self)
+    }
+    fn name(&self) -> ArcStr {
+        arcstr::literal!("via_too_small")
+    }
+
+    fn layout(&self, ctx: &mut LayoutCtx) -> substrate::error::Result<()> {
+        let m0 = ctx.layers().get(Selector::Metal(0))?;
+        let m1 = ctx.layers().get(Selector::Metal(1))?;
+
+        let r0 = Rect::new(Point::zero(), Point::new(100, 100));
+        let r1 = Rect::new(Point::zero(), Point::new(100, 100));
+        ctx.draw_rect(m0, r0);
+        ctx.draw_rect(m1, r1);
+
+        let via = ctx.instantiate::<Via>(
+            &ViaParams::builder()
+                .layers(m0, m1)
+                .geometry(r0, r1)
+                .expand(ViaExpansion::None)
+                .build(),
+        )?;
+        ctx.draw(via)?;
+
+        Ok(())
+    }
+}
+
 #[test]
 fn via_basic() {
     let ctx = setup_ctx();
     ctx.write_layout::<MyRouting>(&NoParams, out_path("via_basic", "layout.gds"))
         .expect("failed to write layout");
 }
+
+#[test]
+fn via_no_expansion_reports_min_size() {
+    let ctx = setup_ctx();
+    let err = ctx
+        .write_layout::<ViaTooSmall>(&NoParams, out_path("via_no_expansion", "layout.gds"))
+        .expect_err("via should not fit without expansion");
+    let msg = err.to_string();
+    assert!(msg.contains("the bottom layer must be at least 170x170"));
+    assert!(msg.contains("the top layer at least 230x290"));
+}